@@ -0,0 +1,67 @@
+//! Times `GameState::step` with a crowded field: `cargo bench --bench step`.
+
+use std::time::{Duration, Instant};
+
+use your_excellency::enemy::{remove_dead, Enemy, EnemyKind};
+use your_excellency::GameState;
+
+const ENEMIES: usize = 10_000;
+const STEPS: u32 = 600;
+
+/// Slow enough to stay on the field for the whole benchmark unless it's killed.
+fn enemy(index: usize, hp: f32) -> Enemy {
+    Enemy {
+        speed: 0.01,
+        kind: match index % 10 {
+            0 => EnemyKind::Healer,
+            _ => EnemyKind::Grunt,
+        },
+        archetype: "grunt".to_owned(),
+        ..Enemy::new(hp, 20. + 80. * index as f32 / ENEMIES as f32)
+    }
+}
+
+/// Steps a field of `ENEMIES` with `hp` each, printing the average time per step.
+fn run(label: &str, hp: f32) {
+    let mut state = GameState::with_seed(0);
+    state.add_enemies((0..ENEMIES).map(|index| enemy(index, hp)));
+    let started = Instant::now();
+    for _ in 0..STEPS {
+        state.step(1. / 60.);
+        state.take_events();
+    }
+    let per_step = started.elapsed() / STEPS;
+    println!(
+        "{label}, {} enemies: {per_step:?} per step, {} still on the field",
+        ENEMIES,
+        state.enemies().len()
+    );
+}
+
+/// Times taking a few dead enemies off a crowded field, what every kill costs.
+fn remove_a_few_dead() {
+    let field: Vec<Enemy> = (0..ENEMIES).map(|index| enemy(index, 1e9)).collect();
+    let mut took = Duration::ZERO;
+    for _ in 0..STEPS {
+        let mut enemies = field.clone();
+        for enemy in enemies.iter_mut().step_by(ENEMIES / 10) {
+            enemy.hp.current = 0.;
+        }
+        let started = Instant::now();
+        let killed = remove_dead(&mut enemies);
+        took += started.elapsed();
+        assert_eq!(killed.len(), 10);
+    }
+    println!(
+        "Removing 10 dead of {} enemies: {:?}",
+        ENEMIES,
+        took / STEPS
+    );
+}
+
+fn main() {
+    run("Too tough to kill", 1e9);
+    // Kills every now and then, taking the dead off a crowded field
+    run("Dying", 1.);
+    remove_a_few_dead();
+}
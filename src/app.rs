@@ -1,469 +1,1743 @@
-use egui::{Color32, Pos2, Sense};
+#[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+mod hot_reload;
+mod migration;
+mod plugins;
+#[cfg(feature = "scripting")]
+mod scripting;
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crate::ascendancy::{self, Ascendancy, ASCENDANCY_KEY};
+use crate::audio::{Audio, MusicMood};
+use crate::automation::{self, Automation, Automations};
+use crate::boss_phases::{self, BossPhase, PhaseAction};
+use crate::character_sheet::CharacterSheet;
+use crate::combat::{
+    AttackKind, AttackOutcome, AttackPhase, BasicAttack, CastMode, DamageSpec, DamageType,
+    HitPoints, TargetingStrategy,
+};
+use crate::combat_log::{CombatLog, FloatingTexts};
+use crate::content::Content;
+use crate::crowd_control::{Control, CrowdControl};
+use crate::daily;
+#[cfg(feature = "discord")]
+use crate::discord::DiscordPresence;
+use crate::dying::DyingEnemies;
+use crate::effects::{Effects, Stat};
+use crate::enemy::{
+    advance, around, count_in_range, remove_dead, remove_escaped, sort_by_distance, Enemy,
+    EnemyKind, HEALING_PER_SECOND, HEAL_RADIUS,
+};
+use crate::events::{DamageSource, GameEvent};
+use crate::excellency::Excellency;
+use crate::export::{self, StatSample, StatSamples};
+#[cfg(feature = "gamepad")]
+use crate::gamepad::Gamepad;
+use crate::graphs::{DamageBySource, HpHistory, WaveDamage};
+use crate::i18n::trf;
+use crate::incidents::{self, Incident, Incidents, BLESSING_HEAL};
+use crate::juice::Juice;
+#[cfg(feature = "leaderboard")]
+use crate::leaderboard::{Leaderboard, Submission};
+use crate::loadouts::Loadout;
+use crate::modes::{self, GameMode};
+use crate::mutators::Mutator;
+use crate::names;
+use crate::new_game_plus;
+use crate::particles::Particles;
+use crate::profiler::Profiler;
+use crate::profiles::{self, Profiles, PROFILES_KEY};
+use crate::records::{HistorySort, NewBests, Records, RunRecord, RunResult, RECORDS_KEY};
+use crate::relics::{self, Reaction, Relic};
+use crate::rng::GameRng;
+use crate::saves;
+use crate::settings::{Action, AutoContinue, Settings, WhenUnfocused, SETTINGS_KEY};
+use crate::snapshot::Snapshot;
+use crate::spawner::{EnemySpawner, Overflow, ScalingCurve, SpawnPattern, SpawnerScaling};
+use crate::stat_sheet::StatSheet;
+use crate::talents::{Talent, LEVELS_PER_TALENT};
+#[cfg(feature = "telemetry")]
+use crate::telemetry::{Build, Report, Telemetry};
+use crate::timer::Timer;
+use crate::toasts::{ToastKind, Toasts};
+use crate::treasure;
+use crate::ui::enemies::{EnemyFilter, EnemySort};
+use crate::ui::Tab;
+use crate::ultimate::{self, Ultimate};
+use crate::undo::EditHistory;
+use crate::victory::Victory;
+use crate::waves::{WavePhase, Waves, EARLY_GOLD_PER_SECOND};
+use crate::world::{Components, Entities, Entity};
+#[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+use hot_reload::ContentWatcher;
+use migration::SaveFormat;
+pub use migration::{SaveError, SAVE_VERSION};
+pub use plugins::GamePlugin;
+#[cfg(feature = "scripting")]
+use scripting::Scripts;
+
+/// Buffers `step` refills every time, kept between steps to reuse their allocations.
+#[derive(Default)]
+pub(crate) struct Scratch {
+    /// Index and distance of every healer on the field.
+    healers: Vec<(usize, f32)>,
+    /// Ids of the enemies on the field, sorted.
+    alive: Vec<Entity>,
+}
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
 pub struct GameState {
-    excellency: Excellency,
-    enemies: Vec<Enemy>,
-    enemy_spawner: EnemySpawner,
+    /// Layout version of this state when serialized, see `migration`.
+    pub(crate) save_version: u32,
+    pub(crate) excellency: Excellency,
+    /// Sorted by distance between steps, see `enemy::sort_by_distance`.
+    pub(crate) enemies: Vec<Enemy>,
+    pub(crate) entities: Entities,
+    /// Timed debuffs of the enemies on the field.
+    pub(crate) debuffs: Components<Effects>,
+    /// Stuns and freezes of the enemies on the field, with their diminishing returns.
+    pub(crate) crowd_control: Components<CrowdControl>,
+    /// The phase every boss on the field has reached.
+    pub(crate) boss_phases: Components<BossPhase>,
+    /// Talents offered for the player to pick one, the game waits while there are.
+    pub(crate) talent_choice: Option<Vec<Talent>>,
+    pub(crate) enemy_spawner: EnemySpawner,
+    /// Spawners running alongside `enemy_spawner`, each on its own timer and stats.
+    pub(crate) extra_spawners: Vec<EnemySpawner>,
+    pub(crate) waves: Waves,
+    pub(crate) incidents: Incidents,
+    /// Applied when the run started.
+    pub(crate) mutators: Vec<Mutator>,
+    #[serde(default = "modes::before_modes")]
+    pub(crate) mode: GameMode,
+    /// The day whose daily challenge this run is, see `daily`.
+    pub(crate) daily: Option<u64>,
+    /// Name of the campaign stage this run plays, see `with_stage`.
+    pub(crate) stage: Option<String>,
+    /// Ends the run in victory once met, none for an endless run.
+    pub(crate) victory: Option<Victory>,
+    pub(crate) victorious: bool,
+    /// What a campaign stage spawns from instead of `content`, rebuilt from it.
+    #[serde(skip)]
+    pub(crate) stage_content: Option<Arc<Content>>,
+    /// New Game+ cycles started before reaching this run, 0 for a regular one.
+    pub(crate) new_game_plus: u32,
+    /// The milestone boss fell, the next New Game+ cycle can start.
+    pub(crate) milestone_cleared: bool,
+    /// Auto-continue stopped for the rest of this run after HP fell below its threshold.
+    pub(crate) auto_continue_stopped: bool,
+    /// Damage dealt and taken, indexed by wave number - 1.
+    pub(crate) wave_damage: Vec<WaveDamage>,
+    pub(crate) damage_by_source: DamageBySource,
+    /// The part of `damage_by_source` that was true damage.
+    pub(crate) true_damage_by_source: DamageBySource,
+    /// Seconds since the run started.
+    pub(crate) elapsed: f32,
+    pub(crate) kills: u32,
+    /// The Excellency fell, the simulation stays frozen until a new run starts.
+    pub(crate) defeated: bool,
+    pub(crate) paused: bool,
+    /// Whether `paused` was set by the window losing focus, to be lifted once it's back.
+    #[serde(skip)]
+    pub(crate) auto_paused: bool,
+    /// Minimized with `Settings::low_power_background` on, stepping in `COARSE_DT` steps
+    /// unless the run tracks records.
+    #[serde(skip)]
+    pub(crate) low_power: bool,
+    /// Multiplier on how fast simulated time passes.
+    pub(crate) game_speed: f32,
+    /// Frame time not yet consumed by fixed simulation steps.
+    #[serde(skip)]
+    pub(crate) accumulator: f32,
+    #[serde(skip)]
+    pub(crate) scratch: Scratch,
+    pub(crate) rng: GameRng,
+    /// Seed typed into the run panel, used by the next restart.
+    #[serde(skip)]
+    pub(crate) next_seed: u64,
+    /// Mutators picked in the run panel, used by the next restart.
+    #[serde(skip)]
+    pub(crate) next_mutators: Vec<Mutator>,
+    /// Mode picked in the run panel, used by the next restart.
+    #[serde(skip)]
+    pub(crate) next_mode: GameMode,
+    /// Win condition picked in the run panel, used by the next restart.
+    #[serde(skip)]
+    pub(crate) next_victory: Option<Victory>,
+    #[serde(skip)]
+    pub(crate) show_save_window: bool,
+    /// Name given to the next save.
+    #[serde(skip)]
+    pub(crate) save_name: String,
+    /// Why the last attempt to load a save failed.
+    #[serde(skip)]
+    pub(crate) load_error: Option<String>,
+    /// JSON pasted into the import box of the save window.
+    #[serde(skip)]
+    pub(crate) import_text: String,
+    /// Persisted on its own under `SETTINGS_KEY`.
+    #[serde(skip)]
+    pub(crate) settings: Settings,
+    /// Persisted on its own under `RECORDS_KEY`.
+    #[serde(skip)]
+    pub(crate) records: Records,
+    /// Persisted on its own under `ASCENDANCY_KEY`.
+    #[serde(skip)]
+    pub(crate) ascendancy: Ascendancy,
+    /// Id of the profile playing, every key it stores is namespaced by it.
+    #[serde(skip)]
+    pub(crate) profile: u32,
+    /// Persisted on its own under `PROFILES_KEY`.
+    #[serde(skip)]
+    pub(crate) profiles: Profiles,
+    #[serde(skip)]
+    pub(crate) show_profile_window: bool,
+    /// Name typed in for the next new profile.
+    #[serde(skip)]
+    pub(crate) profile_name: String,
+    /// Name the spawners are saved under as a preset.
+    #[serde(skip)]
+    pub(crate) preset_name: String,
+    #[serde(skip)]
+    pub(crate) edits: EditHistory,
+    /// The loadout last swapped in this run.
+    pub(crate) loadout: Option<String>,
+    /// Name the attacks are saved under as a loadout.
+    #[serde(skip)]
+    pub(crate) loadout_name: String,
+    /// Records broken by this run, set when it ends.
+    #[serde(skip)]
+    pub(crate) new_bests: Option<NewBests>,
+    #[serde(skip)]
+    pub(crate) show_settings_window: bool,
+    #[serde(skip)]
+    pub(crate) show_history_window: bool,
+    #[serde(skip)]
+    pub(crate) show_stat_sheet_window: bool,
+    #[serde(skip)]
+    pub(crate) show_character_sheet_window: bool,
+    #[serde(skip)]
+    pub(crate) show_campaign_window: bool,
+    #[serde(skip)]
+    pub(crate) show_automation_window: bool,
+    #[serde(skip)]
+    pub(crate) show_ascendancy_window: bool,
+    #[serde(skip)]
+    pub(crate) show_relics_window: bool,
+    #[serde(skip)]
+    pub(crate) profiler: Profiler,
+    #[serde(skip)]
+    pub(crate) history_sort: HistorySort,
+    #[cfg(feature = "leaderboard")]
+    #[serde(skip)]
+    pub(crate) leaderboard: Leaderboard,
+    #[cfg(feature = "telemetry")]
+    #[serde(skip)]
+    pub(crate) telemetry: Telemetry,
+    #[cfg(feature = "discord")]
+    #[serde(skip)]
+    pub(crate) discord: DiscordPresence,
+    #[cfg(feature = "leaderboard")]
+    #[serde(skip)]
+    pub(crate) show_leaderboard_window: bool,
+    #[cfg(feature = "gamepad")]
+    #[serde(skip)]
+    pub(crate) gamepad: Gamepad,
+    /// The action waiting for the next key press to become its binding.
+    #[serde(skip)]
+    pub(crate) rebinding: Option<Action>,
+    /// Filled by the simulation, drained by the UI every frame.
+    #[serde(skip)]
+    pub(crate) events: Vec<GameEvent>,
+    #[serde(skip)]
+    pub(crate) audio: Audio,
+    /// Sampled once per simulated second, starts empty after loading.
+    #[serde(skip)]
+    pub(crate) hp_history: HpHistory,
+    /// Sampled along with `hp_history` but kept for the whole run, for the CSV export.
+    #[serde(skip)]
+    pub(crate) stat_samples: StatSamples,
+    /// The enemy under the cursor on the battlefield.
+    #[serde(skip)]
+    pub(crate) hovered: Option<Entity>,
+    /// Picked to be focused down, see the enemy context menu.
+    pub(crate) focused: Option<Entity>,
+    /// Highlighted by the player to keep an eye on.
+    pub(crate) marked: Vec<Entity>,
+    /// Shown in the inspection window.
+    #[serde(skip)]
+    pub(crate) inspected: Option<Entity>,
+    /// Enemy whose context menu is open on the battlefield.
+    #[serde(skip)]
+    pub(crate) menu_enemy: Option<Entity>,
+    #[serde(skip)]
+    pub(crate) combat_log: CombatLog,
+    #[serde(skip)]
+    pub(crate) floating_texts: FloatingTexts,
+    #[serde(skip)]
+    pub(crate) dying: DyingEnemies,
+    #[serde(skip)]
+    pub(crate) particles: Particles,
+    #[serde(skip)]
+    pub(crate) juice: Juice,
+    #[serde(skip)]
+    pub(crate) enemy_sort: EnemySort,
+    #[serde(skip)]
+    pub(crate) enemy_filter: EnemyFilter,
+    /// Whether the touch layout was used last frame, the style is re-applied when it flips.
+    #[serde(skip)]
+    pub(crate) touch_layout: bool,
+    /// Panel shown by the touch layout, which has room for only one.
+    #[serde(skip)]
+    pub(crate) tab: Tab,
+    /// Enemy archetypes, waves and bosses, loaded once at startup.
+    #[serde(skip)]
+    pub(crate) content: Arc<Content>,
+    /// Why content files were replaced by the built-in ones.
+    #[serde(skip)]
+    pub(crate) content_errors: Vec<String>,
+    /// Abilities loaded from the scripts folder at startup.
+    #[cfg(feature = "scripting")]
+    #[serde(skip)]
+    pub(crate) scripts: Scripts,
+    /// Extensions notified of what happens in the run, see `plugins`.
+    #[serde(skip)]
+    pub(crate) plugins: Vec<Box<dyn GamePlugin>>,
+    /// What the equipped relics answered events with, carried out in `react`.
+    #[serde(skip)]
+    pub(crate) reactions: Vec<Reaction>,
+    #[serde(skip)]
+    pub(crate) toasts: Toasts,
+    #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+    #[serde(skip)]
+    pub(crate) watcher: Option<ContentWatcher>,
 }
 
-#[derive(serde::Deserialize, serde::Serialize)]
-struct EnemySpawner {
-    timer: Timer,
-    maximum_hp: f32,
-    speed: f32,
-    damage: f32,
-}
+const MIN_GAME_SPEED: f32 = 0.25;
+const MAX_GAME_SPEED: f32 = 8.;
 
-#[derive(serde::Deserialize, serde::Serialize)]
-struct Timer {
-    total: f32,
-    remaining: f32,
-    has_just_finished: bool,
-    one_shot: bool,
-    paused: bool,
-}
+/// Length of one simulation step, the simulation runs at 50 Hz regardless of the frame rate.
+const FIXED_DT: f32 = 1. / 50.;
+/// Step length while in low power, timers catch up on everything that finished within it.
+/// Not the same as `FIXED_DT` steps for a seed, so runs tracking records never take it.
+const COARSE_DT: f32 = 0.25;
+/// Upper bound on catch-up work after a long hitch, anything beyond is dropped.
+const MAX_STEPS_PER_FRAME: u32 = 500;
 
-impl Timer {
-    pub fn new(total: f32) -> Self {
-        Self {
-            total,
-            remaining: total,
-            has_just_finished: false,
-            one_shot: false,
-            paused: false,
+impl GameState {
+    /// Feeds the real frame time into the accumulator and runs as many fixed steps as fit.
+    pub fn tick(&mut self, ctx: &egui::Context) {
+        if self.paused || self.talent_choice.is_some() {
+            return;
+        }
+        self.accumulator += ctx.input(|i| i.unstable_dt) * self.game_speed;
+        let step = match self.low_power && !self.mode.tracks_records() {
+            true => COARSE_DT,
+            false => FIXED_DT,
+        };
+        let mut steps = 0;
+        while self.accumulator >= step {
+            if steps == MAX_STEPS_PER_FRAME {
+                self.accumulator = 0.;
+                break;
+            }
+            self.step(step);
+            self.accumulator -= step;
+            steps += 1;
+            if self.talent_choice.is_some() {
+                self.accumulator = 0.;
+                break;
+            }
         }
     }
 
-    pub fn remaining_fraction(&self) -> f32 {
-        self.remaining / self.total
+    /// Pauses while the window is unfocused if the settings ask for it, resuming once it's
+    /// focused again unless the player paused on their own.
+    pub(crate) fn auto_pause(&mut self, focused: bool) {
+        let pause = !focused && self.settings.when_unfocused == WhenUnfocused::Pause;
+        if pause && !self.paused {
+            self.paused = true;
+            self.auto_paused = true;
+        } else if focused && self.auto_paused {
+            self.paused = false;
+            self.auto_paused = false;
+        }
     }
 
-    pub fn tick(&mut self, delta: f32) {
-        if !self.paused {
-            self.remaining -= delta;
-            if self.remaining <= 0. {
-                self.has_just_finished = true;
-                self.remaining += self.total; // Necessary because of the case when we aren't exactly 0
-                if self.one_shot {
-                    self.paused = true
-                }
-            } else {
-                self.has_just_finished = false
-            }
+    /// With auto-continue on, starts the next campaign stage once this one is won. Pauses
+    /// instead, and stops for the rest of the run, once HP falls below its threshold. Checked
+    /// every step, so a long frame can't slip past the threshold.
+    fn auto_continue(&mut self) {
+        let auto_continue = &self.settings.auto_continue;
+        if !auto_continue.enabled || self.stage.is_none() || self.auto_continue_stopped {
+            return;
+        }
+        if auto_continue.is_unsafe(&self.excellency.hp) {
+            self.auto_continue_stopped = true;
+            self.paused = !self.defeated && !self.victorious;
+            self.toasts.push(
+                ToastKind::Info,
+                trf(
+                    "HP fell below {}%, auto-continue stopped.",
+                    &[format!("{:.0}", auto_continue.min_hp * 100.)],
+                ),
+            );
+            return;
+        }
+        if let Some(next) = self.victorious.then(|| self.next_stage()).flatten() {
+            self.start_stage(&next);
         }
     }
 
-    pub fn has_just_finished(&self) -> bool {
-        self.has_just_finished
+    pub(crate) fn run_result(&self) -> RunResult {
+        RunResult {
+            kills: self.kills,
+            survival: self.elapsed,
+            wave: self.waves.number,
+        }
     }
 
-    // pub fn pause(&mut self) {
-    //     self.paused = true
-    // }
+    /// Ends the run in victory, paying its laurels and recording how well a campaign stage went.
+    fn win(&mut self) {
+        self.victorious = true;
+        if !self.mode.tracks_records() {
+            return;
+        }
+        self.records.victories += 1;
+        self.records.laurels += self.victory.map_or(0, |victory| victory.laurels());
+        if let (Some(stage), Some(stars)) = (&self.stage, self.stars()) {
+            self.records.complete_stage(stage, stars, self.elapsed);
+        }
+        self.finish();
+    }
 
-    // pub fn unpause(&mut self) {
-    //     self.paused = false
-    // }
-}
+    fn defeat(&mut self) {
+        self.defeated = true;
+        if !self.mode.tracks_records() {
+            return;
+        }
+        self.finish();
+    }
 
-impl GameState {
-    pub fn tick(&mut self, ctx: &egui::Context) {
-        let delta = ctx.input(|i| i.stable_dt);
+    /// Records the ended run among the bests and in the history, and pays its shards.
+    fn finish(&mut self) {
+        let shards = ascendancy::shards(self.waves.number, self.new_game_plus);
+        if shards > 0 {
+            self.ascendancy.shards += shards;
+            self.toasts.push(
+                ToastKind::Info,
+                trf("+{} Ascendancy shards", &[shards.to_string()]),
+            );
+        }
+        self.new_bests = Some(self.records.submit(&self.run_result()));
+        if let Some(day) = self.daily {
+            self.records.complete_daily(day, &self.run_result());
+        }
+        self.records.remember(RunRecord {
+            seed: self.rng.seed(),
+            finished_at: saves::now(),
+            wave: self.waves.number,
+            duration: self.elapsed,
+            kills: self.kills,
+            basic_attack: self.excellency.basic_attack.snapshot(),
+            big_attack: self.excellency.big_attack.snapshot(),
+            mutators: self.mutators.clone(),
+        });
+        #[cfg(feature = "leaderboard")]
+        self.leaderboard.submit(
+            &self.settings.leaderboard.url,
+            Submission::new(
+                self.settings.leaderboard.player_name.clone(),
+                self.rng.seed(),
+                self.waves.number,
+                self.elapsed,
+            ),
+        );
+        #[cfg(feature = "telemetry")]
+        self.telemetry
+            .record(&self.settings.telemetry, self.telemetry_report());
+    }
 
-        let mut enemies = vec![];
+    #[cfg(feature = "telemetry")]
+    fn telemetry_report(&self) -> Report {
+        Report {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            seed: self.rng.seed(),
+            wave: self.waves.number,
+            duration: self.elapsed,
+            kills: self.kills,
+            victorious: self.victorious,
+            mutators: self.mutators.clone(),
+            build: Build {
+                basic_attack: self.excellency.basic_attack.snapshot(),
+                big_attack: self.excellency.big_attack.snapshot(),
+                talents: self.excellency.talents.clone(),
+                auras: self.excellency.auras.clone(),
+                upgrades: self.excellency.upgrades.len(),
+            },
+        }
+    }
 
-        for enemy in self.enemies.iter_mut() {
-            match enemy.tick(delta) {
-                EnemyAfterTick::Normal => enemies.push(enemy.clone()), // TODO: Clone isn't strictly necessary here
-                EnemyAfterTick::ReachedExcellency => self.excellency.hp.take_damage(enemy.damage),
-            }
+    fn current_wave_damage(&mut self) -> &mut WaveDamage {
+        let index = self.waves.number.saturating_sub(1) as usize;
+        if self.wave_damage.len() <= index {
+            self.wave_damage.resize(index + 1, WaveDamage::default());
         }
+        &mut self.wave_damage[index]
+    }
 
-        self.enemy_spawner.timer.tick(delta);
-        if self.enemy_spawner.timer.has_just_finished() {
-            enemies.push(Enemy {
-                hp: HitPoints::new_full(self.enemy_spawner.maximum_hp),
-                damage: self.enemy_spawner.damage,
-                speed: self.enemy_spawner.speed,
-                distance: Distance::start(),
-                is_hovered: false,
-            })
+    /// Hands `event` to the run's statistics, equipped relics and plugins, then queues it for
+    /// the frame.
+    fn emit(&mut self, event: GameEvent) {
+        // The daily challenge is the same run for everyone
+        if self.daily.is_none() {
+            for relic in self.records.relics.equipped() {
+                self.reactions.extend(relic.react(&event));
+            }
+        }
+        match &event {
+            GameEvent::DamageDealt {
+                amount,
+                source,
+                damage_type,
+            } => {
+                self.current_wave_damage().dealt += amount;
+                self.damage_by_source.add(*source, *amount);
+                if *damage_type == DamageType::True {
+                    self.true_damage_by_source.add(*source, *amount);
+                }
+                let ultimate = &mut self.excellency.ultimate;
+                ultimate.charge.add(amount * ultimate.per_damage);
+            }
+            GameEvent::PlayerHit { damage, .. } => self.current_wave_damage().taken += damage,
+            GameEvent::EnemyKilled(enemy) => {
+                self.kills += 1;
+                let ultimate = &mut self.excellency.ultimate;
+                ultimate.charge.add(ultimate.per_kill);
+                let content = self.stage_content.as_ref().unwrap_or(&self.content);
+                let milestone = new_game_plus::milestone_wave(content);
+                if enemy.is_boss && milestone.is_some_and(|wave| self.waves.number >= wave) {
+                    self.milestone_cleared = true;
+                }
+            }
+            GameEvent::AttackFired { .. }
+            | GameEvent::BossPhaseEntered { .. }
+            | GameEvent::DamageTicked { .. }
+            | GameEvent::EnemySpawned(_)
+            | GameEvent::WaveStarted { .. }
+            | GameEvent::WaveCalledEarly { .. }
+            | GameEvent::RelicFound(_)
+            | GameEvent::EnemyEscaped(_)
+            | GameEvent::Dodged
+            | GameEvent::AttackInterrupted { .. }
+            | GameEvent::UltimateFired { .. }
+            | GameEvent::IncidentStruck(_) => {}
+        }
+        for plugin in &mut self.plugins {
+            plugin.on_event(&event);
         }
+        self.events.push(event);
+    }
 
-        enemies.sort_by(|a, b| {
-            a.distance
-                .partial_cmp(&b.distance)
-                .expect("Compared two f32's")
-        });
+    /// Everything emitted since the last call, oldest first. The UI drains these every frame,
+    /// code stepping the simulation on its own should too.
+    pub fn take_events(&mut self) -> Vec<GameEvent> {
+        std::mem::take(&mut self.events)
+    }
 
-        self.excellency.basic_attack.cooldown_timer.tick(delta);
-        if self
-            .excellency
-            .basic_attack
-            .cooldown_timer
-            .has_just_finished()
-        {
-            let mut targets_hit = 0;
-            enemies = enemies
-                .into_iter()
-                .filter_map(|mut enemy| {
-                    if targets_hit >= self.excellency.basic_attack.max_targets {
-                        return Some(enemy);
-                    }
-                    if enemy.distance.0 <= self.excellency.basic_attack.range {
-                        enemy.hp.take_damage(self.excellency.basic_attack.damage);
-                        targets_hit += 1;
-                        if enemy.hp.current <= 0. {
-                            return None;
-                        } else {
-                            return Some(enemy);
-                        }
-                    }
-                    Some(enemy)
-                })
-                .collect();
+    /// Collects the bounties of an attack's kills, heals by the life steal and reports the
+    /// damage as dealt by `source`. Everything that damages enemies goes through here.
+    fn resolve(&mut self, outcome: AttackOutcome, source: DamageSource) {
+        if outcome.damage_dealt > 0. {
+            let healed = outcome.damage_dealt * self.excellency.life_steal;
+            self.excellency.hp.heal(healed);
         }
+        let parts = [
+            (
+                outcome.damage_dealt - outcome.true_damage,
+                DamageType::Normal,
+            ),
+            (outcome.true_damage, DamageType::True),
+        ];
+        for (amount, damage_type) in parts {
+            if amount > 0. {
+                self.emit(GameEvent::DamageDealt {
+                    amount,
+                    source,
+                    damage_type,
+                });
+            }
+        }
+        for enemy in outcome.killed {
+            self.excellency.collect_bounty(&enemy);
+            self.drop_relic(&enemy);
+            self.emit(GameEvent::EnemyKilled(enemy));
+        }
+    }
 
-        self.excellency.big_attack.cooldown_timer.tick(delta);
-        if self
-            .excellency
-            .big_attack
-            .cooldown_timer
-            .has_just_finished()
-        {
-            let mut targets_hit = 0;
-            enemies = enemies
-                .into_iter()
-                .filter_map(|mut enemy| {
-                    if targets_hit >= self.excellency.big_attack.max_targets {
-                        return Some(enemy);
+    /// Rolls whether `enemy` leaves a relic behind, only in runs counting towards the records.
+    fn drop_relic(&mut self, enemy: &Enemy) {
+        let chance = relics::drop_chance(enemy);
+        if chance <= 0. || !self.mode.tracks_records() {
+            return;
+        }
+        // A stream of its own like the names, so drops don't shift any other roll of the seed
+        let seed = self.rng.seed().wrapping_add(enemy.id.0);
+        let mut rng = GameRng::new(seed.wrapping_add(relics::DROP_STREAM));
+        if rng.chance(chance) {
+            let relic = Relic::roll(&mut rng);
+            self.records.relics.add(relic);
+            self.emit(GameEvent::RelicFound(relic));
+        }
+    }
+
+    /// Carries out what the equipped relics answered the events so far with. Explosions can
+    /// kill and set off further ones, an echo doesn't echo again.
+    fn react(&mut self, enemies: &mut Vec<Enemy>) {
+        while !self.reactions.is_empty() {
+            for reaction in std::mem::take(&mut self.reactions) {
+                match reaction {
+                    Reaction::Echo { kind, damage } => {
+                        let attack = self.excellency.attack_mut(kind);
+                        let full = attack.damage;
+                        attack.damage *= damage;
+                        let mut outcome = self.excellency.attack(kind).fire(
+                            enemies,
+                            &self.excellency.buffs,
+                            self.focused,
+                        );
+                        self.excellency.attack_mut(kind).damage = full;
+                        self.emit(GameEvent::AttackFired {
+                            kind,
+                            hits: std::mem::take(&mut outcome.hits),
+                        });
+                        self.reactions
+                            .retain(|reaction| !matches!(reaction, Reaction::Echo { .. }));
+                        self.resolve(outcome, DamageSource::Attack(kind));
                     }
-                    if enemy.distance.0 <= self.excellency.big_attack.range {
-                        enemy.hp.take_damage(self.excellency.big_attack.damage);
-                        targets_hit += 1;
-                        if enemy.hp.current <= 0. {
-                            return None;
-                        } else {
-                            return Some(enemy);
+                    Reaction::Explode { distance, damage } => {
+                        let mut damage_dealt = 0.;
+                        for index in around(enemies, distance, relics::EXPLOSION_RADIUS) {
+                            damage_dealt += enemies[index].take_damage(damage);
                         }
+                        let outcome = AttackOutcome {
+                            killed: remove_dead(enemies),
+                            damage_dealt,
+                            true_damage: 0.,
+                            hits: vec![],
+                        };
+                        self.resolve(outcome, DamageSource::Relic);
                     }
-                    Some(enemy)
-                })
-                .collect();
+                    Reaction::Heal(hp) => self.excellency.hp.heal(hp),
+                    Reaction::Gold(gold) => self.excellency.gold += gold,
+                }
+            }
         }
+    }
 
-        self.enemies = enemies;
+    /// Hands `enemy` its handle and name and lets it onto the field, unsorted.
+    fn join(&mut self, mut enemy: Enemy, enemies: &mut Vec<Enemy>) {
+        enemy.id = self.entities.spawn();
+        // A stream of its own, so names don't shift any other roll of the seed
+        let mut names_rng = GameRng::new(self.rng.seed().wrapping_add(enemy.id.0));
+        enemy.name = names::generate(&enemy, &mut names_rng);
+        self.emit(GameEvent::EnemySpawned(enemy.clone()));
+        enemies.push(enemy);
     }
-}
 
-#[derive(serde::Deserialize, serde::Serialize)]
-struct Excellency {
-    hp: HitPoints,
-    basic_attack: BasicAttack,
-    big_attack: BasicAttack,
-}
+    /// Moves the bosses on to the phases their HP has fallen into and carries out what
+    /// entering them does.
+    fn advance_boss_phases(&mut self, enemies: &mut Vec<Enemy>) {
+        let mut summons = vec![];
+        for boss in enemies.iter_mut().filter(|enemy| enemy.is_boss) {
+            for phase in self.boss_phases.entry(boss.id).update(&boss.hp) {
+                for &action in boss_phases::actions(phase) {
+                    match action {
+                        PhaseAction::Enrage => boss.speed *= boss_phases::ENRAGE_SPEED,
+                        PhaseAction::Summon => summons.push(boss.distance.0),
+                    }
+                    self.emit(GameEvent::BossPhaseEntered {
+                        boss: boss.display_name().to_owned(),
+                        phase,
+                        action,
+                    });
+                }
+            }
+        }
+        if summons.is_empty() {
+            return;
+        }
+        let content = Arc::clone(self.stage_content.as_ref().unwrap_or(&self.content));
+        for distance in summons {
+            for _ in 0..boss_phases::SUMMONED_ADDS {
+                let (name, archetype) = content.pick(self.waves.number, &mut self.rng);
+                let mut add =
+                    self.enemy_spawner
+                        .spawn(self.elapsed, name, archetype, &mut self.rng);
+                add.distance.0 = distance;
+                self.join(add, enemies);
+            }
+        }
+        sort_by_distance(enemies);
+    }
 
-#[derive(serde::Deserialize, serde::Serialize)]
-struct BasicAttack {
-    cooldown_timer: Timer,
-    damage: f32,
-    range: f32,
-    max_targets: usize,
-}
+    /// Debuffs the enemies within reach of the Excellency's auras.
+    fn apply_auras(&mut self, enemies: &[Enemy]) {
+        for &aura in &self.excellency.auras {
+            let radius = self.excellency.aura_radius(aura);
+            let Some(debuff) = aura.debuff(radius) else {
+                continue;
+            };
+            let in_range = count_in_range(enemies, radius);
+            for enemy in &enemies[..in_range] {
+                self.debuffs.entry(enemy.id).apply(debuff.clone());
+            }
+        }
+    }
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
-struct Enemy {
-    hp: HitPoints,
-    damage: f32,
-    speed: f32,
-    distance: Distance,
-    is_hovered: bool,
-}
+    /// Counts down the debuffs of every enemy and deals the damage over time that came
+    /// due, removing the ones that burnt down.
+    fn tick_debuffs(&mut self, delta: f32, enemies: &mut Vec<Enemy>) -> AttackOutcome {
+        let mut damage_dealt = 0.;
+        for enemy in enemies.iter_mut() {
+            let Some(debuffs) = self.debuffs.get_mut(enemy.id) else {
+                continue;
+            };
+            let damage = debuffs.tick(delta);
+            if damage > 0. {
+                // Damage over time goes through armor and shields
+                let landed = enemy.take_hit(DamageSpec::Flat(damage), DamageType::True);
+                damage_dealt += landed;
+                self.emit(GameEvent::DamageTicked {
+                    amount: landed,
+                    distance: enemy.distance.0,
+                });
+            }
+        }
+        AttackOutcome {
+            killed: remove_dead(enemies),
+            damage_dealt,
+            true_damage: damage_dealt,
+            hits: vec![],
+        }
+    }
 
-impl Enemy {
-    pub fn tick(&mut self, delta: f32) -> EnemyAfterTick {
-        self.distance.0 -= delta * self.speed;
-        match self.distance.0 > 0. {
-            true => EnemyAfterTick::Normal,
-            false => EnemyAfterTick::ReachedExcellency,
+    fn strike(&mut self, incident: Incident, enemies: &mut Vec<Enemy>) {
+        self.emit(GameEvent::IncidentStruck(incident));
+        match incident {
+            Incident::Meteor => {
+                let outcome = incidents::meteor(enemies);
+                self.resolve(outcome, DamageSource::Incident);
+            }
+            // Already running, see `Incidents::spawn_rate`
+            Incident::Frenzy => {}
+            Incident::Blessing => {
+                let hp = &mut self.excellency.hp;
+                hp.heal(hp.maximum * BLESSING_HEAL);
+            }
         }
     }
 
-    pub fn x_coor(&self) -> f32 {
-        self.distance.0 * 10.
+    /// Offers a talent choice once a level that earns one is reached.
+    fn offer_talents(&mut self) {
+        let earned = (self.excellency.level() / LEVELS_PER_TALENT) as usize
+            + self.excellency.inherited_talents
+            + self.excellency.extra_talents;
+        if self.talent_choice.is_none() && self.excellency.talents.len() < earned {
+            self.talent_choice = Some(Talent::roll_choice(&mut self.rng));
+        }
     }
 
-    pub fn radius(&self) -> f32 {
-        10. * (self.hp.current / self.hp.maximum)
+    /// Picks `talent` out of the pending choice, nothing happens if it wasn't offered.
+    pub fn choose_talent(&mut self, talent: Talent) {
+        let offered = self.talent_choice.as_ref();
+        if !offered.is_some_and(|offered| offered.contains(&talent)) {
+            return;
+        }
+        self.talent_choice = None;
+        talent.apply(&mut self.excellency);
+        self.excellency.talents.push(talent);
+        self.offer_talents();
     }
-}
 
-enum EnemyAfterTick {
-    Normal,
-    ReachedExcellency,
-}
+    /// Gold calling the next wave right now would earn, 0 while a wave is running.
+    pub fn early_call_gold(&self) -> u32 {
+        if self.waves.phase != WavePhase::Break {
+            return 0;
+        }
+        let gold = self.waves.timer.remaining * EARLY_GOLD_PER_SECOND;
+        (gold * (1. + self.excellency.gold_bonus)).round() as u32
+    }
 
-#[derive(serde::Deserialize, serde::Serialize, PartialEq, PartialOrd, Clone)]
-struct Distance(f32);
+    /// Starts the next wave on the following step instead of waiting out the break, paying
+    /// gold for the time skipped. Returns whether there was a break to cut short.
+    pub fn call_wave_early(&mut self) -> bool {
+        let gold = self.early_call_gold();
+        if self.waves.call_early().is_none() {
+            return false;
+        }
+        self.excellency.gold += gold;
+        self.emit(GameEvent::WaveCalledEarly {
+            wave: self.waves.number + 1,
+            gold,
+        });
+        true
+    }
 
-impl Distance {
-    pub fn start() -> Self {
-        Self(100.)
+    /// Does whatever the switched on automations are there for.
+    fn automate(&mut self) {
+        let automations = &self.records.automations;
+        if automations.is_on(Automation::CastUltimate) {
+            let ultimate = &mut self.excellency.ultimate;
+            ultimate.queued |= ultimate.charge.is_full();
+        }
+        if automations.is_on(Automation::BuyUpgrades) && !self.mode.free_sliders() {
+            if let Some((kind, upgrade)) = automation::cheapest_upgrade(&self.excellency) {
+                self.excellency.buy_upgrade(kind, upgrade);
+            }
+        }
+        if automations.is_on(Automation::CallWaves) {
+            self.call_wave_early();
+        }
     }
 
-    pub fn as_progress_bar(&self) -> egui::ProgressBar {
-        egui::ProgressBar::new(self.0 / 100.).show_percentage()
+    /// The talents waiting to be picked from, if any.
+    pub fn talent_choice(&self) -> Option<&[Talent]> {
+        self.talent_choice.as_deref()
     }
-}
 
-#[derive(serde::Deserialize, serde::Serialize, Clone)]
-struct HitPoints {
-    maximum: f32,
-    current: f32,
-}
+    /// The part of `step` working on the `enemies` taken out of the field, free to return
+    /// early since `step` puts them back.
+    fn step_field(&mut self, delta: f32, enemies: &mut Vec<Enemy>) {
+        self.excellency.buffs.tick(delta);
+        let regeneration = self.excellency.regeneration * delta;
+        self.excellency.hp.heal(regeneration);
+        self.excellency.hp.tick(delta);
+        // Only the auras deal damage over time so far
+        let burnt = self.tick_debuffs(delta, enemies);
+        self.resolve(burnt, DamageSource::Aura);
+        let mut retaliation = AttackOutcome {
+            killed: vec![],
+            damage_dealt: 0.,
+            true_damage: 0.,
+            hits: vec![],
+        };
+        for (_, crowd_control) in self.crowd_control.iter_mut() {
+            crowd_control.tick(delta);
+        }
+        let arrived = advance(enemies, delta, &self.debuffs, &self.crowd_control);
+        for (removed, index) in arrived.into_iter().enumerate() {
+            // Removed in place to keep the order, arrivals are too rare for the shifting to matter
+            let mut enemy = enemies.remove(index - removed);
+            // Only rolled when it can succeed, so seeds replay the same without dodge
+            if self.excellency.dodge_chance > 0. && self.rng.chance(self.excellency.dodge_chance) {
+                self.emit(GameEvent::Dodged);
+                continue;
+            }
+            let damage_type = enemy.damage_type();
+            let damage = match damage_type {
+                DamageType::Normal => (enemy.damage - self.excellency.block).max(0.),
+                DamageType::True => enemy.damage,
+            };
+            self.excellency.hp.take_damage(damage);
+            self.emit(GameEvent::PlayerHit {
+                damage,
+                blocked: enemy.damage - damage,
+                damage_type,
+            });
+            for kind in AttackKind::ALL {
+                if self.excellency.attack_mut(kind).interrupt() {
+                    self.emit(GameEvent::AttackInterrupted { kind });
+                }
+            }
+            if self.excellency.thorns > 0. {
+                retaliation.damage_dealt += enemy.take_damage(damage * self.excellency.thorns);
+                if enemy.hp.is_dead() {
+                    retaliation.killed.push(enemy);
+                }
+            }
+        }
+        self.resolve(retaliation, DamageSource::Thorns);
+        for enemy in remove_escaped(enemies) {
+            self.emit(GameEvent::EnemyEscaped(enemy));
+        }
+        if self.excellency.hp.is_dead() {
+            self.defeat();
+            return;
+        }
 
-impl HitPoints {
-    pub fn new_full(maximum: f32) -> Self {
-        Self {
-            maximum,
-            current: maximum,
+        // Walking at different speeds may have swapped a few
+        sort_by_distance(enemies);
+        let healers = &mut self.scratch.healers;
+        healers.clear();
+        healers.extend(
+            enemies
+                .iter()
+                .enumerate()
+                .filter(|(_, enemy)| enemy.kind == EnemyKind::Healer)
+                // Held ones don't heal either
+                .filter(|(_, enemy)| {
+                    let held = self.crowd_control.get(enemy.id);
+                    !held.is_some_and(CrowdControl::is_held)
+                })
+                .map(|(index, enemy)| (index, enemy.distance.0)),
+        );
+        for &(healer_index, healer_distance) in healers.iter() {
+            for index in around(enemies, healer_distance, HEAL_RADIUS) {
+                if index != healer_index {
+                    enemies[index].hp.heal(HEALING_PER_SECOND * delta);
+                }
+            }
         }
-    }
 
-    pub fn as_progress_bar(&self) -> egui::ProgressBar {
-        egui::ProgressBar::new(self.current / self.maximum)
-            .text(format!("{}/{}", self.current, self.maximum))
-            .fill(Color32::DARK_RED)
-    }
+        self.elapsed += delta;
+        let content = Arc::clone(self.stage_content.as_ref().unwrap_or(&self.content));
+        let mut spawned = vec![];
+        if self.waves.tick(delta) {
+            self.emit(GameEvent::WaveStarted {
+                wave: self.waves.number,
+            });
+            for (name, archetype) in content.bosses(self.waves.number) {
+                spawned.push(self.enemy_spawner.spawn_boss(self.elapsed, name, archetype));
+            }
+            if treasure::rolls(self.rng.seed(), self.waves.number) {
+                spawned.push(self.enemy_spawner.spawn_chest(self.elapsed));
+            }
+        }
+        let spawners = std::iter::once(&mut self.enemy_spawner).chain(&mut self.extra_spawners);
+        for spawner in spawners {
+            let regular = match self.waves.phase {
+                WavePhase::Active => spawner.tick(
+                    delta,
+                    self.elapsed,
+                    self.incidents.spawn_rate(),
+                    self.waves.number,
+                    &content,
+                    &mut self.rng,
+                ),
+                WavePhase::Break => vec![],
+            };
+            // Bosses always join, the cap only holds back the regular spawns
+            let joining = spawned.len();
+            spawned.extend(spawner.admit(regular, enemies, joining));
+        }
+        for enemy in spawned {
+            self.join(enemy, enemies);
+        }
+
+        sort_by_distance(enemies);
+
+        if let Some(incident) = self.incidents.tick(delta, &mut self.rng) {
+            self.strike(incident, enemies);
+        }
+
+        self.apply_auras(enemies);
 
-    pub fn take_damage(&mut self, damage: f32) {
-        self.current -= damage;
+        let cooldown_delta = self.excellency.buffs.scale(Stat::AttackSpeed, delta);
+        for kind in AttackKind::ALL {
+            if self.mutators.iter().any(|mutator| mutator.disables(kind)) {
+                continue;
+            }
+            for _ in 0..self.excellency.attack_mut(kind).tick(cooldown_delta) {
+                let mut outcome = self.excellency.attack(kind).fire(
+                    enemies,
+                    &self.excellency.buffs,
+                    self.focused,
+                );
+                self.emit(GameEvent::AttackFired {
+                    kind,
+                    hits: std::mem::take(&mut outcome.hits),
+                });
+                self.resolve(outcome, DamageSource::Attack(kind));
+            }
+        }
+
+        let ultimate = &mut self.excellency.ultimate;
+        if std::mem::take(&mut ultimate.queued) && ultimate.charge.is_full() {
+            let mut outcome = ultimate.fire(enemies);
+            self.emit(GameEvent::UltimateFired {
+                hits: std::mem::take(&mut outcome.hits),
+            });
+            self.resolve(outcome, DamageSource::Ultimate);
+            for boss in enemies.iter().filter(|enemy| enemy.is_boss) {
+                let crowd_control = self.crowd_control.entry(boss.id);
+                crowd_control.apply(Control::Stun, ultimate::BOSS_STUN);
+            }
+            // Emptied only now, so its own kills don't count towards the next one
+            self.excellency.ultimate.charge.empty();
+        }
+
+        #[cfg(feature = "scripting")]
+        for outcome in self.scripts.tick(delta, enemies, &mut self.crowd_control) {
+            self.resolve(outcome, DamageSource::Script);
+        }
+
+        self.react(enemies);
+        self.advance_boss_phases(enemies);
     }
 
-    pub fn reset(&mut self) {
-        self.current = self.maximum;
+    /// Advances the simulation by `delta` seconds, independent of any UI.
+    pub fn step(&mut self, delta: f32) {
+        if self.defeated || self.victorious {
+            // Also moves on from a stage won before auto-continue was switched on
+            self.auto_continue();
+            return;
+        }
+        self.automate();
+        // Taken out for the step so it can be mutated alongside the rest of the state, and put
+        // back here whichever way the step on the field ends
+        let mut enemies = std::mem::take(&mut self.enemies);
+        self.step_field(delta, &mut enemies);
+        self.enemies = enemies;
+        if self.defeated {
+            return;
+        }
+        let alive = &mut self.scratch.alive;
+        alive.clear();
+        alive.extend(self.enemies.iter().map(|enemy| enemy.id));
+        alive.sort();
+        self.debuffs.retain_alive(alive);
+        self.crowd_control.retain_alive(alive);
+        self.boss_phases.retain_alive(alive);
+        self.focused = self.focused.filter(|id| alive.binary_search(id).is_ok());
+        self.marked.retain(|id| alive.binary_search(id).is_ok());
+        self.offer_talents();
+        if let Some(victory) = self.victory {
+            let enemies_left = self.enemies.len();
+            if victory.is_met(
+                &self.waves,
+                self.elapsed,
+                enemies_left,
+                self.milestone_cleared,
+            ) {
+                self.win();
+            }
+        }
+        for plugin in &mut self.plugins {
+            plugin.on_tick(delta, self.elapsed, &self.enemies);
+        }
+
+        if self.elapsed.floor() > (self.elapsed - delta).floor() {
+            let enemy_hp = self.enemies.iter().map(|enemy| enemy.hp.current).sum();
+            self.hp_history
+                .record(self.elapsed, self.excellency.hp.current, enemy_hp);
+            self.stat_samples.record(StatSample {
+                elapsed: self.elapsed,
+                wave: self.waves.number,
+                hp: self.excellency.hp.current,
+                gold: self.excellency.gold,
+                kills: self.kills,
+                enemies: self.enemies.len(),
+                enemy_hp,
+                damage_dealt: self.damage_by_source.total(),
+            });
+        }
+        self.auto_continue();
     }
 }
 
 impl Default for GameState {
     fn default() -> Self {
         Self {
+            save_version: SAVE_VERSION,
             excellency: Excellency {
                 hp: HitPoints::new_full(100.),
+                gold: 0,
+                experience: 0,
+                gold_bonus: 0.,
+                life_steal: 0.,
+                thorns: 0.,
+                dodge_chance: 0.,
+                block: 0.,
+                regeneration: 0.,
+                buffs: Effects::default(),
+                auras: vec![],
+                aura_radii: vec![],
+                ultimate: Ultimate::default(),
+                talents: vec![],
+                inherited_talents: 0,
+                extra_talents: 0,
+                base_stats: vec![],
+                perks: vec![],
+                upgrades: vec![],
                 basic_attack: BasicAttack {
                     cooldown_timer: Timer::new(2.),
                     damage: 4.,
                     range: 35.,
                     max_targets: 3,
+                    targeting: TargetingStrategy::Closest,
+                    overkill_carry: false,
+                    wind_up: 0.,
+                    phase: AttackPhase::Cooldown,
+                    cast_mode: CastMode::Auto,
+                    damage_type: DamageType::Normal,
                 },
                 big_attack: BasicAttack {
                     cooldown_timer: Timer::new(10.),
                     damage: 30.,
                     range: 20.,
                     max_targets: 10,
+                    targeting: TargetingStrategy::Closest,
+                    overkill_carry: true,
+                    wind_up: 0.,
+                    phase: AttackPhase::Cooldown,
+                    cast_mode: CastMode::Auto,
+                    damage_type: DamageType::Normal,
                 },
             },
             enemy_spawner: EnemySpawner {
                 timer: Timer::new(1.),
+                interval: 1.,
+                scaling: SpawnerScaling {
+                    hp: ScalingCurve::Linear { per_minute: 0.2 },
+                    speed: ScalingCurve::Linear { per_minute: 0.05 },
+                    damage: ScalingCurve::Linear { per_minute: 0.1 },
+                    spawn_rate: ScalingCurve::Linear { per_minute: 0.1 },
+                },
                 maximum_hp: 10.,
                 speed: 5.,
                 damage: 2.,
+                elite_chance: 0.1,
+                healer_chance: 0.1,
+                max_alive: 2000,
+                overflow: Overflow::Queue,
+                queue: VecDeque::new(),
+                pattern: SpawnPattern::Steady,
+                streak: 0,
+                archetype: None,
+                variance: 0.,
             },
+            extra_spawners: vec![],
+            waves: Waves::default(),
+            incidents: Incidents::default(),
+            mutators: vec![],
+            mode: GameMode::Survival,
+            daily: None,
+            stage: None,
+            victory: None,
+            victorious: false,
+            stage_content: None,
+            new_game_plus: 0,
+            milestone_cleared: false,
+            wave_damage: vec![],
+            damage_by_source: DamageBySource::default(),
+            true_damage_by_source: DamageBySource::default(),
             enemies: vec![],
+            entities: Entities::default(),
+            debuffs: Components::default(),
+            crowd_control: Components::default(),
+            boss_phases: Components::default(),
+            talent_choice: None,
+            auto_continue_stopped: false,
+            elapsed: 0.,
+            kills: 0,
+            defeated: false,
+            paused: false,
+            auto_paused: false,
+            low_power: false,
+            game_speed: 1.,
+            accumulator: 0.,
+            scratch: Scratch::default(),
+            rng: GameRng::default(),
+            next_seed: GameRng::default().seed(),
+            next_mutators: vec![],
+            next_mode: GameMode::Survival,
+            next_victory: None,
+            show_save_window: false,
+            save_name: "My run".to_owned(),
+            load_error: None,
+            import_text: String::new(),
+            settings: Settings::default(),
+            records: Records::default(),
+            ascendancy: Ascendancy::default(),
+            new_bests: None,
+            show_settings_window: false,
+            show_history_window: false,
+            show_stat_sheet_window: false,
+            show_character_sheet_window: false,
+            show_campaign_window: false,
+            show_automation_window: false,
+            show_ascendancy_window: false,
+            show_relics_window: false,
+            profiler: Profiler::default(),
+            profile: 0,
+            profiles: Profiles::default(),
+            show_profile_window: false,
+            profile_name: String::new(),
+            preset_name: String::new(),
+            edits: EditHistory::default(),
+            loadout: None,
+            loadout_name: String::new(),
+            history_sort: HistorySort::default(),
+            #[cfg(feature = "leaderboard")]
+            leaderboard: Leaderboard::default(),
+            #[cfg(feature = "telemetry")]
+            telemetry: Telemetry::default(),
+            #[cfg(feature = "discord")]
+            discord: DiscordPresence::default(),
+            #[cfg(feature = "leaderboard")]
+            show_leaderboard_window: false,
+            #[cfg(feature = "gamepad")]
+            gamepad: Gamepad::default(),
+            rebinding: None,
+            events: vec![],
+            audio: Audio::default(),
+            hp_history: HpHistory::default(),
+            stat_samples: StatSamples::default(),
+            hovered: None,
+            focused: None,
+            marked: vec![],
+            inspected: None,
+            menu_enemy: None,
+            combat_log: CombatLog::default(),
+            floating_texts: FloatingTexts::default(),
+            dying: DyingEnemies::default(),
+            particles: Particles::default(),
+            juice: Juice::default(),
+            enemy_sort: EnemySort::default(),
+            enemy_filter: EnemyFilter::default(),
+            touch_layout: false,
+            tab: Tab::default(),
+            content: Arc::default(),
+            content_errors: vec![],
+            #[cfg(feature = "scripting")]
+            scripts: Scripts::default(),
+            plugins: plugins::built_in(),
+            reactions: vec![],
+            toasts: Toasts::default(),
+            #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+            watcher: None,
         }
     }
 }
 
 impl GameState {
-    /// Called once before the first frame.
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        // This is also where you can customize the look and feel of egui using
-        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+    /// A fresh run whose randomness is fully determined by `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rng: GameRng::new(seed),
+            next_seed: seed,
+            ..Default::default()
+        }
+    }
 
-        // Load previous app state (if any).
-        // Note that you must enable the `persistence` feature for this to work.
+    /// A fresh run like `with_seed`, played under `mutators`.
+    pub fn with_mutators(seed: u64, mutators: Vec<Mutator>) -> Self {
+        let mut state = Self::with_seed(seed);
+        for mutator in &mutators {
+            mutator.apply(&mut state);
+        }
+        state.next_mutators.clone_from(&mutators);
+        state.mutators = mutators;
+        state
+    }
 
-        // TODO: Commented out persistence for now
-        // if let Some(storage) = cc.storage {
-        //     return eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-        // }
+    /// A fresh run in `mode`, the one it's played in unless changed.
+    pub fn with_mode(seed: u64, mutators: Vec<Mutator>, mode: GameMode) -> Self {
+        let mut state = Self::with_mutators(seed, mutators);
+        state.mode = mode;
+        state.next_mode = mode;
+        state
+    }
 
-        Default::default()
+    /// A fresh run won once `victory` is met instead of going on forever.
+    pub fn with_victory(seed: u64, victory: Victory) -> Self {
+        let mut state = Self::with_seed(seed);
+        state.victory = Some(victory);
+        state.next_victory = Some(victory);
+        state
     }
-}
 
-impl eframe::App for GameState {
-    /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+    /// Campaign stage `name` from the built-in content, none if there's no such stage.
+    pub fn with_stage(seed: u64, name: &str) -> Option<Self> {
+        let mut state = Self::with_seed(seed);
+        state.enter_stage(name).then_some(state)
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
-        // For inspiration and more examples, go to https://emilk.github.io/egui
+    /// Makes this fresh run play stage `name` of its content, false if there's none.
+    fn enter_stage(&mut self, name: &str) -> bool {
+        let Some(stage) = self.content.stage(name) else {
+            return false;
+        };
+        self.victory = Some(stage.victory);
+        self.stage = Some(name.to_owned());
+        self.refresh_stage();
+        true
+    }
 
-        self.tick(ctx);
+    /// Rebuilds what the stage spawns from after `content` changed.
+    pub(crate) fn refresh_stage(&mut self) {
+        let stage = self
+            .stage
+            .as_deref()
+            .and_then(|name| self.content.stage(name));
+        self.stage_content = stage.map(|stage| Arc::new(self.content.for_stage(stage)));
+    }
 
-        egui::SidePanel::right("right_panel").show(ctx, |ui| {
-            ui.heading("Enemy spawner");
-            ui.horizontal(|ui| {
-                ui.label("Damage:");
-                ui.add(egui::Slider::new(&mut self.enemy_spawner.damage, 0. ..=20.));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Maximum HP:");
-                ui.add(egui::Slider::new(
-                    &mut self.enemy_spawner.maximum_hp,
-                    0. ..=100.,
-                ));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Speed:");
-                ui.add(egui::Slider::new(&mut self.enemy_spawner.speed, 0. ..=20.));
-            });
+    /// Starts campaign stage `name` with the next seed and mode.
+    pub(crate) fn start_stage(&mut self, name: &str) {
+        let mut run = GameState::with_mode(self.next_seed, vec![], self.next_mode);
+        run.content = Arc::clone(&self.content);
+        if run.enter_stage(name) {
+            self.start_fresh(run);
+        }
+    }
 
-            ui.separator();
-            ui.heading("Enemies");
+    /// The automations unlocked for good and which are on, what the automation window changes.
+    pub fn automations_mut(&mut self) -> &mut Automations {
+        &mut self.records.automations
+    }
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                for enemy in self.enemies.iter() {
-                    if enemy.is_hovered {
-                        ui.label("HOVERED");
-                    }
-                    ui.horizontal(|ui| {
-                        ui.label("Distance:");
-                        ui.add(enemy.distance.as_progress_bar());
-                    });
-                    ui.horizontal(|ui| {
-                        ui.label("HP:");
-                        ui.add(enemy.hp.as_progress_bar());
-                    });
-                    ui.label(format!("Damage: {}", enemy.damage));
-                    ui.label(format!("Speed: {}", enemy.speed));
-                    ui.separator();
-                }
-            })
-        });
+    /// The campaign stage this run plays, none outside the campaign.
+    pub fn stage(&self) -> Option<&str> {
+        self.stage.as_deref()
+    }
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Player stuff");
-            ui.separator();
-            ui.horizontal(|ui| {
-                ui.label("HP:");
-                ui.add(self.excellency.hp.as_progress_bar());
-            });
-            if ui.button("Reset HP").clicked() {
-                self.excellency.hp.reset()
-            }
-            ui.separator();
-            ui.heading("Basic Attack");
-            ui.horizontal(|ui| {
-                ui.label("Cooldown:");
-                ui.add(
-                    egui::ProgressBar::new(
-                        self.excellency
-                            .basic_attack
-                            .cooldown_timer
-                            .remaining_fraction(),
-                    )
-                    .text(format!(
-                        "{:.1}s / {:.1}s",
-                        self.excellency.basic_attack.cooldown_timer.remaining,
-                        self.excellency.basic_attack.cooldown_timer.total
-                    ))
-                    .fill(Color32::DARK_BLUE),
-                )
-            });
-            ui.horizontal(|ui| {
-                ui.label("Damage:");
-                ui.add(egui::Slider::new(
-                    &mut self.excellency.basic_attack.damage,
-                    1. ..=100.,
-                ));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Range:");
-                ui.add(egui::Slider::new(
-                    &mut self.excellency.basic_attack.range,
-                    1. ..=50.,
-                ));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Max Targets:");
-                ui.add(egui::Slider::new(
-                    &mut self.excellency.basic_attack.max_targets,
-                    1..=10,
-                ));
-            });
+    /// Switches auto-continue on or off, stopping below `min_hp` of the maximum HP. What the
+    /// campaign window sets.
+    pub fn set_auto_continue(&mut self, enabled: bool, min_hp: f32) {
+        self.settings.auto_continue = AutoContinue { enabled, min_hp };
+    }
 
-            ui.separator();
-            ui.heading("Big Attack");
-            ui.horizontal(|ui| {
-                ui.label("Cooldown:");
-                ui.add(
-                    egui::ProgressBar::new(
-                        self.excellency
-                            .big_attack
-                            .cooldown_timer
-                            .remaining_fraction(),
-                    )
-                    .text(format!(
-                        "{:.1}s / {:.1}s",
-                        self.excellency.big_attack.cooldown_timer.remaining,
-                        self.excellency.big_attack.cooldown_timer.total
-                    ))
-                    .fill(Color32::DARK_BLUE),
-                )
-            });
-            ui.horizontal(|ui| {
-                ui.label("Damage:");
-                ui.add(egui::Slider::new(
-                    &mut self.excellency.big_attack.damage,
-                    1. ..=100.,
-                ));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Range:");
-                ui.add(egui::Slider::new(
-                    &mut self.excellency.big_attack.range,
-                    1. ..=50.,
-                ));
-            });
-            ui.horizontal(|ui| {
-                ui.label("Max Targets:");
-                ui.add(egui::Slider::new(
-                    &mut self.excellency.big_attack.max_targets,
-                    1..=10,
-                ));
-            });
+    /// The campaign stage after the one this run plays, none past the last one.
+    pub fn next_stage(&self) -> Option<String> {
+        let stages = &self.content.stages;
+        let index = stages
+            .iter()
+            .position(|stage| Some(&stage.name) == self.stage.as_ref())?;
+        stages.get(index + 1).map(|stage| stage.name.clone())
+    }
 
-            // TODO: Sense?
-            let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::hover());
+    /// Stars the won campaign stage is worth, none if this isn't one or it isn't won.
+    pub fn stars(&self) -> Option<u32> {
+        let stage = self.content.stage(self.stage.as_deref()?)?;
+        let hp = &self.excellency.hp;
+        self.victorious
+            .then(|| stage.stars(hp.current / hp.maximum))
+    }
 
-            self.enemies.iter().for_each(|enemy| {
-                painter.add(egui::Shape::circle_filled(
-                    Pos2::new(enemy.x_coor(), 400.),
-                    enemy.radius(),
-                    Color32::RED,
-                ));
-            });
+    pub fn is_victorious(&self) -> bool {
+        self.victorious
+    }
 
-            if let Some(position) = response.hover_pos() {
-                self.enemies.iter_mut().for_each(|enemy| {
-                    let x = enemy.x_coor();
-                    let radius = enemy.radius();
+    /// The daily challenge of `day`, the same run for everyone playing it.
+    pub fn daily(day: u64) -> Self {
+        let mut state = Self::with_mutators(daily::seed(day), daily::mutators(day));
+        state.daily = Some(day);
+        state
+    }
 
-                    if position.x >= x - radius && position.x <= x + radius {
-                        enemy.is_hovered = true
-                    }
-                })
+    /// The next New Game+ cycle of this run: same mutators, tougher enemies, and the
+    /// Excellency keeping its auras and talents.
+    pub fn next_cycle(&self) -> Self {
+        let mut next = Self::with_mutators(self.next_seed, self.mutators.clone());
+        next.new_game_plus = self.new_game_plus + 1;
+        next.mode = self.mode;
+        next.next_mode = self.mode;
+        next.victory = self.victory;
+        next.next_victory = self.victory;
+        new_game_plus::scale(&mut next.enemy_spawner, next.new_game_plus);
+        let excellency = &mut next.excellency;
+        excellency.auras.clone_from(&self.excellency.auras);
+        for talent in &self.excellency.talents {
+            talent.apply(excellency);
+        }
+        excellency.talents.clone_from(&self.excellency.talents);
+        excellency.extra_talents = self.excellency.extra_talents;
+        excellency.inherited_talents = excellency
+            .talents
+            .len()
+            .saturating_sub(excellency.extra_talents);
+        next
+    }
+
+    /// Moves on to `next_cycle`, only once the milestone boss fell.
+    pub(crate) fn start_next_cycle(&mut self) {
+        if !self.milestone_cleared {
+            return;
+        }
+        let next = self.next_cycle();
+        if self.mode.tracks_records() {
+            let deepest = &mut self.records.deepest_new_game_plus;
+            *deepest = (*deepest).max(next.new_game_plus);
+        }
+        self.start_fresh(next);
+    }
+
+    /// Continues as `run`, with every Ascendancy perk bought.
+    fn start_fresh(&mut self, mut run: GameState) {
+        self.ascendancy.apply(&mut run.excellency);
+        run.offer_talents();
+        self.replace_run(run);
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    /// Starts over with the next seed, mutators, mode and win condition picked in the run panel.
+    pub(crate) fn restart(&mut self, seed: u64, mutators: Vec<Mutator>) {
+        let mut run = GameState::with_mode(seed, mutators, self.next_mode);
+        run.victory = self.next_victory;
+        run.next_victory = self.next_victory;
+        self.start_fresh(run);
+    }
+
+    /// New Game+ cycles started before reaching this run.
+    pub fn new_game_plus(&self) -> u32 {
+        self.new_game_plus
+    }
+
+    /// Derived numbers for the current Excellency against the current spawner.
+    pub fn stat_sheet(&self) -> StatSheet {
+        let mut sheet = StatSheet::compute(&self.excellency, &self.enemy_spawner, self.elapsed);
+        if let Some(name) = &self.loadout {
+            sheet.loadout = Some(name.clone());
+            sheet.loadout_modified =
+                self.settings.loadouts.get(name) != Some(&Loadout::capture(&self.excellency));
+        }
+        sheet
+    }
+
+    /// Every stat of the Excellency with where it came from.
+    pub fn character_sheet(&self) -> CharacterSheet {
+        CharacterSheet::compute(&self.excellency)
+    }
+
+    /// Swaps the attacks for the saved loadout `name`, right away even mid-run.
+    pub fn swap_loadout(&mut self, name: &str) -> bool {
+        let Some(loadout) = self.settings.loadouts.get(name) else {
+            return false;
+        };
+        loadout.apply(&mut self.excellency);
+        self.loadout = Some(name.to_owned());
+        true
+    }
+
+    /// Saves the current attacks as the loadout `name`, replacing one of the same name.
+    pub fn save_loadout(&mut self, name: &str) {
+        let loadout = Loadout::capture(&self.excellency);
+        self.settings.loadouts.insert(name.to_owned(), loadout);
+        self.loadout = Some(name.to_owned());
+    }
+
+    pub fn excellency(&self) -> &Excellency {
+        &self.excellency
+    }
+
+    /// Sorted by distance, closest first.
+    pub fn enemies(&self) -> &[Enemy] {
+        &self.enemies
+    }
+
+    /// Puts `enemies` on the field as they are, for tests and benchmarks.
+    pub fn add_enemies(&mut self, enemies: impl IntoIterator<Item = Enemy>) {
+        for mut enemy in enemies {
+            enemy.id = self.entities.spawn();
+            self.enemies.push(enemy);
+        }
+        sort_by_distance(&mut self.enemies);
+    }
+
+    /// Hooks `plugin` into this run, next to the built-in ones. Like them it's dropped when
+    /// the run is restarted or a save is loaded.
+    pub fn register_plugin(&mut self, plugin: Box<dyn GamePlugin>) {
+        self.plugins.push(plugin);
+    }
+
+    pub fn waves(&self) -> &Waves {
+        &self.waves
+    }
+
+    /// Simulated seconds since the run started.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    /// The per-second samples and the combat log as one CSV timeline, see `export::to_csv`.
+    pub fn export_csv(&self) -> String {
+        export::to_csv(self.stat_samples.samples(), self.combat_log.lines())
+    }
+
+    /// Damage dealt so far this run, by what dealt it.
+    pub fn damage_by_source(&self) -> &DamageBySource {
+        &self.damage_by_source
+    }
+
+    /// The part of `damage_by_source` that ignored armor and shields.
+    pub fn true_damage_by_source(&self) -> &DamageBySource {
+        &self.true_damage_by_source
+    }
+
+    /// The run's numbers right now, see `step` for moving it along.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            elapsed: self.elapsed,
+            wave: self.waves.number,
+            hp: self.excellency.hp.current,
+            max_hp: self.excellency.hp.maximum,
+            gold: self.excellency.gold,
+            experience: self.excellency.experience,
+            kills: self.kills,
+            enemies: self.enemies.len(),
+            enemy_hp: self.enemies.iter().map(|enemy| enemy.hp.current).sum(),
+            defeated: self.defeated,
+            victorious: self.victorious,
+        }
+    }
+
+    /// Whether the Excellency fell, `step` does nothing from then on.
+    pub fn is_defeated(&self) -> bool {
+        self.defeated
+    }
+
+    /// Parses a serialized `GameState`, upgrading it from older layouts if needed.
+    pub fn from_save(save: &str) -> Result<Self, SaveError> {
+        migration::load(save, SaveFormat::Ron)
+    }
+
+    /// Like `from_save`, but for the JSON produced by `to_json`.
+    pub fn from_json(json: &str) -> Result<Self, SaveError> {
+        migration::load(json, SaveFormat::Json)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Swaps in another run, keeping the settings, records and the open windows.
+    pub(crate) fn replace_run(&mut self, run: GameState) {
+        let settings = std::mem::take(&mut self.settings);
+        let records = std::mem::take(&mut self.records);
+        let ascendancy = std::mem::take(&mut self.ascendancy);
+        #[cfg(feature = "leaderboard")]
+        let leaderboard = std::mem::take(&mut self.leaderboard);
+        #[cfg(feature = "leaderboard")]
+        let show_leaderboard_window = self.show_leaderboard_window;
+        #[cfg(feature = "telemetry")]
+        let telemetry = std::mem::take(&mut self.telemetry);
+        let audio = std::mem::take(&mut self.audio);
+        #[cfg(feature = "discord")]
+        let discord = std::mem::take(&mut self.discord);
+        #[cfg(feature = "gamepad")]
+        let gamepad = std::mem::take(&mut self.gamepad);
+        let save_name = std::mem::take(&mut self.save_name);
+        let show_save_window = self.show_save_window;
+        let show_settings_window = self.show_settings_window;
+        let (show_history_window, history_sort) = (self.show_history_window, self.history_sort);
+        let (profile, profiles) = (self.profile, std::mem::take(&mut self.profiles));
+        let show_profile_window = self.show_profile_window;
+        let show_stat_sheet_window = self.show_stat_sheet_window;
+        let show_character_sheet_window = self.show_character_sheet_window;
+        let show_campaign_window = self.show_campaign_window;
+        let show_automation_window = self.show_automation_window;
+        let show_ascendancy_window = self.show_ascendancy_window;
+        let show_relics_window = self.show_relics_window;
+        let profiler = std::mem::take(&mut self.profiler);
+        let profile_name = std::mem::take(&mut self.profile_name);
+        let preset_name = std::mem::take(&mut self.preset_name);
+        let loadout_name = std::mem::take(&mut self.loadout_name);
+        let (enemy_sort, enemy_filter) = (self.enemy_sort, self.enemy_filter);
+        let (touch_layout, tab) = (self.touch_layout, self.tab);
+        let content = Arc::clone(&self.content);
+        let content_errors = std::mem::take(&mut self.content_errors);
+        #[cfg(feature = "scripting")]
+        let scripts = std::mem::take(&mut self.scripts);
+        let toasts = std::mem::take(&mut self.toasts);
+        #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+        let watcher = self.watcher.take();
+        *self = run;
+        self.settings = settings;
+        self.records = records;
+        self.ascendancy = ascendancy;
+        #[cfg(feature = "leaderboard")]
+        {
+            self.leaderboard = leaderboard;
+            self.show_leaderboard_window = show_leaderboard_window;
+        }
+        #[cfg(feature = "telemetry")]
+        {
+            self.telemetry = telemetry;
+        }
+        self.audio = audio;
+        #[cfg(feature = "discord")]
+        {
+            self.discord = discord;
+        }
+        #[cfg(feature = "gamepad")]
+        {
+            self.gamepad = gamepad;
+        }
+        self.save_name = save_name;
+        self.show_save_window = show_save_window;
+        self.show_settings_window = show_settings_window;
+        self.show_history_window = show_history_window;
+        self.profile = profile;
+        self.profiles = profiles;
+        self.show_profile_window = show_profile_window;
+        self.show_stat_sheet_window = show_stat_sheet_window;
+        self.show_character_sheet_window = show_character_sheet_window;
+        self.show_campaign_window = show_campaign_window;
+        self.show_automation_window = show_automation_window;
+        self.show_ascendancy_window = show_ascendancy_window;
+        self.show_relics_window = show_relics_window;
+        self.profiler = profiler;
+        self.profile_name = profile_name;
+        self.preset_name = preset_name;
+        self.loadout_name = loadout_name;
+        self.history_sort = history_sort;
+        self.enemy_sort = enemy_sort;
+        self.enemy_filter = enemy_filter;
+        self.touch_layout = touch_layout;
+        self.tab = tab;
+        self.content = content;
+        self.content_errors = content_errors;
+        self.refresh_stage();
+        #[cfg(feature = "scripting")]
+        {
+            self.scripts = scripts;
+        }
+        self.toasts = toasts;
+        #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+        {
+            self.watcher = watcher;
+        }
+    }
+
+    /// Calm between waves, intense while an elite fights or the Excellency is low on HP.
+    pub(crate) fn music_mood(&self) -> MusicMood {
+        let hp = &self.excellency.hp;
+        let elite_fight =
+            self.waves.phase == WavePhase::Active && self.enemies.iter().any(Enemy::is_elite);
+        let boss_fight = self.enemies.iter().any(|enemy| enemy.is_boss);
+        match elite_fight || boss_fight || hp.current < hp.maximum * 0.3 {
+            true => MusicMood::Intense,
+            false => MusicMood::Calm,
+        }
+    }
+
+    pub(crate) fn perform(&mut self, action: Action) {
+        match action {
+            Action::Pause => {
+                self.paused = !self.paused;
+                self.auto_paused = false;
             }
-        });
+            Action::CastBasicAttack => self.excellency.basic_attack.cast(),
+            Action::CastBigAttack => self.excellency.big_attack.cast(),
+            Action::CastUltimate => {
+                let ultimate = &mut self.excellency.ultimate;
+                ultimate.queued = ultimate.charge.is_full();
+            }
+            Action::SpeedUp => self.game_speed = (self.game_speed * 2.).min(MAX_GAME_SPEED),
+            Action::SpeedDown => self.game_speed = (self.game_speed / 2.).max(MIN_GAME_SPEED),
+            Action::ToggleProfiler => self.profiler.visible = !self.profiler.visible,
+            Action::CallWave => {
+                self.call_wave_early();
+            }
+        }
+    }
 
-        ctx.request_repaint_after(std::time::Duration::from_millis(16)) // ~60fps
+    /// Called once before the first frame.
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        // This is also where you can customize the look and feel of egui using
+        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+
+        let profiles = Profiles::load(cc.storage);
+        let mut state = Self::load_profile(cc.storage, profiles.current);
+        // Ask who's playing when it's not obvious
+        state.show_profile_window = profiles.list.len() > 1;
+        state.profiles = profiles;
+        let (content, content_errors) = Content::load();
+        for err in &content_errors {
+            log::error!("Failed to load content: {err}");
+        }
+        state.content = Arc::new(content);
+        state.content_errors = content_errors;
+        state.refresh_stage();
+        #[cfg(feature = "scripting")]
+        {
+            state.scripts = Scripts::load();
+        }
+        #[cfg(feature = "telemetry")]
+        {
+            state.telemetry = Telemetry::load(cc.storage);
+        }
+        #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+        {
+            state.watcher = ContentWatcher::start();
+        }
+        state.touch_layout = state.settings.touch_layout(&cc.egui_ctx);
+        state.settings.apply(&cc.egui_ctx, state.touch_layout);
+        state
+    }
+
+    /// The last session of `profile` with its settings and records.
+    fn load_profile(storage: Option<&dyn eframe::Storage>, profile: u32) -> Self {
+        let mut state = Self::load_session(storage, profile);
+        if let Some(storage) = storage {
+            state.settings = eframe::get_value(storage, &profiles::key_of(profile, SETTINGS_KEY))
+                .unwrap_or_default();
+            state.records = eframe::get_value(storage, &profiles::key_of(profile, RECORDS_KEY))
+                .unwrap_or_default();
+            state.ascendancy =
+                eframe::get_value(storage, &profiles::key_of(profile, ASCENDANCY_KEY))
+                    .unwrap_or_default();
+        }
+        state.profile = profile;
+        state
+    }
+
+    /// Stores the current profile and continues as `profile`.
+    pub(crate) fn switch_profile(
+        &mut self,
+        ctx: &egui::Context,
+        storage: &mut dyn eframe::Storage,
+        profile: u32,
+    ) {
+        eframe::App::save(self, storage);
+        let mut next = Self::load_profile(Some(&*storage), profile);
+        let settings = std::mem::take(&mut next.settings);
+        let records = std::mem::take(&mut next.records);
+        let ascendancy = std::mem::take(&mut next.ascendancy);
+        self.replace_run(next);
+        self.settings = settings;
+        self.records = records;
+        self.ascendancy = ascendancy;
+        self.profile = profile;
+        self.profiles.current = profile;
+        eframe::set_value(storage, PROFILES_KEY, &self.profiles);
+        self.touch_layout = self.settings.touch_layout(ctx);
+        self.settings.apply(ctx, self.touch_layout);
+    }
+
+    fn load_session(storage: Option<&dyn eframe::Storage>, profile: u32) -> Self {
+        // Load previous app state (if any).
+        // Note that you must enable the `persistence` feature for this to work.
+        let key = profiles::key_of(profile, eframe::APP_KEY);
+        let Some(save) = storage.and_then(|storage| storage.get_string(&key)) else {
+            return Default::default();
+        };
+
+        match Self::from_save(&save) {
+            Ok(state) => state,
+            Err(err) => {
+                // The broken save gets overwritten on shutdown, so log it for manual recovery
+                log::error!("Failed to load the previous session: {err}\n{save}");
+                Self {
+                    load_error: Some(trf(
+                        "Previous session couldn't be loaded: {}",
+                        &[err.to_string()],
+                    )),
+                    ..Default::default()
+                }
+            }
+        }
     }
 }
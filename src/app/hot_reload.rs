@@ -0,0 +1,127 @@
+//! Watches the content and script folders, compiled only with the `hot-reload` feature.
+//!
+//! File events arrive on notify's thread and are only collected there; the reload itself
+//! happens on the next frame, on the UI thread that owns the game state.
+
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+
+use notify::Watcher;
+
+#[cfg(feature = "scripting")]
+use super::scripting::{Scripts, SCRIPTS_DIR};
+use super::GameState;
+use crate::content::{Content, CONTENT_DIR};
+use crate::i18n::{tr, trf};
+use crate::toasts::ToastKind;
+
+/// What changed since the last poll.
+#[derive(Default)]
+pub struct Changes {
+    pub content: bool,
+    pub scripts: bool,
+}
+
+pub struct ContentWatcher {
+    /// Dropping the watcher stops the events.
+    _watcher: notify::RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    content_dir: Option<PathBuf>,
+    scripts_dir: Option<PathBuf>,
+}
+
+impl ContentWatcher {
+    /// Watches whichever of the folders exist, `None` if there's nothing to watch.
+    pub fn start() -> Option<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|err| log::warn!("Can't watch the content for changes: {err}"))
+            .ok()?;
+        let mut watch = |dir: &str| {
+            let dir = Path::new(dir).canonicalize().ok()?;
+            match watcher.watch(&dir, notify::RecursiveMode::Recursive) {
+                Ok(()) => Some(dir),
+                Err(err) => {
+                    log::warn!("Can't watch {} for changes: {err}", dir.display());
+                    None
+                }
+            }
+        };
+        let content_dir = watch(CONTENT_DIR);
+        #[cfg(feature = "scripting")]
+        let scripts_dir = watch(SCRIPTS_DIR);
+        #[cfg(not(feature = "scripting"))]
+        let scripts_dir = None;
+        if content_dir.is_none() && scripts_dir.is_none() {
+            return None;
+        }
+        Some(Self {
+            _watcher: watcher,
+            events,
+            content_dir,
+            scripts_dir,
+        })
+    }
+
+    pub fn poll(&self) -> Changes {
+        let mut changes = Changes::default();
+        for event in self.events.try_iter() {
+            let event = match event {
+                Ok(event) => event,
+                Err(err) => {
+                    log::warn!("Watching the content failed: {err}");
+                    continue;
+                }
+            };
+            if event.kind.is_access() {
+                continue;
+            }
+            let touches = |dir: &Option<PathBuf>| {
+                dir.as_ref()
+                    .is_some_and(|dir| event.paths.iter().any(|path| path.starts_with(dir)))
+            };
+            changes.content |= touches(&self.content_dir);
+            changes.scripts |= touches(&self.scripts_dir);
+        }
+        changes
+    }
+}
+
+impl GameState {
+    /// Picks up edited content and scripts, reporting the result in a toast.
+    pub(crate) fn hot_reload(&mut self) {
+        let Some(watcher) = &self.watcher else {
+            return;
+        };
+        let changes = watcher.poll();
+        if changes.content {
+            let (content, errors) = Content::load();
+            if errors.is_empty() {
+                self.content = Arc::new(content);
+                self.refresh_stage();
+                self.toasts.push(ToastKind::Info, tr("Content reloaded"));
+            } else {
+                // Mid-run a typo shouldn't swap everything for the built-in content
+                for err in errors {
+                    self.toasts
+                        .push(ToastKind::Error, trf("Content not reloaded: {}", &[err]));
+                }
+            }
+        }
+        #[cfg(feature = "scripting")]
+        if changes.scripts {
+            self.scripts = Scripts::load();
+            for err in self.scripts.load_errors() {
+                self.toasts.push(
+                    ToastKind::Error,
+                    trf("Not loaded: {}", std::slice::from_ref(err)),
+                );
+            }
+            self.toasts.push(
+                ToastKind::Info,
+                trf("Scripts reloaded: {}", &[self.scripts.len().to_string()]),
+            );
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Upgrades serialized `GameState`s from older layouts to the current one.
+//!
+//! Every layout change that can't be covered by `#[serde(default)]` bumps `SAVE_VERSION`
+//! and freezes the previous layout in a module here, together with a conversion to the
+//! next version.
+
+use super::GameState;
+
+/// Version of the layout `GameState` currently serializes to.
+pub const SAVE_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum SaveError {
+    Parse(ron::error::SpannedError),
+    Json(serde_json::Error),
+    /// The save was written by a newer version of the game.
+    UnsupportedVersion(u32),
+    /// The save parsed, but describes a state the game can't run.
+    Invalid(&'static str),
+}
+
+impl std::fmt::Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Parse(err) => write!(f, "failed to parse save: {err}"),
+            SaveError::Json(err) => write!(f, "failed to parse save: {err}"),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "save version {version} is newer than the supported version {SAVE_VERSION}"
+            ),
+            SaveError::Invalid(reason) => write!(f, "invalid save: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        SaveError::Parse(err)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Json(err)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum SaveFormat {
+    /// What eframe storage and the save slots use.
+    Ron,
+    /// What exports use.
+    Json,
+}
+
+impl SaveFormat {
+    fn parse<T: serde::de::DeserializeOwned>(self, save: &str) -> Result<T, SaveError> {
+        match self {
+            SaveFormat::Ron => Ok(ron::from_str(save)?),
+            SaveFormat::Json => Ok(serde_json::from_str(save)?),
+        }
+    }
+}
+
+/// Reads only the version, ignoring the rest of the save.
+#[derive(serde::Deserialize)]
+struct VersionProbe {
+    /// Saves from before versioning have no `save_version` field.
+    #[serde(default)]
+    save_version: u32,
+}
+
+pub fn load(save: &str, format: SaveFormat) -> Result<GameState, SaveError> {
+    let VersionProbe { save_version } = format.parse(save)?;
+    let mut state = match save_version {
+        0 => format.parse::<v0::GameState>(save)?.into(),
+        SAVE_VERSION => format.parse::<GameState>(save)?,
+        newer => return Err(SaveError::UnsupportedVersion(newer)),
+    };
+    validate(&state)?;
+    state
+        .entities
+        .adopt(state.enemies.iter_mut().map(|enemy| &mut enemy.id));
+    state.save_version = SAVE_VERSION;
+    state.next_seed = state.rng.seed();
+    state.next_mutators.clone_from(&state.mutators);
+    state.next_mode = state.mode;
+    Ok(state)
+}
+
+fn is_positive(value: f32) -> bool {
+    value.is_finite() && value > 0.
+}
+
+/// Catches hand-edited or corrupted saves that would break the simulation.
+fn validate(state: &GameState) -> Result<(), SaveError> {
+    let spawners = || std::iter::once(&state.enemy_spawner).chain(&state.extra_spawners);
+    let mut timers = [
+        &state.excellency.basic_attack.cooldown_timer,
+        &state.excellency.big_attack.cooldown_timer,
+        &state.waves.timer,
+    ]
+    .into_iter()
+    .chain(spawners().map(|spawner| &spawner.timer));
+    if timers.any(|timer| !is_positive(timer.total) || !timer.remaining.is_finite()) {
+        return Err(SaveError::Invalid("timers must have a positive duration"));
+    }
+    if !is_positive(state.excellency.hp.maximum)
+        || !spawners().all(|spawner| is_positive(spawner.interval))
+    {
+        return Err(SaveError::Invalid(
+            "maximum HP and spawn interval must be positive",
+        ));
+    }
+    let mut hit_points =
+        std::iter::once(&state.excellency.hp).chain(state.enemies.iter().map(|enemy| &enemy.hp));
+    if hit_points.any(|hp| !hp.current.is_finite() || !hp.maximum.is_finite()) {
+        return Err(SaveError::Invalid("hit points must be finite"));
+    }
+    if !is_positive(state.waves.active_duration) || !is_positive(state.waves.break_duration) {
+        return Err(SaveError::Invalid(
+            "wave phases must have a positive duration",
+        ));
+    }
+    if !is_positive(state.game_speed) {
+        return Err(SaveError::Invalid("game speed must be positive"));
+    }
+    if !state.elapsed.is_finite() || state.elapsed < 0. {
+        return Err(SaveError::Invalid("elapsed time must be non-negative"));
+    }
+    Ok(())
+}
+
+/// The original layout: no economy, elites, healers, scaling or seeded randomness.
+mod v0 {
+    use crate::{app, combat, enemy, timer};
+
+    #[derive(serde::Deserialize)]
+    pub struct GameState {
+        excellency: Excellency,
+        enemies: Vec<Enemy>,
+        enemy_spawner: EnemySpawner,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct EnemySpawner {
+        timer: Timer,
+        maximum_hp: f32,
+        speed: f32,
+        damage: f32,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Timer {
+        total: f32,
+        remaining: f32,
+        one_shot: bool,
+        paused: bool,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Excellency {
+        hp: HitPoints,
+        basic_attack: BasicAttack,
+        big_attack: BasicAttack,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct BasicAttack {
+        cooldown_timer: Timer,
+        damage: f32,
+        range: f32,
+        max_targets: usize,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Enemy {
+        hp: HitPoints,
+        damage: f32,
+        speed: f32,
+        distance: Distance,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Distance(f32);
+
+    #[derive(serde::Deserialize)]
+    struct HitPoints {
+        maximum: f32,
+        current: f32,
+    }
+
+    impl From<Timer> for timer::Timer {
+        fn from(timer: Timer) -> Self {
+            Self {
+                total: timer.total,
+                remaining: timer.remaining,
+                one_shot: timer.one_shot,
+                paused: timer.paused,
+            }
+        }
+    }
+
+    impl From<HitPoints> for combat::HitPoints {
+        fn from(hp: HitPoints) -> Self {
+            Self {
+                maximum: hp.maximum,
+                current: hp.current,
+                overheal: None,
+            }
+        }
+    }
+
+    impl BasicAttack {
+        fn upgrade(self, attack: &mut combat::BasicAttack) {
+            attack.cooldown_timer = self.cooldown_timer.into();
+            attack.damage = self.damage;
+            attack.range = self.range;
+            attack.max_targets = self.max_targets;
+        }
+    }
+
+    impl From<GameState> for app::GameState {
+        fn from(old: GameState) -> Self {
+            let mut state = app::GameState::default();
+
+            state.excellency.hp = old.excellency.hp.into();
+            old.excellency
+                .basic_attack
+                .upgrade(&mut state.excellency.basic_attack);
+            old.excellency
+                .big_attack
+                .upgrade(&mut state.excellency.big_attack);
+
+            let spawner = &mut state.enemy_spawner;
+            spawner.interval = old.enemy_spawner.timer.total;
+            spawner.timer = old.enemy_spawner.timer.into();
+            spawner.maximum_hp = old.enemy_spawner.maximum_hp;
+            spawner.speed = old.enemy_spawner.speed;
+            spawner.damage = old.enemy_spawner.damage;
+
+            state.enemies = old
+                .enemies
+                .into_iter()
+                .map(|enemy| enemy::Enemy {
+                    hp: enemy.hp.into(),
+                    damage: enemy.damage,
+                    speed: enemy.speed,
+                    ..enemy::Enemy::new(0., enemy.distance.0)
+                })
+                .collect();
+
+            state
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Gameplay extensions hooked into the simulation and the player panel.
+//!
+//! A plugin sees what happens in a run without owning any of it, which keeps optional
+//! features like statistics or achievements out of `GameState::step`. Plugins live as long
+//! as their run: a restart or a loaded save starts them afresh.
+
+use std::collections::BTreeMap;
+
+use crate::enemy::Enemy;
+use crate::events::GameEvent;
+use crate::i18n::{tr, trf};
+
+pub trait GamePlugin {
+    /// Heading of the plugin's section in the player panel.
+    fn name(&self) -> &'static str;
+
+    /// After every simulation step, with the enemies still on the field.
+    fn on_tick(&mut self, _delta: f32, _elapsed: f32, _enemies: &[Enemy]) {}
+
+    /// Every event of the run, as it's emitted.
+    fn on_event(&mut self, _event: &GameEvent) {}
+
+    /// Drawn in a collapsible section of its own, if `has_ui_panel`.
+    fn ui_panel(&mut self, _ui: &mut egui::Ui) {}
+
+    fn has_ui_panel(&self) -> bool {
+        false
+    }
+}
+
+/// The plugins every run starts with.
+pub fn built_in() -> Vec<Box<dyn GamePlugin>> {
+    vec![Box::<RunStatistics>::default()]
+}
+
+/// Spawns and kills per archetype, and how crowded the field got.
+#[derive(Default)]
+struct RunStatistics {
+    /// Keyed by archetype, `(spawned, killed)`.
+    archetypes: BTreeMap<String, (u32, u32)>,
+    bosses_slain: u32,
+    most_enemies_on_field: usize,
+}
+
+impl RunStatistics {
+    fn entry(&mut self, enemy: &Enemy) -> &mut (u32, u32) {
+        let archetype = match enemy.archetype.is_empty() {
+            true => tr("Unknown"),
+            false => &enemy.archetype,
+        };
+        self.archetypes.entry(archetype.to_owned()).or_default()
+    }
+}
+
+impl GamePlugin for RunStatistics {
+    fn name(&self) -> &'static str {
+        tr("Run statistics")
+    }
+
+    fn on_tick(&mut self, _delta: f32, _elapsed: f32, enemies: &[Enemy]) {
+        self.most_enemies_on_field = self.most_enemies_on_field.max(enemies.len());
+    }
+
+    fn on_event(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::EnemySpawned(enemy) => self.entry(enemy).0 += 1,
+            GameEvent::EnemyKilled(enemy) => {
+                self.entry(enemy).1 += 1;
+                if enemy.is_boss {
+                    self.bosses_slain += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn ui_panel(&mut self, ui: &mut egui::Ui) {
+        ui.label(trf(
+            "Most enemies at once: {}",
+            &[self.most_enemies_on_field.to_string()],
+        ));
+        ui.label(trf("Bosses slain: {}", &[self.bosses_slain.to_string()]));
+        egui::Grid::new("run_statistics")
+            .striped(true)
+            .show(ui, |ui| {
+                for header in [tr("Archetype"), tr("Spawned"), tr("Killed")] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for (archetype, (spawned, killed)) in &self.archetypes {
+                    ui.label(archetype);
+                    ui.label(spawned.to_string());
+                    ui.label(killed.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn has_ui_panel(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,362 @@
+//! Abilities written in Rhai, compiled only with the `scripting` feature.
+//!
+//! Every `*.rhai` file in `SCRIPTS_DIR` is an ability firing on its own cooldown. A script
+//! defines some of these functions:
+//!
+//! - `cooldown()`: seconds between casts, 5 if missing.
+//! - `on_cast(targets)`: `targets` are the enemies as maps, closest first. Returns an array
+//!   of hits like `#{ target: 0, damage: 5.0 }`, where `target` indexes into `targets`.
+//!   Instead of `damage` a hit may take a fraction of the target's HP with `current_hp` or
+//!   `max_hp`, like `#{ target: 0, max_hp: 0.1 }`. With `true_damage: true` a hit goes
+//!   straight through armor and shields.
+//! - `on_hit(target, landed)`: called after each hit with the target's updated map and the
+//!   damage that got through. May return effects like `#{ slow: 0.8, armor: -1.0 }`, or
+//!   `stun` and `freeze` holding the target for that many seconds, with diminishing returns.
+//!
+//! Target maps hold `distance`, `hp`, `max_hp`, `armor`, `shield`, `speed`, `regeneration`,
+//! `archetype`, `modifiers` (elite modifier names), `elite`, `boss` and `healer`.
+
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+
+use crate::combat::{AttackOutcome, DamageSpec, DamageType};
+use crate::crowd_control::{Control, CrowdControl};
+use crate::durations;
+use crate::enemy::{remove_dead, Enemy, EnemyKind};
+use crate::i18n::{tr, trf};
+use crate::timer::Timer;
+use crate::world::Components;
+
+pub const SCRIPTS_DIR: &str = "scripts";
+const DEFAULT_COOLDOWN: f32 = 5.;
+/// Stops runaway scripts, a cast taking more operations than this fails.
+const MAX_OPERATIONS: u64 = 100_000;
+
+struct ScriptedAbility {
+    name: String,
+    ast: AST,
+    cooldown_timer: Timer,
+    has_on_hit: bool,
+    /// The last runtime error, cleared by the next successful cast.
+    error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct Scripts {
+    engine: Engine,
+    abilities: Vec<ScriptedAbility>,
+    /// Scripts that failed to compile, with why.
+    load_errors: Vec<String>,
+}
+
+impl Scripts {
+    /// Compiles every script in `SCRIPTS_DIR`, in file name order.
+    pub fn load() -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        let mut scripts = Self {
+            engine,
+            ..Default::default()
+        };
+        for (name, source) in read_scripts() {
+            match scripts.compile(name.clone(), &source) {
+                Ok(ability) => scripts.abilities.push(ability),
+                Err(err) => {
+                    log::error!("Failed to load the script {name}: {err}");
+                    scripts.load_errors.push(format!("{name}: {err}"));
+                }
+            }
+        }
+        scripts
+    }
+
+    /// Scripts that failed to compile, with why.
+    #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+    pub fn load_errors(&self) -> &[String] {
+        &self.load_errors
+    }
+
+    #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+    pub fn len(&self) -> usize {
+        self.abilities.len()
+    }
+
+    /// Each ability's name with the fraction of its cooldown left.
+    pub fn cooldowns(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.abilities.iter().map(|ability| {
+            let timer = &ability.cooldown_timer;
+            (ability.name.as_str(), timer.remaining_fraction())
+        })
+    }
+
+    fn compile(&self, name: String, source: &str) -> Result<ScriptedAbility, String> {
+        let ast = self.engine.compile(source).map_err(|err| err.to_string())?;
+        let defines = |function: &str| ast.iter_functions().any(|f| f.name == function);
+        if !defines("on_cast") {
+            return Err("no on_cast function".to_owned());
+        }
+        let has_on_hit = defines("on_hit");
+        let cooldown = if defines("cooldown") {
+            let cooldown = self
+                .engine
+                .call_fn::<Dynamic>(&mut Scope::new(), &ast, "cooldown", ())
+                .map_err(|err| err.to_string())?;
+            number(&cooldown)
+                .filter(|cooldown| *cooldown > 0.)
+                .ok_or("cooldown() has to return a positive number")?
+        } else {
+            DEFAULT_COOLDOWN
+        };
+        Ok(ScriptedAbility {
+            name,
+            ast,
+            cooldown_timer: Timer::new(cooldown),
+            has_on_hit,
+            error: None,
+        })
+    }
+
+    /// Casts every ability whose cooldown finished, one outcome per cast.
+    pub fn tick(
+        &mut self,
+        delta: f32,
+        enemies: &mut Vec<Enemy>,
+        crowd_control: &mut Components<CrowdControl>,
+    ) -> Vec<AttackOutcome> {
+        let mut outcomes = vec![];
+        for ability in &mut self.abilities {
+            for _ in 0..ability.cooldown_timer.tick(delta) {
+                let (outcome, result) = cast(&self.engine, ability, enemies, crowd_control);
+                outcomes.push(outcome);
+                match result {
+                    Ok(()) => ability.error = None,
+                    Err(err) => {
+                        if ability.error.is_none() {
+                            log::warn!("The script {} failed: {err}", ability.name);
+                        }
+                        ability.error = Some(err);
+                    }
+                }
+            }
+        }
+        outcomes
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui, cooldown_color: egui::Color32) {
+        if self.abilities.is_empty() && self.load_errors.is_empty() {
+            ui.label(trf(
+                "Put .rhai scripts in {} to add abilities.",
+                &[SCRIPTS_DIR.to_owned()],
+            ));
+        }
+        for ability in &self.abilities {
+            ui.horizontal(|ui| {
+                ui.label(&ability.name);
+                ui.add(
+                    egui::ProgressBar::new(ability.cooldown_timer.remaining_fraction())
+                        .fill(cooldown_color)
+                        .text(durations::format(ability.cooldown_timer.remaining)),
+                );
+            });
+            if let Some(err) = &ability.error {
+                ui.colored_label(ui.visuals().warn_fg_color, err);
+            }
+        }
+        for err in &self.load_errors {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                trf("Not loaded: {}", std::slice::from_ref(err)),
+            );
+        }
+        if !self.load_errors.is_empty() {
+            #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+            let hint = tr("Fix the scripts, saved scripts reload automatically.");
+            #[cfg(not(all(feature = "hot-reload", not(target_arch = "wasm32"))))]
+            let hint = tr("Fix the scripts and restart the game to load them.");
+            ui.label(hint);
+        }
+    }
+}
+
+/// Runs one cast. Hits landed before a script error still count, so the outcome always
+/// comes back.
+fn cast(
+    engine: &Engine,
+    ability: &ScriptedAbility,
+    enemies: &mut Vec<Enemy>,
+    crowd_control: &mut Components<CrowdControl>,
+) -> (AttackOutcome, Result<(), String>) {
+    let mut outcome = AttackOutcome {
+        killed: vec![],
+        damage_dealt: 0.,
+        true_damage: 0.,
+        hits: vec![],
+    };
+    let result = apply_hits(engine, ability, enemies, crowd_control, &mut outcome);
+    outcome.killed = remove_dead(enemies);
+    (outcome, result)
+}
+
+fn apply_hits(
+    engine: &Engine,
+    ability: &ScriptedAbility,
+    enemies: &mut [Enemy],
+    crowd_control: &mut Components<CrowdControl>,
+    outcome: &mut AttackOutcome,
+) -> Result<(), String> {
+    let targets: Array = enemies
+        .iter()
+        .map(|enemy| Dynamic::from(to_map(enemy)))
+        .collect();
+    let hits = engine
+        .call_fn::<Dynamic>(&mut Scope::new(), &ability.ast, "on_cast", (targets,))
+        .map_err(|err| err.to_string())?;
+    let hits = match hits.is_unit() {
+        true => Array::new(),
+        false => hits
+            .try_cast::<Array>()
+            .ok_or("on_cast() has to return an array of hits")?,
+    };
+
+    for hit in hits {
+        let hit = hit.try_cast::<Map>().ok_or("a hit has to be a map")?;
+        let target = hit
+            .get("target")
+            .and_then(|target| target.as_int().ok())
+            .and_then(|target| usize::try_from(target).ok())
+            .filter(|&target| target < enemies.len())
+            .ok_or("a hit needs a valid target index")?;
+        let damage =
+            hit_damage(&hit).ok_or("a hit needs a non-negative damage, current_hp or max_hp")?;
+
+        let damage_type = match hit.get("true_damage").map(Dynamic::as_bool) {
+            None | Some(Ok(false)) => DamageType::Normal,
+            Some(Ok(true)) => DamageType::True,
+            Some(Err(_)) => return Err("true_damage has to be true or false".to_owned()),
+        };
+
+        let enemy = &mut enemies[target];
+        let landed = enemy.take_hit(damage, damage_type);
+        outcome.damage_dealt += landed;
+        if damage_type == DamageType::True {
+            outcome.true_damage += landed;
+        }
+        if ability.has_on_hit {
+            let effects = engine
+                .call_fn::<Dynamic>(
+                    &mut Scope::new(),
+                    &ability.ast,
+                    "on_hit",
+                    (to_map(enemy), landed as rhai::FLOAT),
+                )
+                .map_err(|err| err.to_string())?;
+            if let Some(effects) = effects.try_cast::<Map>() {
+                apply_effects(enemy, crowd_control.entry(enemy.id), &effects)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The damage of `hit` under whichever of its damage keys it has, see the module docs.
+fn hit_damage(hit: &Map) -> Option<DamageSpec> {
+    let amount = |key: &str| hit.get(key).and_then(number).filter(|amount| *amount >= 0.);
+    amount("damage")
+        .map(DamageSpec::Flat)
+        .or_else(|| amount("current_hp").map(DamageSpec::CurrentHp))
+        .or_else(|| amount("max_hp").map(DamageSpec::MaximumHp))
+}
+
+fn apply_effects(
+    enemy: &mut Enemy,
+    crowd_control: &mut CrowdControl,
+    effects: &Map,
+) -> Result<(), String> {
+    for (effect, value) in effects {
+        let value = number(value).ok_or_else(|| format!("effect {effect} needs a number"))?;
+        match effect.as_str() {
+            "slow" => enemy.speed *= value.clamp(0., 1.),
+            "armor" => enemy.armor = (enemy.armor + value).max(0.),
+            "shield" => enemy.shield = (enemy.shield + value).max(0.),
+            "regeneration" => enemy.regeneration = (enemy.regeneration + value).max(0.),
+            "stun" => {
+                crowd_control.apply(Control::Stun, value.max(0.));
+            }
+            "freeze" => {
+                crowd_control.apply(Control::Freeze, value.max(0.));
+            }
+            _ => return Err(format!("unknown effect {effect}")),
+        }
+    }
+    Ok(())
+}
+
+fn to_map(enemy: &Enemy) -> Map {
+    let modifiers: Array = enemy
+        .modifiers
+        .iter()
+        .map(|modifier| Dynamic::from(format!("{modifier:?}")))
+        .collect();
+    let mut map = Map::new();
+    for (key, value) in [
+        ("distance", enemy.distance.0),
+        ("hp", enemy.hp.current),
+        ("max_hp", enemy.hp.maximum),
+        ("armor", enemy.armor),
+        ("shield", enemy.shield),
+        ("speed", enemy.speed),
+        ("regeneration", enemy.regeneration),
+    ] {
+        map.insert(key.into(), Dynamic::from(value as rhai::FLOAT));
+    }
+    map.insert("archetype".into(), Dynamic::from(enemy.archetype.clone()));
+    map.insert("modifiers".into(), Dynamic::from(modifiers));
+    map.insert("elite".into(), Dynamic::from(enemy.is_elite()));
+    map.insert("boss".into(), Dynamic::from(enemy.is_boss));
+    map.insert(
+        "healer".into(),
+        Dynamic::from(enemy.kind == EnemyKind::Healer),
+    );
+    map
+}
+
+/// Scripts may write `5` or `5.0`.
+fn number(value: &Dynamic) -> Option<f32> {
+    value
+        .as_float()
+        .ok()
+        .map(|value| value as f32)
+        .or_else(|| value.as_int().ok().map(|value| value as f32))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_scripts() -> Vec<(String, String)> {
+    let Ok(entries) = std::fs::read_dir(SCRIPTS_DIR) else {
+        return vec![];
+    };
+    let mut scripts: Vec<(String, String)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "rhai")
+        })
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            match std::fs::read_to_string(&path) {
+                Ok(source) => Some((name, source)),
+                Err(err) => {
+                    log::error!("Failed to read {}: {err}", path.display());
+                    None
+                }
+            }
+        })
+        .collect();
+    scripts.sort_by(|a, b| a.0.cmp(&b.0));
+    scripts
+}
+
+/// The browser has no scripts folder.
+#[cfg(target_arch = "wasm32")]
+fn read_scripts() -> Vec<(String, String)> {
+    vec![]
+}
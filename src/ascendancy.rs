@@ -0,0 +1,131 @@
+//! Ascension, the prestige layer above New Game+: runs reaching deep enough pay Ascendancy
+//! shards, spent on a grid of permanent perks every later fresh run starts with. Persisted
+//! per profile under `ASCENDANCY_KEY`, next to the records.
+//!
+//! The Excellency's abilities are fixed, so the extra slot the grid offers is a talent slot.
+
+use std::collections::BTreeMap;
+
+use crate::character_sheet::{CharacterStat, Modifier};
+use crate::combat::AttackKind;
+use crate::excellency::Excellency;
+use crate::i18n::tr;
+
+pub const ASCENDANCY_KEY: &str = "ascendancy";
+
+/// Wave a run has to reach to pay any shards at all.
+pub const MIN_WAVE: u32 = 10;
+/// Waves past `MIN_WAVE` for every further shard.
+const WAVES_PER_SHARD: u32 = 5;
+/// Gold every rank of `Perk::StartingGold` starts a run with.
+const GOLD_PER_RANK: u32 = 25;
+/// Damage multiplier on both attacks for every rank of `Perk::BaseDamage`.
+const DAMAGE_PER_RANK: f32 = 0.05;
+
+/// Shards a finished run reaching `wave` pays, every New Game+ cycle multiplying them.
+pub fn shards(wave: u32, new_game_plus: u32) -> u32 {
+    if wave < MIN_WAVE {
+        return 0;
+    }
+    (1 + (wave - MIN_WAVE) / WAVES_PER_SHARD) * (1 + new_game_plus)
+}
+
+#[derive(
+    serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug,
+)]
+pub enum Perk {
+    StartingGold,
+    BaseDamage,
+    ExtraTalentSlot,
+}
+
+impl Perk {
+    pub const ALL: [Perk; 3] = [Perk::StartingGold, Perk::BaseDamage, Perk::ExtraTalentSlot];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Perk::StartingGold => "Starting gold",
+            Perk::BaseDamage => "Base damage",
+            Perk::ExtraTalentSlot => "Extra talent slot",
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        tr(match self {
+            Perk::StartingGold => "+25 gold at the start of every run per rank.",
+            Perk::BaseDamage => "+5% damage for both attacks per rank.",
+            Perk::ExtraTalentSlot => "A talent to pick right at the start of every run.",
+        })
+    }
+
+    pub fn max_rank(&self) -> u32 {
+        match self {
+            Perk::StartingGold | Perk::BaseDamage => 5,
+            Perk::ExtraTalentSlot => 1,
+        }
+    }
+
+    /// What `rank` of it does to the stats, nothing for the perks that aren't stats.
+    pub fn modifiers(&self, rank: u32) -> Vec<(CharacterStat, Modifier)> {
+        match self {
+            Perk::BaseDamage => AttackKind::ALL
+                .into_iter()
+                .map(|kind| {
+                    let damage = 1. + rank as f32 * DAMAGE_PER_RANK;
+                    (CharacterStat::Damage(kind), Modifier::Multiply(damage))
+                })
+                .collect(),
+            Perk::StartingGold | Perk::ExtraTalentSlot => vec![],
+        }
+    }
+
+    /// Shards the rank after `rank` costs.
+    pub fn cost(&self, rank: u32) -> u32 {
+        let base = match self {
+            Perk::StartingGold => 1,
+            Perk::BaseDamage => 2,
+            Perk::ExtraTalentSlot => 10,
+        };
+        base * (rank + 1)
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Default, Debug)]
+#[serde(default)]
+pub struct Ascendancy {
+    /// Unspent.
+    pub shards: u32,
+    /// Ranks bought of each perk, the ones never bought left out.
+    ranks: BTreeMap<Perk, u32>,
+}
+
+impl Ascendancy {
+    pub fn rank(&self, perk: Perk) -> u32 {
+        self.ranks.get(&perk).copied().unwrap_or(0)
+    }
+
+    /// Spends shards on the next rank of `perk`, false if it's unaffordable or maxed out.
+    pub fn buy(&mut self, perk: Perk) -> bool {
+        let rank = self.rank(perk);
+        let cost = perk.cost(rank);
+        if rank >= perk.max_rank() || self.shards < cost {
+            return false;
+        }
+        self.shards -= cost;
+        self.ranks.insert(perk, rank + 1);
+        true
+    }
+
+    /// Gives a fresh run's `excellency` every perk bought.
+    pub fn apply(&self, excellency: &mut Excellency) {
+        for (&perk, &rank) in &self.ranks {
+            for (stat, modifier) in perk.modifiers(rank) {
+                excellency.remember_base(stat);
+                stat.set(excellency, modifier.apply(stat.value(excellency)));
+            }
+            excellency.perks.push((perk, rank));
+        }
+        excellency.gold += self.rank(Perk::StartingGold) * GOLD_PER_RANK;
+        excellency.extra_talents = self.rank(Perk::ExtraTalentSlot) as usize;
+    }
+}
@@ -0,0 +1,288 @@
+//! Sound effects, decoupled from the simulation through its `GameEvent`s.
+//!
+//! `GameState::step` only records what happened, the UI turns the frame's events into a
+//! queue of `SoundEvent`s and hands it to `Audio`. Without the `audio` feature nothing is
+//! played at all.
+
+use crate::events::GameEvent;
+use crate::i18n::tr;
+
+/// Volume preferences, all volumes are in `0..=1`.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SoundSettings {
+    pub muted: bool,
+    pub master_volume: f32,
+    pub effects_volume: f32,
+    pub music_volume: f32,
+    /// Go quiet while the window is in the background.
+    pub mute_when_unfocused: bool,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            master_volume: 1.,
+            effects_volume: 1.,
+            music_volume: 1.,
+            mute_when_unfocused: true,
+        }
+    }
+}
+
+impl SoundSettings {
+    fn master(&self, focused: bool) -> f32 {
+        match self.muted || (self.mute_when_unfocused && !focused) {
+            true => 0.,
+            false => self.master_volume,
+        }
+    }
+
+    pub fn effects(&self, focused: bool) -> f32 {
+        self.master(focused) * self.effects_volume
+    }
+
+    pub fn music(&self, focused: bool) -> f32 {
+        self.master(focused) * self.music_volume
+    }
+
+    pub fn show_editor(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.muted, tr("Mute"));
+        ui.add_enabled_ui(!self.muted, |ui| {
+            egui::Grid::new("volumes").show(ui, |ui| {
+                for (name, volume) in [
+                    (tr("Master"), &mut self.master_volume),
+                    (tr("Effects"), &mut self.effects_volume),
+                    (tr("Music"), &mut self.music_volume),
+                ] {
+                    ui.label(name);
+                    ui.add(
+                        egui::Slider::new(volume, 0.0..=1.)
+                            .custom_formatter(|volume, _| format!("{:.0}%", volume * 100.)),
+                    );
+                    ui.end_row();
+                }
+            });
+        });
+        ui.checkbox(
+            &mut self.mute_when_unfocused,
+            tr("Mute when the window is in the background"),
+        );
+    }
+}
+
+/// Which background track should be playing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MusicMood {
+    Calm,
+    Intense,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SoundEvent {
+    AttackFired,
+    EnemyDied,
+    ExcellencyHit,
+    BossSpawned,
+}
+
+impl SoundEvent {
+    /// The sound `event` makes, if any.
+    pub fn for_event(event: &GameEvent) -> Option<Self> {
+        match event {
+            // Aura ticks sound like attacks, the queue keeps a swarm burning from piling up
+            GameEvent::AttackFired { .. }
+            | GameEvent::UltimateFired { .. }
+            | GameEvent::DamageTicked { .. } => Some(SoundEvent::AttackFired),
+            GameEvent::EnemyKilled(_) => Some(SoundEvent::EnemyDied),
+            GameEvent::PlayerHit { .. } => Some(SoundEvent::ExcellencyHit),
+            GameEvent::EnemySpawned(enemy) if enemy.is_boss => Some(SoundEvent::BossSpawned),
+            GameEvent::DamageDealt { .. }
+            | GameEvent::BossPhaseEntered { .. }
+            | GameEvent::Dodged
+            | GameEvent::AttackInterrupted { .. }
+            | GameEvent::IncidentStruck(_)
+            | GameEvent::EnemySpawned(_)
+            | GameEvent::WaveStarted { .. }
+            | GameEvent::WaveCalledEarly { .. }
+            | GameEvent::RelicFound(_)
+            | GameEvent::EnemyEscaped(_) => None,
+        }
+    }
+}
+
+/// Sounds waiting to be played, at most one of each kind per frame.
+#[derive(Default)]
+pub struct SoundQueue(Vec<SoundEvent>);
+
+impl SoundQueue {
+    pub fn push(&mut self, event: SoundEvent) {
+        if !self.0.contains(&event) {
+            self.0.push(event);
+        }
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = SoundEvent> + '_ {
+        self.0.drain(..)
+    }
+}
+
+#[derive(Default)]
+pub struct Audio {
+    #[cfg(feature = "audio")]
+    output: backend::Output,
+}
+
+impl Audio {
+    pub fn play(&mut self, events: impl Iterator<Item = SoundEvent>, volume: f32) {
+        #[cfg(feature = "audio")]
+        for event in events {
+            if volume > 0. {
+                self.output.play(event, volume);
+            }
+        }
+        #[cfg(not(feature = "audio"))]
+        {
+            let _ = volume;
+            events.for_each(drop);
+        }
+    }
+
+    /// Crossfades towards the track for `mood`, call once per frame with the frame time.
+    pub fn update_music(&mut self, mood: MusicMood, delta: f32, volume: f32) {
+        #[cfg(feature = "audio")]
+        self.output.update_music(mood, delta, volume);
+        #[cfg(not(feature = "audio"))]
+        let _ = (mood, delta, volume);
+    }
+}
+
+#[cfg(feature = "audio")]
+mod backend {
+    use std::time::Duration;
+
+    use rodio::source::{SineWave, Source};
+
+    use super::{MusicMood, SoundEvent};
+
+    const MUSIC_VOLUME: f32 = 0.05;
+    const CROSSFADE_SECONDS: f32 = 2.;
+
+    /// The output device is opened lazily, so headless uses of `GameState` never touch it.
+    #[derive(Default)]
+    pub enum Output {
+        #[default]
+        Unopened,
+        Open {
+            /// Dropping the stream stops all sound.
+            _stream: rodio::OutputStream,
+            handle: rodio::OutputStreamHandle,
+            music: Option<Music>,
+        },
+        Unavailable,
+    }
+
+    impl Output {
+        fn open(&mut self) {
+            if let Output::Unopened = self {
+                *self = match rodio::OutputStream::try_default() {
+                    Ok((_stream, handle)) => Output::Open {
+                        _stream,
+                        handle,
+                        music: None,
+                    },
+                    Err(err) => {
+                        log::warn!("No audio output, sounds are disabled: {err}");
+                        Output::Unavailable
+                    }
+                };
+            }
+        }
+
+        pub fn update_music(&mut self, mood: MusicMood, delta: f32, volume: f32) {
+            self.open();
+            if let Output::Open { handle, music, .. } = self {
+                if music.is_none() {
+                    *music = Music::start(handle);
+                }
+                if let Some(music) = music {
+                    music.update(mood, delta, volume);
+                }
+            }
+        }
+
+        pub fn play(&mut self, event: SoundEvent, volume: f32) {
+            self.open();
+            if let Output::Open { handle, .. } = self {
+                let (frequency, millis) = tone(event);
+                let source = SineWave::new(frequency)
+                    .take_duration(Duration::from_millis(millis))
+                    .fade_in(Duration::from_millis(5))
+                    .amplify(0.15 * volume);
+                if let Err(err) = handle.play_raw(source) {
+                    log::warn!("Failed to play a sound: {err}");
+                }
+            }
+        }
+    }
+
+    /// Both tracks loop forever, the crossfade only moves their volumes.
+    pub struct Music {
+        calm: rodio::Sink,
+        intense: rodio::Sink,
+        /// 0 is fully calm, 1 fully intense.
+        blend: f32,
+    }
+
+    impl Music {
+        fn start(handle: &rodio::OutputStreamHandle) -> Option<Self> {
+            let sink = |frequencies: &[f32]| -> Option<rodio::Sink> {
+                let sink = rodio::Sink::try_new(handle)
+                    .map_err(|err| log::warn!("Failed to start music: {err}"))
+                    .ok()?;
+                let mut voices = frequencies
+                    .iter()
+                    .map(|&frequency| SineWave::new(frequency));
+                let first = voices.next()?;
+                let chord: Box<dyn Source<Item = f32> + Send> =
+                    voices.fold(Box::new(first), |chord, voice| Box::new(chord.mix(voice)));
+                sink.append(chord);
+                sink.set_volume(0.);
+                Some(sink)
+            };
+            Some(Self {
+                // A soft A minor pad
+                calm: sink(&[110., 130.81, 164.81])?,
+                // Close frequencies beat against each other, which sounds restless
+                intense: sink(&[110., 113.5, 155.56, 233.08])?,
+                blend: 0.,
+            })
+        }
+
+        fn update(&mut self, mood: MusicMood, delta: f32, volume: f32) {
+            let target = match mood {
+                MusicMood::Calm => 0.,
+                MusicMood::Intense => 1.,
+            };
+            let step = delta / CROSSFADE_SECONDS;
+            self.blend = match self.blend < target {
+                true => (self.blend + step).min(target),
+                false => (self.blend - step).max(target),
+            };
+            let volume = volume * MUSIC_VOLUME;
+            self.calm.set_volume(volume * (1. - self.blend));
+            self.intense.set_volume(volume * self.blend);
+        }
+    }
+
+    /// Sounds are synthesized, so there are no assets to ship.
+    fn tone(event: SoundEvent) -> (f32, u64) {
+        match event {
+            SoundEvent::AttackFired => (660., 40),
+            SoundEvent::EnemyDied => (330., 90),
+            SoundEvent::ExcellencyHit => (110., 160),
+            SoundEvent::BossSpawned => (80., 400),
+        }
+    }
+}
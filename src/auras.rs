@@ -0,0 +1,107 @@
+//! Passive auras around the Excellency, working on every enemy within their radius.
+
+use std::ops::RangeInclusive;
+
+use egui::Color32;
+
+use crate::effects::{DamageOverTime, Effect, Stacking, Stat};
+use crate::i18n::tr;
+
+/// How long the frost slow outlasts leaving the aura.
+const FROST_LINGER: f32 = 0.5;
+/// Slow per stack at `FROST_RADIUS`, spread thinner over a wider radius.
+const FROST_SLOW: f32 = -0.1;
+const FROST_STACKS: u32 = 3;
+/// Seconds inside the aura each stack after the first takes.
+const FROST_STACK_INTERVAL: f32 = 1.;
+const FROST_RADIUS: f32 = 15.;
+/// HP burnt every `BURN_INTERVAL`, armor and shields don't help against it.
+pub const BURN_DAMAGE: f32 = 1.5;
+pub const BURN_INTERVAL: f32 = 0.5;
+/// How long the burn outlasts leaving the aura, enough for one more tick.
+const BURN_LINGER: f32 = 0.5;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Aura {
+    Frost,
+    Burn,
+}
+
+impl Aura {
+    pub const ALL: [Aura; 2] = [Aura::Frost, Aura::Burn];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Aura::Frost => "Frost aura",
+            Aura::Burn => "Burning aura",
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        tr(match self {
+            Aura::Frost => "Slows enemies inside it by 10% more every second they stay, up to 30%. A wider radius slows less.",
+            Aura::Burn => "Burns enemies within 10 distance for 3 HP per second.",
+        })
+    }
+
+    /// The radius it starts with, see `Excellency::aura_radius`.
+    pub fn radius(&self) -> f32 {
+        match self {
+            Aura::Frost => FROST_RADIUS,
+            Aura::Burn => 10.,
+        }
+    }
+
+    /// How far the player may set the radius, if at all.
+    pub fn radius_range(&self) -> Option<RangeInclusive<f32>> {
+        match self {
+            Aura::Frost => Some(8.0..=30.),
+            Aura::Burn => None,
+        }
+    }
+
+    /// Gold it takes to unlock.
+    pub fn cost(&self) -> u32 {
+        match self {
+            Aura::Frost => 50,
+            Aura::Burn => 100,
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            Aura::Frost => Color32::from_rgb(120, 200, 255),
+            Aura::Burn => Color32::from_rgb(255, 120, 40),
+        }
+    }
+
+    /// The debuff put on enemies inside the aura when it reaches out to `radius`, refreshed
+    /// every step they stay.
+    pub fn debuff(&self, radius: f32) -> Option<Effect> {
+        match self {
+            Aura::Frost => Some(Effect {
+                // Not translated, the name is what keeps it from stacking with itself
+                name: "Frost aura".to_owned(),
+                stat: Stat::Speed,
+                amount: FROST_SLOW * FROST_RADIUS / radius.max(1.),
+                duration: FROST_LINGER,
+                stacking: Stacking::Building {
+                    max_stacks: FROST_STACKS,
+                    interval: FROST_STACK_INTERVAL,
+                },
+                damage_over_time: None,
+            }),
+            Aura::Burn => Some(Effect {
+                name: "Burning aura".to_owned(),
+                stat: Stat::Speed,
+                amount: 0.,
+                duration: BURN_LINGER,
+                stacking: Stacking::Refresh,
+                damage_over_time: Some(DamageOverTime {
+                    damage: BURN_DAMAGE,
+                    interval: BURN_INTERVAL,
+                }),
+            }),
+        }
+    }
+}
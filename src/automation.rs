@@ -0,0 +1,103 @@
+//! Late-game automation for idle play: unlocked for good with laurels, then switched on and
+//! off at will. The switched on ones are evaluated every simulation step.
+
+use crate::combat::AttackKind;
+use crate::excellency::Excellency;
+use crate::i18n::tr;
+use crate::upgrades::Upgrade;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Automation {
+    /// Unleashes the ultimate as soon as it's charged.
+    CastUltimate,
+    /// Buys the cheapest affordable upgrade whenever there's gold for it.
+    BuyUpgrades,
+    /// Calls every wave as soon as the one before ends.
+    CallWaves,
+}
+
+impl Automation {
+    pub const ALL: [Automation; 3] = [
+        Automation::CastUltimate,
+        Automation::BuyUpgrades,
+        Automation::CallWaves,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Automation::CastUltimate => "Auto-cast the ultimate",
+            Automation::BuyUpgrades => "Auto-buy upgrades",
+            Automation::CallWaves => "Auto-call waves",
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        tr(match self {
+            Automation::CastUltimate => "Unleashes the ultimate as soon as it's charged.",
+            Automation::BuyUpgrades => "Spends gold on the cheapest upgrade it can afford.",
+            Automation::CallWaves => "Cuts every break short, collecting the early bonus.",
+        })
+    }
+
+    /// Laurels it takes to unlock.
+    pub fn cost(&self) -> u32 {
+        match self {
+            Automation::CastUltimate => 5,
+            Automation::BuyUpgrades => 10,
+            Automation::CallWaves => 8,
+        }
+    }
+}
+
+/// Which automations were unlocked and which of those are switched on, kept in the records.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Automations {
+    unlocked: Vec<Automation>,
+    enabled: Vec<Automation>,
+}
+
+impl Automations {
+    pub fn is_unlocked(&self, automation: Automation) -> bool {
+        self.unlocked.contains(&automation)
+    }
+
+    /// Spends `laurels` on `automation`, switching it on. False if it's unaffordable or
+    /// already unlocked.
+    pub fn unlock(&mut self, automation: Automation, laurels: &mut u32) -> bool {
+        if self.is_unlocked(automation) || *laurels < automation.cost() {
+            return false;
+        }
+        *laurels -= automation.cost();
+        self.unlocked.push(automation);
+        self.enabled.push(automation);
+        true
+    }
+
+    pub fn is_on(&self, automation: Automation) -> bool {
+        self.enabled.contains(&automation)
+    }
+
+    /// Switches an unlocked `automation` on or off, locked ones stay off.
+    pub fn set(&mut self, automation: Automation, on: bool) {
+        self.enabled.retain(|&enabled| enabled != automation);
+        if on && self.is_unlocked(automation) {
+            self.enabled.push(automation);
+        }
+    }
+}
+
+/// The cheapest upgrade of either attack `excellency` can afford right now.
+pub fn cheapest_upgrade(excellency: &Excellency) -> Option<(AttackKind, Upgrade)> {
+    AttackKind::ALL
+        .into_iter()
+        .flat_map(|kind| Upgrade::ALL.map(|upgrade| (kind, upgrade)))
+        .filter(|&(kind, upgrade)| !upgrade.is_maxed(excellency.attack(kind)))
+        .map(|(kind, upgrade)| {
+            let cost = upgrade.cost(excellency.upgrades_bought(kind, upgrade));
+            (cost, kind, upgrade)
+        })
+        .filter(|&(cost, _, _)| cost <= excellency.gold)
+        .min_by_key(|&(cost, _, _)| cost)
+        .map(|(_, kind, upgrade)| (kind, upgrade))
+}
@@ -0,0 +1,115 @@
+//! Runs the headless simulation for many seeds and configs and prints how they fared, to
+//! judge balance changes without playing. Usage: `your_excellency_sim [config.toml]`, see
+//! `balance.toml` for the format.
+
+#![warn(clippy::all, rust_2018_idioms)]
+
+use your_excellency::mutators::Mutator;
+use your_excellency::GameState;
+
+const DEFAULT_CONFIG: &str = "balance.toml";
+
+#[derive(serde::Deserialize)]
+struct Balance {
+    /// Every config is played once per seed in `0..seeds`.
+    seeds: u64,
+    /// Simulated seconds after which a run counts as survived.
+    max_duration: f32,
+    /// Seconds per simulation step.
+    #[serde(default = "default_step")]
+    step: f32,
+    #[serde(rename = "config")]
+    configs: Vec<Config>,
+}
+
+fn default_step() -> f32 {
+    1. / 60.
+}
+
+#[derive(serde::Deserialize)]
+struct Config {
+    name: String,
+    #[serde(default)]
+    mutators: Vec<Mutator>,
+    #[serde(default)]
+    new_game_plus: u32,
+}
+
+impl Config {
+    fn start(&self, seed: u64) -> GameState {
+        let mut state = GameState::with_mutators(seed, self.mutators.clone());
+        for _ in 0..self.new_game_plus {
+            state = state.next_cycle();
+        }
+        state
+    }
+}
+
+/// Plays one run until the Excellency falls or `max_duration` passes, always taking the
+/// first talent offered.
+fn play(mut state: GameState, balance: &Balance) -> GameState {
+    while !state.is_defeated() && state.elapsed() < balance.max_duration {
+        if let Some(&talent) = state.talent_choice().and_then(|choice| choice.first()) {
+            state.choose_talent(talent);
+        }
+        state.step(balance.step);
+        // Nothing drains the events without a UI, they'd pile up for the whole run
+        state.take_events();
+    }
+    state
+}
+
+#[derive(Default)]
+struct Summary {
+    runs: u32,
+    defeats: u32,
+    survival: Vec<f32>,
+    kills: u64,
+    waves: u64,
+}
+
+impl Summary {
+    fn add(&mut self, run: &GameState) {
+        self.runs += 1;
+        self.defeats += u32::from(run.is_defeated());
+        self.survival.push(run.elapsed());
+        self.kills += u64::from(run.kills());
+        self.waves += u64::from(run.waves().number);
+    }
+
+    fn print(&mut self, name: &str) {
+        let runs = self.runs.max(1) as f32;
+        self.survival.sort_by(|a, b| a.total_cmp(b));
+        let median = self.survival.get(self.survival.len() / 2).copied();
+        println!(
+            "{name:<24} {:>5} {:>7.1}% {:>9.1} {:>9.1} {:>9.1} {:>7.1}",
+            self.runs,
+            100. * self.defeats as f32 / runs,
+            self.survival.iter().sum::<f32>() / runs,
+            median.unwrap_or_default(),
+            self.kills as f32 / runs,
+            self.waves as f32 / runs,
+        );
+    }
+}
+
+fn main() -> Result<(), String> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_CONFIG.to_owned());
+    let text = std::fs::read_to_string(&path).map_err(|err| format!("{path}: {err}"))?;
+    let balance: Balance = toml::from_str(&text).map_err(|err| format!("{path}: {err}"))?;
+
+    println!(
+        "{:<24} {:>5} {:>8} {:>9} {:>9} {:>9} {:>7}",
+        "config", "runs", "defeats", "survival", "median", "kills", "wave"
+    );
+    for config in &balance.configs {
+        let mut summary = Summary::default();
+        for seed in 0..balance.seeds {
+            summary.add(&play(config.start(seed), &balance));
+        }
+        summary.print(&config.name);
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! Bosses fight in phases: every time one falls below the next threshold of its maximum HP
+//! it changes how it fights, speeding up or calling in adds. Each boss keeps its phase in a
+//! column keyed by its handle.
+
+use crate::combat::HitPoints;
+use crate::i18n::trf;
+
+/// Fractions of the maximum HP where the next phase starts, highest first.
+pub const THRESHOLDS: [f32; 3] = [0.75, 0.5, 0.25];
+/// Speed multiplier of an enraged boss.
+pub const ENRAGE_SPEED: f32 = 1.3;
+/// Adds summoned at once.
+pub const SUMMONED_ADDS: usize = 3;
+
+/// What a boss does on entering a phase.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PhaseAction {
+    /// Walks faster, by `ENRAGE_SPEED`.
+    Enrage,
+    /// Calls `SUMMONED_ADDS` regular enemies in next to it.
+    Summon,
+}
+
+impl PhaseAction {
+    pub fn describe(&self, boss: &str) -> String {
+        match self {
+            PhaseAction::Enrage => trf("{} is enraged", &[boss.to_owned()]),
+            PhaseAction::Summon => trf("{} calls for help", &[boss.to_owned()]),
+        }
+    }
+}
+
+/// What entering `phase` does, phase 0 is the one a boss starts in.
+pub fn actions(phase: usize) -> &'static [PhaseAction] {
+    match phase {
+        1 => &[PhaseAction::Enrage],
+        2 => &[PhaseAction::Summon],
+        3 => &[PhaseAction::Enrage, PhaseAction::Summon],
+        _ => &[],
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Default, Debug)]
+pub struct BossPhase {
+    /// How many thresholds it's fallen below.
+    pub phase: usize,
+}
+
+impl BossPhase {
+    /// Moves on past every threshold `hp` has fallen below since the last call, returning
+    /// the phases entered on the way. A big hit can skip a phase, its actions still come.
+    pub fn update(&mut self, hp: &HitPoints) -> std::ops::Range<usize> {
+        let fraction = hp.current / hp.maximum;
+        let reached = THRESHOLDS
+            .iter()
+            .take_while(|&&threshold| fraction < threshold)
+            .count();
+        let entered = self.phase + 1..reached + 1;
+        self.phase = self.phase.max(reached);
+        entered
+    }
+}
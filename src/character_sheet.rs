@@ -0,0 +1,329 @@
+//! Every stat of the Excellency taken apart into the value it started the run with and
+//! what changed it since: Ascendancy perks, talents, upgrades and the buffs active right now.
+
+use crate::auras::Aura;
+use crate::combat::AttackKind;
+use crate::durations;
+use crate::effects::Stat;
+use crate::excellency::Excellency;
+use crate::i18n::{tr, trf};
+
+/// A change a single source makes to a single stat.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Modifier {
+    Add(f32),
+    Multiply(f32),
+}
+
+impl Modifier {
+    pub fn apply(&self, value: f32) -> f32 {
+        match *self {
+            Modifier::Add(amount) => value + amount,
+            Modifier::Multiply(factor) => value * factor,
+        }
+    }
+
+    /// The value before `apply`, a multiplier of zero can't be undone and keeps it.
+    pub fn undo(&self, value: f32) -> f32 {
+        match *self {
+            Modifier::Add(amount) => value - amount,
+            Modifier::Multiply(factor) if factor != 0. => value / factor,
+            Modifier::Multiply(_) => value,
+        }
+    }
+
+    /// The same source applied `times` over.
+    fn stacked(&self, times: usize) -> Modifier {
+        match *self {
+            Modifier::Add(amount) => Modifier::Add(amount * times as f32),
+            Modifier::Multiply(factor) => Modifier::Multiply(factor.powi(times as i32)),
+        }
+    }
+
+    fn describe(&self, stat: CharacterStat) -> String {
+        match *self {
+            Modifier::Add(amount) if stat.is_fraction() => format!("{:+.0}%", amount * 100.),
+            Modifier::Add(amount) => format!("{amount:+.1}"),
+            Modifier::Multiply(factor) => format!("x{factor:.2}"),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum CharacterStat {
+    Damage(AttackKind),
+    Range(AttackKind),
+    Cooldown(AttackKind),
+    Targets(AttackKind),
+    MaxHp,
+    Regeneration,
+    Block,
+    Dodge,
+    LifeSteal,
+    Thorns,
+    GoldBonus,
+}
+
+impl CharacterStat {
+    /// Offensive ones first, attack by attack.
+    pub fn all() -> Vec<CharacterStat> {
+        let offensive = AttackKind::ALL.into_iter().flat_map(|kind| {
+            [
+                CharacterStat::Damage(kind),
+                CharacterStat::Range(kind),
+                CharacterStat::Cooldown(kind),
+                CharacterStat::Targets(kind),
+            ]
+        });
+        offensive
+            .chain([
+                CharacterStat::MaxHp,
+                CharacterStat::Regeneration,
+                CharacterStat::Block,
+                CharacterStat::Dodge,
+                CharacterStat::LifeSteal,
+                CharacterStat::Thorns,
+                CharacterStat::GoldBonus,
+            ])
+            .collect()
+    }
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            CharacterStat::Damage(_) => "Damage",
+            CharacterStat::Range(_) => "Range",
+            CharacterStat::Cooldown(_) => "Cooldown",
+            CharacterStat::Targets(_) => "Targets",
+            CharacterStat::MaxHp => "Max HP",
+            CharacterStat::Regeneration => "Regeneration",
+            CharacterStat::Block => "Block",
+            CharacterStat::Dodge => "Dodge chance",
+            CharacterStat::LifeSteal => "Life steal",
+            CharacterStat::Thorns => "Thorns",
+            CharacterStat::GoldBonus => "Gold bonus",
+        })
+    }
+
+    /// The attack it belongs to, none for the Excellency's own stats.
+    pub fn attack(&self) -> Option<AttackKind> {
+        match *self {
+            CharacterStat::Damage(kind)
+            | CharacterStat::Range(kind)
+            | CharacterStat::Cooldown(kind)
+            | CharacterStat::Targets(kind) => Some(kind),
+            _ => None,
+        }
+    }
+
+    /// The value kept on `excellency`, before any buffs.
+    pub fn value(&self, excellency: &Excellency) -> f32 {
+        match *self {
+            CharacterStat::Damage(kind) => excellency.attack(kind).damage,
+            CharacterStat::Range(kind) => excellency.attack(kind).range,
+            CharacterStat::Cooldown(kind) => excellency.attack(kind).cooldown_timer.total,
+            CharacterStat::Targets(kind) => excellency.attack(kind).max_targets as f32,
+            CharacterStat::MaxHp => excellency.hp.maximum,
+            CharacterStat::Regeneration => excellency.regeneration,
+            CharacterStat::Block => excellency.block,
+            CharacterStat::Dodge => excellency.dodge_chance,
+            CharacterStat::LifeSteal => excellency.life_steal,
+            CharacterStat::Thorns => excellency.thorns,
+            CharacterStat::GoldBonus => excellency.gold_bonus,
+        }
+    }
+
+    pub fn set(&self, excellency: &mut Excellency, value: f32) {
+        match *self {
+            CharacterStat::Damage(kind) => excellency.attack_mut(kind).damage = value,
+            CharacterStat::Range(kind) => excellency.attack_mut(kind).range = value,
+            CharacterStat::Cooldown(kind) => {
+                excellency.attack_mut(kind).cooldown_timer.total = value
+            }
+            CharacterStat::Targets(kind) => {
+                excellency.attack_mut(kind).max_targets = value.round() as usize
+            }
+            CharacterStat::MaxHp => excellency.hp.maximum = value,
+            CharacterStat::Regeneration => excellency.regeneration = value,
+            CharacterStat::Block => excellency.block = value,
+            CharacterStat::Dodge => excellency.dodge_chance = value,
+            CharacterStat::LifeSteal => excellency.life_steal = value,
+            CharacterStat::Thorns => excellency.thorns = value,
+            CharacterStat::GoldBonus => excellency.gold_bonus = value,
+        }
+    }
+
+    /// The buff stat scaling it on the fly, see `Effects::scale`.
+    fn buffed_by(&self) -> Option<Stat> {
+        match self {
+            CharacterStat::Damage(_) => Some(Stat::Damage),
+            CharacterStat::Cooldown(_) => Some(Stat::AttackSpeed),
+            _ => None,
+        }
+    }
+
+    /// Shown as a percentage.
+    fn is_fraction(&self) -> bool {
+        matches!(
+            self,
+            CharacterStat::Dodge
+                | CharacterStat::LifeSteal
+                | CharacterStat::Thorns
+                | CharacterStat::GoldBonus
+        )
+    }
+
+    fn format(&self, value: f32) -> String {
+        match self {
+            CharacterStat::Cooldown(_) => durations::format(value),
+            CharacterStat::Targets(_) => format!("{value:.0}"),
+            _ if self.is_fraction() => format!("{:.0}%", value * 100.),
+            _ => format!("{value:.1}"),
+        }
+    }
+}
+
+pub struct Contribution {
+    pub source: String,
+    pub modifier: Modifier,
+}
+
+pub struct Breakdown {
+    pub stat: CharacterStat,
+    /// What it was before the first contribution, see `Excellency::base_stats`.
+    pub base: f32,
+    pub contributions: Vec<Contribution>,
+    /// What it is right now, buffs included. `base` with every contribution applied, unless
+    /// a cap cut some of them short.
+    pub total: f32,
+}
+
+pub struct CharacterSheet {
+    pub breakdowns: Vec<Breakdown>,
+    pub auras: Vec<Aura>,
+}
+
+impl CharacterSheet {
+    pub fn compute(excellency: &Excellency) -> Self {
+        let mut sources: Vec<(String, CharacterStat, Modifier)> = vec![];
+        for &(perk, rank) in &excellency.perks {
+            for (stat, modifier) in perk.modifiers(rank) {
+                sources.push((perk.name().to_owned(), stat, modifier));
+            }
+        }
+        for talent in &excellency.talents {
+            let (stat, modifier) = talent.modifier();
+            sources.push((talent.name().to_owned(), stat, modifier));
+        }
+        for &(kind, upgrade) in &excellency.upgrades {
+            let source = trf("Upgrade {}", &[upgrade.name().to_owned()]);
+            sources.push((source, upgrade.stat(kind), upgrade.modifier()));
+        }
+
+        let breakdowns = CharacterStat::all()
+            .into_iter()
+            .map(|stat| {
+                // The same talent or upgrade picked again is folded into one line
+                let mut stacks: Vec<(&str, Modifier, usize)> = vec![];
+                for (source, _, modifier) in sources.iter().filter(|(_, of, _)| *of == stat) {
+                    match stacks.iter_mut().find(|(name, ..)| name == source) {
+                        Some((.., times)) => *times += 1,
+                        None => stacks.push((source, *modifier, 1)),
+                    }
+                }
+                let mut contributions: Vec<_> = stacks
+                    .into_iter()
+                    .map(|(source, modifier, times)| Contribution {
+                        source: match times {
+                            1 => source.to_owned(),
+                            _ => format!("{source} x{times}"),
+                        },
+                        modifier: modifier.stacked(times),
+                    })
+                    .collect();
+                let kept = stat.value(excellency);
+                let base = match excellency.base_stat(stat) {
+                    Some(base) => base,
+                    // Saves from before the bases were kept, wrong only if a cap was hit
+                    None => contributions
+                        .iter()
+                        .rev()
+                        .fold(kept, |value, contribution| {
+                            contribution.modifier.undo(value)
+                        }),
+                };
+
+                let mut total = kept;
+                let buffed = stat.buffed_by().map(|buff| excellency.buffs.total(buff));
+                if let Some(buff) = buffed.filter(|&buff| buff != 0.) {
+                    let scale = (1. + buff).max(0.);
+                    // Attack speed runs the cooldown faster rather than shortening it
+                    let modifier = match stat {
+                        CharacterStat::Cooldown(_) => Modifier::Multiply(1. / scale),
+                        _ => Modifier::Multiply(scale),
+                    };
+                    total = modifier.apply(kept);
+                    contributions.push(Contribution {
+                        source: tr("Buffs").to_owned(),
+                        modifier,
+                    });
+                }
+                Breakdown {
+                    stat,
+                    base,
+                    contributions,
+                    total,
+                }
+            })
+            .collect();
+        Self {
+            breakdowns,
+            auras: excellency.auras.clone(),
+        }
+    }
+
+    pub fn breakdown(&self, stat: CharacterStat) -> Option<&Breakdown> {
+        self.breakdowns
+            .iter()
+            .find(|breakdown| breakdown.stat == stat)
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        let mut section = None;
+        for breakdown in &self.breakdowns {
+            let stat = breakdown.stat;
+            let heading = stat.attack();
+            if section != Some(heading) {
+                section = Some(heading);
+                ui.separator();
+                ui.strong(match heading {
+                    Some(kind) => kind.name(),
+                    None => tr("Defense"),
+                });
+            }
+            ui.horizontal(|ui| {
+                ui.label(stat.name());
+                ui.strong(stat.format(breakdown.total));
+                if breakdown.contributions.is_empty() {
+                    return;
+                }
+                let mut parts = vec![trf("{} base", &[stat.format(breakdown.base)])];
+                parts.extend(breakdown.contributions.iter().map(|contribution| {
+                    format!(
+                        "{} ({})",
+                        contribution.modifier.describe(stat),
+                        contribution.source
+                    )
+                }));
+                ui.weak(parts.join(", "));
+            });
+        }
+        ui.separator();
+        ui.strong(tr("Auras"));
+        if self.auras.is_empty() {
+            ui.label(tr("None unlocked yet."));
+        }
+        for aura in &self.auras {
+            ui.label(format!("{}: {}", aura.name(), aura.description()));
+        }
+    }
+}
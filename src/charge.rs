@@ -0,0 +1,40 @@
+//! A resource filled by what happens in the fight rather than by time, unlike `Timer`.
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Charge {
+    /// What it takes to be full.
+    pub required: f32,
+    /// In `0..=required`.
+    pub current: f32,
+}
+
+impl Charge {
+    /// An empty charge.
+    pub fn new(required: f32) -> Self {
+        Self {
+            required,
+            current: 0.,
+        }
+    }
+
+    /// Fills it by `amount`, anything past full is lost.
+    pub fn add(&mut self, amount: f32) {
+        self.current = (self.current + amount.max(0.)).min(self.required);
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.current >= self.required
+    }
+
+    /// From 0 when empty to 1 when full.
+    pub fn fraction(&self) -> f32 {
+        match self.required > 0. {
+            true => self.current / self.required,
+            false => 1.,
+        }
+    }
+
+    pub fn empty(&mut self) {
+        self.current = 0.;
+    }
+}
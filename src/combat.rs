@@ -0,0 +1,531 @@
+//! Attacks of the Excellency and the health they chip away at.
+
+use egui::Color32;
+
+use crate::durations;
+use crate::effects::{Effects, Stat};
+use crate::enemy::{count_in_range, remove_dead, Distance, Enemy, EnemyKind};
+use crate::i18n::tr;
+use crate::records::AttackSnapshot;
+use crate::timer::Timer;
+use crate::widgets::{stat_slider, stat_tooltip};
+use crate::world::Entity;
+
+/// An attack firing on its own cooldown at the enemies within its range.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct BasicAttack {
+    pub cooldown_timer: Timer,
+    /// Dealt to each target before armor and shields.
+    pub damage: f32,
+    pub range: f32,
+    pub max_targets: usize,
+    pub targeting: TargetingStrategy,
+    /// Damage left over from a kill goes on to the next target in range, even past
+    /// `max_targets`.
+    #[serde(default)]
+    pub overkill_carry: bool,
+    /// Seconds it charges after the cooldown before going off, 0 fires right away.
+    #[serde(default)]
+    pub wind_up: f32,
+    #[serde(default)]
+    pub phase: AttackPhase,
+    #[serde(default)]
+    pub cast_mode: CastMode,
+    #[serde(default)]
+    pub damage_type: DamageType,
+}
+
+/// Whether a hit can be mitigated.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum DamageType {
+    #[default]
+    Normal,
+    /// Goes straight through armor, shields and block.
+    True,
+}
+
+/// Whether an attack goes off on its own once charged.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum CastMode {
+    #[default]
+    Auto,
+    /// Waits charged until the player casts it.
+    Hold,
+}
+
+/// Which of the Excellency's attacks.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum AttackKind {
+    Basic,
+    Big,
+}
+
+impl AttackKind {
+    pub const ALL: [AttackKind; 2] = [AttackKind::Basic, AttackKind::Big];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            AttackKind::Basic => "Basic Attack",
+            AttackKind::Big => "Big Attack",
+        })
+    }
+}
+
+/// Where an attack is in its cycle. The cooldown timer only runs while cooling down.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum AttackPhase {
+    #[default]
+    Cooldown,
+    /// Held back until cast, see `CastMode::Hold`.
+    Ready,
+    /// Charging, it goes off once `remaining` runs out unless the Excellency gets hit.
+    WindingUp { remaining: f32 },
+}
+
+impl BasicAttack {
+    /// The stats kept in the run history.
+    pub(crate) fn snapshot(&self) -> AttackSnapshot {
+        AttackSnapshot {
+            damage: self.damage,
+            range: self.range,
+            max_targets: self.max_targets,
+            cooldown: self.cooldown_timer.total,
+        }
+    }
+
+    /// Damage per second with every target slot filled.
+    pub fn effective_dps(&self) -> f32 {
+        self.damage * self.max_targets as f32 / self.cycle()
+    }
+
+    /// Seconds from one shot to the next.
+    pub fn cycle(&self) -> f32 {
+        self.cooldown_timer.total + self.wind_up.max(0.)
+    }
+
+    /// Advances the cooldown or the wind-up, returns how many times it should fire.
+    pub fn tick(&mut self, delta: f32) -> u32 {
+        match self.phase {
+            AttackPhase::Cooldown => {
+                let completions = self.cooldown_timer.tick(delta);
+                if completions == 0 {
+                    return 0;
+                }
+                match self.cast_mode {
+                    CastMode::Auto if self.wind_up <= 0. => return completions,
+                    CastMode::Auto => self.start_wind_up(),
+                    CastMode::Hold => self.phase = AttackPhase::Ready,
+                }
+                0
+            }
+            AttackPhase::Ready => 0,
+            AttackPhase::WindingUp { remaining } => {
+                let remaining = remaining - delta;
+                if remaining > 0. {
+                    self.phase = AttackPhase::WindingUp { remaining };
+                    return 0;
+                }
+                self.phase = AttackPhase::Cooldown;
+                1
+            }
+        }
+    }
+
+    /// Goes off after the wind-up, on the next tick without one.
+    fn start_wind_up(&mut self) {
+        self.phase = AttackPhase::WindingUp {
+            remaining: self.wind_up.max(0.),
+        };
+    }
+
+    /// What the cast button and hotkey do: a held attack that's ready starts going off, an
+    /// auto-cast one skips the rest of its cooldown.
+    pub fn cast(&mut self) {
+        match (self.cast_mode, self.phase) {
+            (CastMode::Hold, AttackPhase::Ready) => self.start_wind_up(),
+            (CastMode::Hold, _) => {}
+            (CastMode::Auto, _) => self.cooldown_timer.finish_now(),
+        }
+    }
+
+    pub fn can_cast(&self) -> bool {
+        self.cast_mode == CastMode::Auto || self.phase == AttackPhase::Ready
+    }
+
+    /// Cancels a wind-up in progress and starts the cooldown over, returns whether there
+    /// was one.
+    pub fn interrupt(&mut self) -> bool {
+        if self.wind_up <= 0. || !matches!(self.phase, AttackPhase::WindingUp { .. }) {
+            return false;
+        }
+        self.phase = AttackPhase::Cooldown;
+        self.cooldown_timer.reset();
+        true
+    }
+
+    /// Without `free_sliders` the stats are only shown, the controls for casting stay usable.
+    pub fn show_editor(
+        &mut self,
+        ui: &mut egui::Ui,
+        id_source: &str,
+        cooldown_color: Color32,
+        free_sliders: bool,
+    ) {
+        ui.horizontal(|ui| {
+            let timer = &mut self.cooldown_timer;
+            let mut enabled = !timer.is_paused();
+            if ui.checkbox(&mut enabled, tr("Enabled")).changed() {
+                match enabled {
+                    true => timer.unpause(),
+                    false => timer.pause(),
+                }
+            }
+            if ui.button(tr("Reset cooldown")).clicked() {
+                timer.reset()
+            }
+            let mut hold = self.cast_mode == CastMode::Hold;
+            if ui
+                .checkbox(&mut hold, tr("Hold"))
+                .on_hover_text(tr(
+                    "Once charged, wait for a cast instead of firing on its own.",
+                ))
+                .changed()
+            {
+                self.cast_mode = match hold {
+                    true => CastMode::Hold,
+                    false => CastMode::Auto,
+                };
+                // A held charge shouldn't be lost by switching back
+                if !hold && self.phase == AttackPhase::Ready {
+                    self.start_wind_up();
+                }
+            }
+        });
+        if self.phase == AttackPhase::Ready {
+            ui.colored_label(Color32::GOLD, tr("Ready, waiting to be cast."));
+        }
+        let dps = [(tr("Effective DPS"), format!("{:.1}", self.effective_dps()))];
+        let cycle = self.cycle();
+        ui.horizontal(|ui| {
+            ui.label(tr("Cooldown:"));
+            let timer = &self.cooldown_timer;
+            let bar = ui.add(
+                egui::ProgressBar::new(timer.remaining_fraction())
+                    .text(format!(
+                        "{} / {}",
+                        durations::format(timer.remaining),
+                        durations::format(timer.total)
+                    ))
+                    .fill(cooldown_color),
+            );
+            stat_tooltip(
+                bar,
+                tr("Time until the attack fires again."),
+                &[
+                    (tr("Attacks per second"), format!("{:.2}", 1. / cycle)),
+                    dps[0].clone(),
+                ],
+            );
+        });
+        if let AttackPhase::WindingUp { remaining } = self.phase {
+            ui.horizontal(|ui| {
+                ui.label(tr("Charging:"));
+                let charged = 1. - remaining / self.wind_up.max(f32::EPSILON);
+                ui.add(
+                    egui::ProgressBar::new(charged)
+                        .text(durations::format(remaining))
+                        .fill(Color32::GOLD),
+                );
+            });
+        }
+        ui.add_enabled_ui(free_sliders, |ui| self.show_stat_sliders(ui, &dps));
+        ui.horizontal(|ui| {
+            ui.label(tr("Targeting:"));
+            egui::ComboBox::from_id_source(id_source)
+                .selected_text(self.targeting.name())
+                .show_ui(ui, |ui| {
+                    for strategy in TargetingStrategy::ALL {
+                        ui.selectable_value(&mut self.targeting, strategy, strategy.name());
+                    }
+                });
+        });
+    }
+
+    fn show_stat_sliders(&mut self, ui: &mut egui::Ui, dps: &[(&str, String)]) {
+        stat_slider(
+            ui,
+            tr("Wind-up:"),
+            &mut self.wind_up,
+            0. ..=3.,
+            tr("Seconds the attack charges after its cooldown before going off. Getting hit while charging starts the cooldown over."),
+            dps,
+        );
+        stat_slider(
+            ui,
+            tr("Damage:"),
+            &mut self.damage,
+            1. ..=100.,
+            tr("Damage dealt to each target, reduced by its armor and absorbed by its shield."),
+            dps,
+        );
+        let lane_share = self.range / Distance::start().0 * 100.;
+        stat_slider(
+            ui,
+            tr("Range:"),
+            &mut self.range,
+            1. ..=50.,
+            tr("Only enemies at most this far away can be targeted."),
+            &[(tr("Share of the lane"), format!("{lane_share:.0}%"))],
+        );
+        stat_slider(
+            ui,
+            tr("Max Targets:"),
+            &mut self.max_targets,
+            1..=10,
+            tr("How many enemies in range each attack hits."),
+            dps,
+        );
+        let carry = ui.checkbox(&mut self.overkill_carry, tr("Carry overkill"));
+        stat_tooltip(
+            carry,
+            tr("Damage left over from a kill hits the next enemy in range, even past the max targets."),
+            &[],
+        );
+        let mut true_damage = self.damage_type == DamageType::True;
+        let checkbox = ui.checkbox(&mut true_damage, tr("True damage"));
+        if checkbox.changed() {
+            self.damage_type = match true_damage {
+                true => DamageType::True,
+                false => DamageType::Normal,
+            };
+        }
+        stat_tooltip(
+            checkbox,
+            tr("Hits go straight through armor and shields."),
+            &[],
+        );
+    }
+
+    /// Hits up to `max_targets` enemies in range, picked by the targeting strategy,
+    /// and removes the ones that died. `buffs` are the Excellency's, `enemies` must be sorted
+    /// by distance, `focused` is the player's pick for `TargetingStrategy::Focused`.
+    ///
+    /// With `overkill_carry`, what a kill didn't need is added to the next hit, and keeps
+    /// going down the candidates after the last regular target while anything is left.
+    pub fn fire(
+        &self,
+        enemies: &mut Vec<Enemy>,
+        buffs: &Effects,
+        focused: Option<Entity>,
+    ) -> AttackOutcome {
+        let damage = buffs.scale(Stat::Damage, self.damage);
+        let mut candidates: Vec<usize> = (0..count_in_range(enemies, self.range)).collect();
+        self.targeting.prioritize(&mut candidates, enemies, focused);
+        let mut damage_dealt = 0.;
+        let mut hits = vec![];
+        let mut carried = 0.;
+        for (slot, &index) in candidates.iter().enumerate() {
+            let hit = match slot < self.max_targets {
+                true => damage + carried,
+                false => carried,
+            };
+            if hit <= 0. {
+                break;
+            }
+            let enemy = &mut enemies[index];
+            let after_armor = match self.damage_type {
+                DamageType::Normal => (hit - enemy.armor).max(0.),
+                DamageType::True => hit,
+            };
+            let landed = enemy.take_hit(DamageSpec::Flat(hit), self.damage_type);
+            damage_dealt += landed;
+            if landed > 0. {
+                hits.push(enemy.distance.0);
+            }
+            carried = match self.overkill_carry && enemy.hp.is_dead() {
+                true => after_armor - landed,
+                false => 0.,
+            };
+        }
+
+        let killed = remove_dead(enemies);
+        AttackOutcome {
+            killed,
+            damage_dealt,
+            true_damage: match self.damage_type {
+                DamageType::Normal => 0.,
+                DamageType::True => damage_dealt,
+            },
+            hits,
+        }
+    }
+}
+
+/// What a single attack did.
+pub struct AttackOutcome {
+    /// Removed from the field already, bounties still to be collected.
+    pub killed: Vec<Enemy>,
+    /// Damage that landed, not counting overkill.
+    pub damage_dealt: f32,
+    /// The part of `damage_dealt` that was `DamageType::True`.
+    pub true_damage: f32,
+    /// Distances of the enemies that took damage, for the effects drawn on them.
+    pub hits: Vec<f32>,
+}
+
+/// Which enemies in range an attack hits first.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum TargetingStrategy {
+    Closest,
+    LowestHp,
+    HealersFirst,
+    /// The enemy the player focused while it's in range, otherwise the closest.
+    Focused,
+}
+
+impl TargetingStrategy {
+    pub const ALL: [TargetingStrategy; 4] = [
+        TargetingStrategy::Closest,
+        TargetingStrategy::LowestHp,
+        TargetingStrategy::HealersFirst,
+        TargetingStrategy::Focused,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            TargetingStrategy::Closest => "Closest",
+            TargetingStrategy::LowestHp => "Lowest HP",
+            TargetingStrategy::HealersFirst => "Healers first",
+            TargetingStrategy::Focused => "Focused",
+        })
+    }
+
+    /// Reorders candidate indices so the preferred targets come first.
+    /// Enemies are kept sorted by distance, so ties fall back to the closest one.
+    pub fn prioritize(&self, candidates: &mut [usize], enemies: &[Enemy], focused: Option<Entity>) {
+        match self {
+            TargetingStrategy::Closest => {}
+            TargetingStrategy::LowestHp => candidates.sort_by(|&a, &b| {
+                enemies[a]
+                    .hp
+                    .current
+                    .partial_cmp(&enemies[b].hp.current)
+                    .expect("Compared two f32's")
+            }),
+            TargetingStrategy::HealersFirst => {
+                candidates.sort_by_key(|&index| enemies[index].kind != EnemyKind::Healer)
+            }
+            TargetingStrategy::Focused => {
+                candidates.sort_by_key(|&index| Some(enemies[index].id) != focused)
+            }
+        }
+    }
+}
+
+/// How much a hit deals, worked out against the HP of whoever it lands on. Armor and
+/// shields apply to the result like to any other hit.
+///
+/// Only the ultimate and scripted abilities deal percentages. The attacks, thorns and
+/// incidents stay `Flat`: their damage is a single number the sliders, upgrades and talents
+/// scale, and overkill carries over as flat damage.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum DamageSpec {
+    Flat(f32),
+    /// Fraction of the target's current HP, hits hardest while it's still healthy.
+    CurrentHp(f32),
+    /// Fraction of the target's maximum HP, the same share of a boss as of anything else.
+    MaximumHp(f32),
+}
+
+impl DamageSpec {
+    /// The damage against `hp`, before armor and shields.
+    pub fn against(&self, hp: &HitPoints) -> f32 {
+        let damage = match *self {
+            DamageSpec::Flat(damage) => damage,
+            DamageSpec::CurrentHp(fraction) => hp.current * fraction,
+            DamageSpec::MaximumHp(fraction) => hp.maximum * fraction,
+        };
+        damage.max(0.)
+    }
+}
+
+impl From<f32> for DamageSpec {
+    fn from(damage: f32) -> Self {
+        DamageSpec::Flat(damage)
+    }
+}
+
+/// Healing past the maximum HP, draining back down to it over time.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct Overheal {
+    /// How far above the maximum healing can go, as a fraction of the maximum.
+    pub cap: f32,
+    /// Fraction of the maximum the excess loses every second.
+    pub decay: f32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq, Debug)]
+pub struct HitPoints {
+    pub maximum: f32,
+    /// Never below zero, and above `maximum` only with `overheal`.
+    pub current: f32,
+    /// Healing stops at `maximum` without it. Left out of saves when off, keeping their layout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overheal: Option<Overheal>,
+}
+
+impl HitPoints {
+    pub fn new_full(maximum: f32) -> Self {
+        Self {
+            maximum,
+            current: maximum,
+            overheal: None,
+        }
+    }
+
+    pub fn with_overheal(mut self, overheal: Overheal) -> Self {
+        self.overheal = Some(overheal);
+        self
+    }
+
+    /// The most healing can bring it to.
+    pub fn limit(&self) -> f32 {
+        let cap = self.overheal.map_or(0., |overheal| overheal.cap.max(0.));
+        self.maximum * (1. + cap)
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.current <= 0.
+    }
+
+    pub fn as_progress_bar(&self, color: Color32) -> egui::ProgressBar {
+        egui::ProgressBar::new(self.current / self.maximum)
+            .text(format!("{}/{}", self.current, self.maximum))
+            .fill(color)
+    }
+
+    pub fn take_damage(&mut self, damage: f32) {
+        self.current = (self.current - damage.max(0.)).max(0.);
+    }
+
+    /// Up to `limit`, never lowering HP that's above it already.
+    pub fn heal(&mut self, amount: f32) {
+        let healed = (self.current + amount.max(0.)).min(self.limit());
+        self.current = self.current.max(healed);
+    }
+
+    /// Drains the overheal for `delta` seconds, never below the maximum.
+    pub fn tick(&mut self, delta: f32) {
+        if self.current <= self.maximum {
+            return;
+        }
+        let decay = self
+            .overheal
+            .map_or(f32::INFINITY, |overheal| overheal.decay);
+        self.current = (self.current - self.maximum * decay * delta).max(self.maximum);
+    }
+
+    pub fn reset(&mut self) {
+        self.current = self.maximum;
+    }
+}
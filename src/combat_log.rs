@@ -0,0 +1,155 @@
+//! What happened to the Excellency recently, as readable lines and as text floating over
+//! the battlefield. Both are filled from the events the UI drains every frame.
+
+use egui::{Color32, Pos2};
+
+use crate::combat::DamageType;
+use crate::durations;
+use crate::events::GameEvent;
+use crate::i18n::{tr, trf};
+
+/// Lines shown in the log, the export gets all of them.
+const SHOWN: usize = 100;
+/// Seconds a floating text stays up.
+const FLOAT_SECONDS: f64 = 1.2;
+/// Points a floating text rises over its lifetime.
+const FLOAT_RISE: f32 = 30.;
+
+struct Line {
+    /// Run time it happened at.
+    elapsed: f32,
+    text: String,
+}
+
+#[derive(Default)]
+pub struct CombatLog(Vec<Line>);
+
+impl CombatLog {
+    /// Adds a line for `event` if it's worth one.
+    pub fn record(&mut self, elapsed: f32, event: &GameEvent) {
+        let text = match event {
+            GameEvent::PlayerHit {
+                damage,
+                damage_type: DamageType::True,
+                ..
+            } => trf("Took {} true damage", &[format!("{damage:.1}")]),
+            GameEvent::PlayerHit {
+                damage, blocked, ..
+            } if *blocked > 0. => trf(
+                "Took {} damage, blocked {}",
+                &[format!("{damage:.1}"), format!("{blocked:.1}")],
+            ),
+            GameEvent::PlayerHit { damage, .. } => trf("Took {} damage", &[format!("{damage:.1}")]),
+            GameEvent::Dodged => tr("Dodged a hit").to_owned(),
+            GameEvent::IncidentStruck(incident) => incident.announcement().to_owned(),
+            GameEvent::EnemyEscaped(enemy) => {
+                trf("{} got away", &[enemy.display_name().to_owned()])
+            }
+            GameEvent::RelicFound(relic) => trf("Found a relic: {}", &[relic.describe()]),
+            GameEvent::UltimateFired { hits } => trf(
+                "Unleashed the ultimate on {} enemies",
+                &[hits.len().to_string()],
+            ),
+            GameEvent::AttackInterrupted { kind } => {
+                trf("{} interrupted", &[kind.name().to_owned()])
+            }
+            GameEvent::EnemyKilled(enemy) if enemy.is_boss => {
+                trf("Slew the boss {}", &[enemy.display_name().to_owned()])
+            }
+            GameEvent::EnemyKilled(enemy) if enemy.is_elite() => {
+                trf("Slew {}", &[enemy.display_name().to_owned()])
+            }
+            GameEvent::WaveStarted { wave } => trf("Wave {} started", &[wave.to_string()]),
+            GameEvent::WaveCalledEarly { wave, gold } => trf(
+                "Called wave {} early for {} gold",
+                &[wave.to_string(), gold.to_string()],
+            ),
+            GameEvent::BossPhaseEntered { boss, action, .. } => action.describe(boss),
+            GameEvent::AttackFired { .. }
+            | GameEvent::DamageDealt { .. }
+            | GameEvent::DamageTicked { .. }
+            | GameEvent::EnemySpawned(_)
+            | GameEvent::EnemyKilled(_) => return,
+        };
+        self.0.push(Line { elapsed, text });
+    }
+
+    /// Oldest first, with the run time each happened at.
+    pub fn lines(&self) -> impl Iterator<Item = (f32, &str)> {
+        self.0.iter().map(|line| (line.elapsed, line.text.as_str()))
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        if self.0.is_empty() {
+            ui.weak(tr("Nothing happened yet."));
+            return;
+        }
+        egui::ScrollArea::vertical()
+            .max_height(120.)
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for line in &self.0[self.0.len().saturating_sub(SHOWN)..] {
+                    let elapsed = durations::format(line.elapsed);
+                    ui.label(format!("{elapsed:>6}  {}", line.text));
+                }
+            });
+    }
+}
+
+struct FloatingText {
+    text: String,
+    color: Color32,
+    /// Distance from the Excellency it floats up from.
+    distance: f32,
+    /// `None` until first drawn, like toasts.
+    expires_at: Option<f64>,
+}
+
+/// Short texts rising and fading over the battlefield.
+#[derive(Default)]
+pub struct FloatingTexts(Vec<FloatingText>);
+
+impl FloatingTexts {
+    /// Adds a text for `event` if it has one.
+    pub fn record(&mut self, event: &GameEvent) {
+        let (text, color, distance) = match event {
+            GameEvent::Dodged => (tr("Dodged!").to_owned(), Color32::LIGHT_BLUE, 0.),
+            GameEvent::PlayerHit { blocked, .. } if *blocked > 0. => (
+                trf("Blocked {}", &[format!("{blocked:.0}")]),
+                Color32::LIGHT_GRAY,
+                0.,
+            ),
+            GameEvent::DamageTicked { amount, distance } => (
+                format!("{amount:.1}"),
+                Color32::from_rgb(255, 120, 40),
+                *distance,
+            ),
+            _ => return,
+        };
+        self.0.push(FloatingText {
+            text,
+            color,
+            distance,
+            expires_at: None,
+        });
+    }
+
+    /// Draws the live texts, `position` maps a distance onto the battlefield.
+    pub fn paint(&mut self, painter: &egui::Painter, now: f64, position: impl Fn(f32) -> Pos2) {
+        for text in &mut self.0 {
+            text.expires_at.get_or_insert(now + FLOAT_SECONDS);
+        }
+        self.0.retain(|text| text.expires_at > Some(now));
+        for text in &self.0 {
+            let left = (text.expires_at.unwrap_or(now) - now) / FLOAT_SECONDS;
+            let rise = (1. - left as f32) * FLOAT_RISE;
+            painter.text(
+                position(text.distance) - egui::vec2(0., 20. + rise),
+                egui::Align2::CENTER_BOTTOM,
+                &text.text,
+                egui::FontId::proportional(14.),
+                text.color.gamma_multiply(left as f32),
+            );
+        }
+    }
+}
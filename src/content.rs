@@ -0,0 +1,391 @@
+//! Enemy archetypes, wave compositions, boss schedules and campaign stages, read from RON files in
+//! `CONTENT_DIR` at startup so content can change without recompiling.
+//!
+//! A copy of every file is embedded as a fallback: a missing file quietly uses it, a broken
+//! one uses it too but reports why, so a typo never leaves the game without enemies.
+
+use std::collections::BTreeMap;
+
+use crate::enemy::{EliteModifier, EnemyKind};
+use crate::rng::GameRng;
+use crate::victory::Victory;
+
+pub const CONTENT_DIR: &str = "assets/content";
+
+pub const ENEMIES_FILE: &str = "enemies.ron";
+pub const WAVES_FILE: &str = "waves.ron";
+pub const BOSSES_FILE: &str = "bosses.ron";
+pub const CAMPAIGN_FILE: &str = "campaign.ron";
+
+const EMBEDDED_ENEMIES: &str = include_str!("../assets/content/enemies.ron");
+const EMBEDDED_WAVES: &str = include_str!("../assets/content/waves.ron");
+const EMBEDDED_BOSSES: &str = include_str!("../assets/content/bosses.ron");
+const EMBEDDED_CAMPAIGN: &str = include_str!("../assets/content/campaign.ron");
+
+/// A kind of enemy, its stats relative to what the spawner rolls.
+#[derive(serde::Deserialize, Clone)]
+#[serde(default)]
+pub struct Archetype {
+    pub hp: f32,
+    pub speed: f32,
+    pub damage: f32,
+    /// Added on top of any elite armor.
+    pub armor: f32,
+    pub kind: EnemyKind,
+    /// Always present, on top of any rolled as an elite.
+    pub modifiers: Vec<EliteModifier>,
+}
+
+impl Default for Archetype {
+    fn default() -> Self {
+        Self {
+            hp: 1.,
+            speed: 1.,
+            damage: 1.,
+            armor: 0.,
+            kind: EnemyKind::Grunt,
+            modifiers: vec![],
+        }
+    }
+}
+
+/// The mix of archetypes spawning from `from_wave` until the next composition.
+#[derive(serde::Deserialize)]
+pub struct Composition {
+    pub from_wave: u32,
+    /// Relative chance of each archetype.
+    pub weights: BTreeMap<String, f32>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct BossSpawn {
+    pub wave: u32,
+    pub archetype: String,
+}
+
+/// One wave of a campaign stage.
+#[derive(serde::Deserialize)]
+pub struct StageWave {
+    /// Relative chance of each archetype.
+    pub weights: BTreeMap<String, f32>,
+    /// Archetypes joining as bosses when the wave starts.
+    #[serde(default)]
+    pub bosses: Vec<String>,
+}
+
+/// An authored campaign stage with its own waves and a way to win it.
+#[derive(serde::Deserialize)]
+pub struct Stage {
+    pub name: String,
+    pub description: String,
+    pub waves: Vec<StageWave>,
+    pub victory: Victory,
+    /// Fraction of the Excellency's HP left that a victory needs for two stars.
+    #[serde(default = "default_two_stars")]
+    pub two_stars: f32,
+    #[serde(default = "default_three_stars")]
+    pub three_stars: f32,
+}
+
+fn default_two_stars() -> f32 {
+    0.5
+}
+
+fn default_three_stars() -> f32 {
+    0.9
+}
+
+impl Stage {
+    /// Stars a victory with `hp_left` of the Excellency's HP is worth, 1 to 3.
+    pub fn stars(&self, hp_left: f32) -> u32 {
+        1 + u32::from(hp_left >= self.two_stars) + u32::from(hp_left >= self.three_stars)
+    }
+}
+
+pub struct Content {
+    pub archetypes: BTreeMap<String, Archetype>,
+    /// Sorted by `from_wave`, the first one starts at wave 1.
+    pub compositions: Vec<Composition>,
+    pub bosses: Vec<BossSpawn>,
+    /// In the order they unlock.
+    pub stages: Vec<Stage>,
+}
+
+impl Default for Content {
+    /// Only the embedded files.
+    fn default() -> Self {
+        Self {
+            archetypes: ron::from_str(EMBEDDED_ENEMIES).expect("Embedded enemies are valid"),
+            compositions: ron::from_str(EMBEDDED_WAVES).expect("Embedded waves are valid"),
+            bosses: ron::from_str(EMBEDDED_BOSSES).expect("Embedded bosses are valid"),
+            stages: ron::from_str(EMBEDDED_CAMPAIGN).expect("Embedded campaign is valid"),
+        }
+    }
+}
+
+impl Content {
+    /// Reads `CONTENT_DIR`, returning what went wrong with the files that couldn't be used.
+    pub fn load() -> (Self, Vec<String>) {
+        Self::load_with(read)
+    }
+
+    /// Like `load`, but reads every file with `read`, which gives `None` for a missing one.
+    pub fn load_with(read: impl Fn(&str) -> Option<Result<String, String>>) -> (Self, Vec<String>) {
+        let mut errors = vec![];
+        let archetypes = load_file(
+            &read,
+            ENEMIES_FILE,
+            EMBEDDED_ENEMIES,
+            &mut errors,
+            validate_archetypes,
+        )
+        .expect("Embedded enemies are valid");
+        let compositions = load_file(
+            &read,
+            WAVES_FILE,
+            EMBEDDED_WAVES,
+            &mut errors,
+            |compositions| validate_compositions(compositions, &archetypes),
+        );
+        let bosses = load_file(&read, BOSSES_FILE, EMBEDDED_BOSSES, &mut errors, |bosses| {
+            validate_bosses(bosses, &archetypes)
+        });
+        let stages = load_file(
+            &read,
+            CAMPAIGN_FILE,
+            EMBEDDED_CAMPAIGN,
+            &mut errors,
+            |stages| validate_stages(stages, &archetypes),
+        );
+        match (compositions, bosses, stages) {
+            (Some(compositions), Some(bosses), Some(stages)) => (
+                Self {
+                    archetypes,
+                    compositions,
+                    bosses,
+                    stages,
+                },
+                errors,
+            ),
+            _ => {
+                errors.push(format!(
+                    "the built-in waves need archetypes missing from {ENEMIES_FILE}, \
+                     using only built-in content"
+                ));
+                (Self::default(), errors)
+            }
+        }
+    }
+
+    /// Rolls which archetype the next regular spawn of `wave` is.
+    pub fn pick(&self, wave: u32, rng: &mut GameRng) -> (&str, &Archetype) {
+        let composition = self
+            .compositions
+            .iter()
+            .rev()
+            .find(|composition| composition.from_wave <= wave)
+            .unwrap_or(&self.compositions[0]);
+        let mut weights = composition.weights.iter();
+        // Only roll when there's a choice, so single-archetype waves don't shift the seed
+        let name = if composition.weights.len() == 1 {
+            weights.next().expect("Compositions aren't empty").0
+        } else {
+            let total: f32 = composition.weights.values().sum();
+            let mut roll = rng.next_f32() * total;
+            let mut picked = None;
+            for (name, weight) in weights {
+                picked = Some(name);
+                roll -= weight;
+                if roll < 0. {
+                    break;
+                }
+            }
+            picked.expect("Compositions aren't empty")
+        };
+        (name, &self.archetypes[name])
+    }
+
+    pub fn stage(&self, name: &str) -> Option<&Stage> {
+        self.stages.iter().find(|stage| stage.name == name)
+    }
+
+    /// The content a run of `stage` spawns from: the same archetypes, the stage's waves.
+    pub fn for_stage(&self, stage: &Stage) -> Content {
+        let waves = (1..).zip(&stage.waves);
+        Content {
+            archetypes: self.archetypes.clone(),
+            compositions: waves
+                .clone()
+                .map(|(from_wave, wave)| Composition {
+                    from_wave,
+                    weights: wave.weights.clone(),
+                })
+                .collect(),
+            bosses: waves
+                .flat_map(|(wave, stage_wave)| {
+                    stage_wave.bosses.iter().map(move |archetype| BossSpawn {
+                        wave,
+                        archetype: archetype.clone(),
+                    })
+                })
+                .collect(),
+            stages: vec![],
+        }
+    }
+
+    /// Archetypes of the bosses joining at the start of `wave`.
+    pub fn bosses(&self, wave: u32) -> impl Iterator<Item = (&str, &Archetype)> {
+        self.bosses
+            .iter()
+            .filter(move |boss| boss.wave == wave)
+            .map(|boss| (boss.archetype.as_str(), &self.archetypes[&boss.archetype]))
+    }
+}
+
+/// `None` if the file doesn't exist, the embedded copy is used then.
+#[cfg(not(target_arch = "wasm32"))]
+fn read(file: &str) -> Option<Result<String, String>> {
+    let path = std::path::Path::new(CONTENT_DIR).join(file);
+    match std::fs::read_to_string(path) {
+        Ok(text) => Some(Ok(text)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+        Err(err) => Some(Err(err.to_string())),
+    }
+}
+
+/// The browser has no content directory, only the embedded copies.
+#[cfg(target_arch = "wasm32")]
+fn read(_file: &str) -> Option<Result<String, String>> {
+    None
+}
+
+/// Reads `file` with `read`, falling back to the `embedded` copy when it's missing or
+/// invalid. `None` if even the embedded copy doesn't pass `validate`.
+fn load_file<T: serde::de::DeserializeOwned>(
+    read: impl Fn(&str) -> Option<Result<String, String>>,
+    file: &str,
+    embedded: &str,
+    errors: &mut Vec<String>,
+    validate: impl Fn(T) -> Result<T, String>,
+) -> Option<T> {
+    if let Some(text) = read(file) {
+        let loaded = text
+            .and_then(|text| ron::from_str(&text).map_err(|err| err.to_string()))
+            .and_then(&validate);
+        match loaded {
+            Ok(value) => return Some(value),
+            Err(err) => errors.push(format!("{file}: {err}")),
+        }
+    }
+    ron::from_str(embedded)
+        .ok()
+        .and_then(|value| validate(value).ok())
+}
+
+fn validate_archetypes(
+    archetypes: BTreeMap<String, Archetype>,
+) -> Result<BTreeMap<String, Archetype>, String> {
+    if archetypes.is_empty() {
+        return Err("no archetypes".to_owned());
+    }
+    for (name, archetype) in &archetypes {
+        let multipliers = [archetype.hp, archetype.speed, archetype.damage];
+        if multipliers
+            .iter()
+            .any(|multiplier| !multiplier.is_finite() || *multiplier <= 0.)
+        {
+            return Err(format!("\"{name}\" needs positive hp, speed and damage"));
+        }
+        if !archetype.armor.is_finite() || archetype.armor < 0. {
+            return Err(format!("\"{name}\" has negative armor"));
+        }
+    }
+    Ok(archetypes)
+}
+
+fn validate_compositions(
+    mut compositions: Vec<Composition>,
+    archetypes: &BTreeMap<String, Archetype>,
+) -> Result<Vec<Composition>, String> {
+    compositions.sort_by_key(|composition| composition.from_wave);
+    if compositions
+        .first()
+        .map(|composition| composition.from_wave)
+        != Some(1)
+    {
+        return Err("the first composition has to start at wave 1".to_owned());
+    }
+    for composition in &compositions {
+        let wave = composition.from_wave;
+        if composition.weights.is_empty() {
+            return Err(format!("wave {wave} has no archetypes"));
+        }
+        for (name, weight) in &composition.weights {
+            if !archetypes.contains_key(name) {
+                return Err(format!("wave {wave} uses the unknown archetype \"{name}\""));
+            }
+            if !weight.is_finite() || *weight <= 0. {
+                return Err(format!(
+                    "wave {wave} gives \"{name}\" a non-positive weight"
+                ));
+            }
+        }
+    }
+    Ok(compositions)
+}
+
+fn validate_bosses(
+    bosses: Vec<BossSpawn>,
+    archetypes: &BTreeMap<String, Archetype>,
+) -> Result<Vec<BossSpawn>, String> {
+    for boss in &bosses {
+        if !archetypes.contains_key(&boss.archetype) {
+            return Err(format!("unknown boss archetype \"{}\"", boss.archetype));
+        }
+        // Bosses join when a wave starts, and the first one starts with the run
+        if boss.wave < 2 {
+            return Err(format!("\"{}\" can't join before wave 2", boss.archetype));
+        }
+    }
+    Ok(bosses)
+}
+
+fn validate_stages(
+    stages: Vec<Stage>,
+    archetypes: &BTreeMap<String, Archetype>,
+) -> Result<Vec<Stage>, String> {
+    for stage in &stages {
+        let name = &stage.name;
+        if stage.waves.is_empty() {
+            return Err(format!("stage \"{name}\" has no waves"));
+        }
+        for (wave, stage_wave) in (1..).zip(&stage.waves) {
+            if stage_wave.weights.is_empty() {
+                return Err(format!("wave {wave} of \"{name}\" has no archetypes"));
+            }
+            let archetypes_used = stage_wave.weights.keys().chain(&stage_wave.bosses);
+            if let Some(unknown) = archetypes_used
+                .into_iter()
+                .find(|used| !archetypes.contains_key(*used))
+            {
+                return Err(format!(
+                    "wave {wave} of \"{name}\" uses the unknown archetype \"{unknown}\""
+                ));
+            }
+            if stage_wave
+                .weights
+                .values()
+                .any(|weight| !weight.is_finite() || *weight <= 0.)
+            {
+                return Err(format!(
+                    "wave {wave} of \"{name}\" has a non-positive weight"
+                ));
+            }
+            // Like in the regular waves, the first one starts with the run
+            if wave == 1 && !stage_wave.bosses.is_empty() {
+                return Err(format!("\"{name}\" can't have bosses in wave 1"));
+            }
+        }
+    }
+    Ok(stages)
+}
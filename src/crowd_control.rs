@@ -0,0 +1,116 @@
+//! Hard crowd control: stuns and freezes stopping an enemy outright, movement, healing and
+//! all. Enemies carry theirs in a column keyed by their handle, next to their debuffs.
+//!
+//! Every hold within `DIMINISHING_RESET` seconds of the last one lasts a fraction of the
+//! one before it, down to none at all, so chaining them can't keep an enemy in place for
+//! good. Stuns and freezes share the same diminishing returns.
+
+use egui::Color32;
+
+use crate::durations;
+use crate::i18n::{tr, trf};
+
+/// What each hold in a row is worth, anything past them is resisted.
+const DIMINISHING: [f32; 3] = [1., 0.5, 0.25];
+/// Seconds after a hold ends until the next one is worth full again.
+pub const DIMINISHING_RESET: f32 = 8.;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Control {
+    Stun,
+    Freeze,
+}
+
+impl Control {
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Control::Stun => "Stunned",
+            Control::Freeze => "Frozen",
+        })
+    }
+
+    /// Ring around a held enemy on the battlefield.
+    pub fn color(&self) -> Color32 {
+        match self {
+            Control::Stun => Color32::YELLOW,
+            Control::Freeze => Color32::from_rgb(120, 200, 255),
+        }
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+pub struct CrowdControl {
+    /// What holds the enemy and for how many more seconds.
+    held: Option<(Control, f32)>,
+    /// Holds landed since the diminishing returns last reset.
+    applied: u32,
+    /// Seconds until they reset.
+    reset_in: f32,
+}
+
+impl CrowdControl {
+    /// Holds the enemy for `duration` cut down by the diminishing returns, or for longer if
+    /// it's already held for longer. Returns the seconds the new hold was worth.
+    pub fn apply(&mut self, control: Control, duration: f32) -> f32 {
+        let Some(factor) = DIMINISHING.get(self.applied as usize) else {
+            return 0.;
+        };
+        let duration = duration * factor;
+        self.applied += 1;
+        match self.held {
+            Some((_, remaining)) if remaining >= duration => {}
+            _ => self.held = Some((control, duration)),
+        }
+        let remaining = self.held.map_or(0., |(_, remaining)| remaining);
+        self.reset_in = remaining + DIMINISHING_RESET;
+        duration
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        if let Some((_, remaining)) = &mut self.held {
+            *remaining -= delta;
+            if *remaining <= 0. {
+                self.held = None;
+            }
+        }
+        self.reset_in -= delta;
+        if self.reset_in <= 0. {
+            self.applied = 0;
+        }
+    }
+
+    /// What holds the enemy right now, if anything.
+    pub fn control(&self) -> Option<Control> {
+        self.held.map(|(control, _)| control)
+    }
+
+    pub fn is_held(&self) -> bool {
+        self.held.is_some()
+    }
+
+    /// What the next hold would be worth, 0 while it's resisted.
+    pub fn next_factor(&self) -> f32 {
+        DIMINISHING
+            .get(self.applied as usize)
+            .copied()
+            .unwrap_or(0.)
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        if let Some((control, remaining)) = self.held {
+            ui.colored_label(
+                control.color(),
+                trf(
+                    "{} for {}",
+                    &[control.name().to_owned(), durations::format(remaining)],
+                ),
+            );
+        }
+        if self.applied > 0 {
+            ui.label(trf(
+                "Crowd control resistance: next hold lasts {}%",
+                &[format!("{:.0}", self.next_factor() * 100.)],
+            ));
+        }
+    }
+}
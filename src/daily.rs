@@ -0,0 +1,56 @@
+//! The daily challenge: one seed and set of mutators per UTC day, the same for everyone.
+
+use crate::mutators::Mutator;
+use crate::rng::GameRng;
+use crate::saves;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Mutators a daily is played under.
+const DAILY_MUTATORS: usize = 2;
+
+/// Days since the Unix epoch, in UTC.
+pub fn today() -> u64 {
+    saves::now() / SECONDS_PER_DAY
+}
+
+pub fn seconds_until_next() -> u64 {
+    SECONDS_PER_DAY - saves::now() % SECONDS_PER_DAY
+}
+
+/// The seed of `day`'s run, scrambled so neighbouring days play nothing alike.
+pub fn seed(day: u64) -> u64 {
+    // splitmix64
+    let mut z = day.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// The mutators of `day`'s run, always different ones.
+pub fn mutators(day: u64) -> Vec<Mutator> {
+    let mut rng = GameRng::new(!seed(day));
+    let mut pool = Mutator::ALL.to_vec();
+    (0..DAILY_MUTATORS)
+        .map(|_| pool.swap_remove(rng.next_index(pool.len())))
+        .collect()
+}
+
+/// `day` as `YYYY-MM-DD`.
+pub fn date(day: u64) -> String {
+    // Howard Hinnant's civil_from_days
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day_of_month:02}")
+}
@@ -0,0 +1,99 @@
+//! Discord Rich Presence, compiled only with the `discord` feature.
+//!
+//! The IPC client lives on its own thread so a slow or missing Discord never stalls a frame.
+//! The application id is baked in at build time from `YOUR_EXCELLENCY_DISCORD_APP_ID`,
+//! without it, or in the browser, presence is silently disabled.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+const APP_ID: Option<&str> = option_env!("YOUR_EXCELLENCY_DISCORD_APP_ID");
+
+/// How far the start time may drift (game speed, pauses) before it's republished.
+const MAX_DRIFT_MILLIS: i64 = 60_000;
+
+/// What the player is shown doing on their Discord profile.
+#[derive(Clone, PartialEq)]
+pub struct Presence {
+    pub details: String,
+    pub state: String,
+}
+
+#[derive(Default)]
+pub struct DiscordPresence {
+    /// `None` asks the thread to clear the activity.
+    sender: Option<Sender<Option<(Presence, i64)>>>,
+    thread: Option<JoinHandle<()>>,
+    last: Option<(Presence, i64)>,
+}
+
+impl DiscordPresence {
+    /// Publishes `presence` if it changed, `started_at` is a unix timestamp in milliseconds.
+    pub fn update(&mut self, presence: Presence, started_at: i64) {
+        if let Some((last, last_started_at)) = &self.last {
+            if *last == presence && (started_at - last_started_at).abs() < MAX_DRIFT_MILLIS {
+                return;
+            }
+        }
+        if self.sender.is_none() {
+            self.start();
+        }
+        if let Some(sender) = &self.sender {
+            sender.send(Some((presence.clone(), started_at))).ok();
+        }
+        self.last = Some((presence, started_at));
+    }
+
+    fn start(&mut self) {
+        let Some(app_id) = APP_ID else {
+            return;
+        };
+        if cfg!(target_arch = "wasm32") {
+            return;
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.thread = Some(std::thread::spawn(move || run_client(app_id, receiver)));
+        self.sender = Some(sender);
+    }
+
+    /// Clears the activity and waits for the client to disconnect, call on exit.
+    pub fn clear(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            sender.send(None).ok();
+        }
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+        self.last = None;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run_client(app_id: &str, receiver: Receiver<Option<(Presence, i64)>>) {
+    use discord_rich_presence::activity::{Activity, Timestamps};
+    use discord_rich_presence::{DiscordIpc, DiscordIpcClient};
+
+    let mut client = DiscordIpcClient::new(app_id);
+    if let Err(err) = client.connect() {
+        log::info!("Discord isn't reachable, no rich presence: {err}");
+        return;
+    }
+    for message in receiver {
+        let result = match &message {
+            Some((presence, started_at)) => client.set_activity(
+                Activity::new()
+                    .details(presence.details.as_str())
+                    .state(presence.state.as_str())
+                    .timestamps(Timestamps::new().start(*started_at)),
+            ),
+            None => client.clear_activity(),
+        };
+        if let Err(err) = result {
+            log::warn!("Failed to update the Discord presence: {err}");
+        }
+    }
+    client.close().ok();
+}
+
+#[cfg(target_arch = "wasm32")]
+fn run_client(_app_id: &str, _receiver: Receiver<Option<(Presence, i64)>>) {}
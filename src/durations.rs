@@ -0,0 +1,64 @@
+//! How durations are shown, picked in the settings and applied everywhere a cooldown, wave
+//! timer or run length is displayed.
+
+use std::cell::Cell;
+
+use crate::i18n::tr;
+
+/// What a displayed frame is worth, regardless of the actual frame rate.
+pub const FRAMES_PER_SECOND: f32 = 60.;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum DurationFormat {
+    /// `1.5s`
+    #[default]
+    Seconds,
+    /// `2:05`, with tenths under a minute like `0:01.5`.
+    Clock,
+    /// `90f`, see `FRAMES_PER_SECOND`.
+    Frames,
+}
+
+impl DurationFormat {
+    pub const ALL: [DurationFormat; 3] = [
+        DurationFormat::Seconds,
+        DurationFormat::Clock,
+        DurationFormat::Frames,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DurationFormat::Seconds => tr("Seconds (1.5s)"),
+            DurationFormat::Clock => tr("Clock (m:ss)"),
+            DurationFormat::Frames => tr("Frames (90f)"),
+        }
+    }
+
+    /// `seconds` written this way.
+    pub fn format(&self, seconds: f32) -> String {
+        let seconds = seconds.max(0.);
+        match self {
+            DurationFormat::Seconds => format!("{seconds:.1}s"),
+            // Below what would round up to a whole minute
+            DurationFormat::Clock if seconds < 59.95 => format!("0:{seconds:04.1}"),
+            DurationFormat::Clock => {
+                let whole = seconds.round() as u64;
+                format!("{}:{:02}", whole / 60, whole % 60)
+            }
+            DurationFormat::Frames => format!("{:.0}f", seconds * FRAMES_PER_SECOND),
+        }
+    }
+}
+
+thread_local! {
+    static FORMAT: Cell<DurationFormat> = const { Cell::new(DurationFormat::Seconds) };
+}
+
+pub fn set_format(format: DurationFormat) {
+    FORMAT.with(|current| current.set(format));
+}
+
+/// `seconds` in the active format.
+pub fn format(seconds: f32) -> String {
+    FORMAT.with(Cell::get).format(seconds)
+}
@@ -0,0 +1,46 @@
+//! Killed enemies lingering on the battlefield for a moment while they fade out. Purely
+//! visual, the simulation has removed them already.
+
+use crate::enemy::Enemy;
+use crate::events::GameEvent;
+use crate::world::Components;
+
+/// Seconds of real time a fade takes.
+const FADE_SECONDS: f32 = 0.4;
+
+struct Dying {
+    enemy: Enemy,
+    /// Seconds since it died.
+    age: f32,
+}
+
+#[derive(Default)]
+pub struct DyingEnemies(Components<Dying>);
+
+impl DyingEnemies {
+    /// Starts the fade of an enemy that was just killed.
+    pub fn record(&mut self, event: &GameEvent) {
+        if let GameEvent::EnemyKilled(enemy) = event {
+            let dying = Dying {
+                enemy: enemy.clone(),
+                age: 0.,
+            };
+            self.0.insert(enemy.id, dying);
+        }
+    }
+
+    /// Advances the fades by a frame's `delta`, dropping the finished ones.
+    pub fn tick(&mut self, delta: f32) {
+        self.0.retain(|_, dying| {
+            dying.age += delta;
+            dying.age < FADE_SECONDS
+        });
+    }
+
+    /// Every fading enemy as it was when it died, with how far along its fade is in `0..1`.
+    pub fn iter(&self) -> impl Iterator<Item = (&Enemy, f32)> {
+        self.0
+            .iter()
+            .map(|(_, dying)| (&dying.enemy, dying.age / FADE_SECONDS))
+    }
+}
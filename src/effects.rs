@@ -0,0 +1,234 @@
+//! Timed buffs and debuffs, and the rules for applying the same one again.
+//!
+//! The Excellency carries its buffs, enemies carry their debuffs in a column keyed by their
+//! handle. Both go through `Effects::apply`, so stacking works the same on either side.
+//!
+//! Damage over time ticks on a timer of its own effect rather than every frame, so it deals
+//! the same in discrete chunks whatever the frame rate.
+
+use crate::durations;
+use crate::i18n::{tr, trf};
+use crate::timer::Timer;
+
+/// What an effect changes, every stat is scaled by `1 + amount`.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Stat {
+    /// How fast an enemy walks.
+    Speed,
+    /// Damage of the Excellency's attacks.
+    Damage,
+    /// How fast the Excellency's cooldowns run.
+    AttackSpeed,
+}
+
+impl Stat {
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Stat::Speed => "Speed",
+            Stat::Damage => "Damage",
+            Stat::AttackSpeed => "Attack speed",
+        })
+    }
+}
+
+/// How applying an effect that's already active combines with it.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Stacking {
+    /// The new application replaces the old one and restarts the duration.
+    Refresh,
+    /// Every application adds a stack, up to `max_stacks`, and restarts the duration.
+    Intensity { max_stacks: u32 },
+    /// Only the strongest application counts, a weaker one is ignored.
+    StrongestWins,
+    /// Every application restarts the duration, and every `interval` seconds it's been
+    /// active in a row adds a stack, up to `max_stacks`.
+    Building { max_stacks: u32, interval: f32 },
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
+pub struct Effect {
+    /// Applications with the same name stack, different names never do.
+    pub name: String,
+    pub stat: Stat,
+    /// Per stack, `-0.2` slows by 20%.
+    pub amount: f32,
+    /// Seconds it lasts.
+    pub duration: f32,
+    pub stacking: Stacking,
+    /// Damage dealt on top of the stat, an `amount` of zero makes a pure damage over time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub damage_over_time: Option<DamageOverTime>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct DamageOverTime {
+    /// Per tick and stack.
+    pub damage: f32,
+    /// Seconds between ticks, the first one comes a full interval after applying.
+    pub interval: f32,
+}
+
+impl DamageOverTime {
+    pub fn per_second(&self) -> f32 {
+        self.damage / self.interval
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+struct ActiveEffect {
+    effect: Effect,
+    stacks: u32,
+    remaining: f32,
+    /// Seconds since it was first applied, only `Stacking::Building` cares.
+    #[serde(default)]
+    age: f32,
+    /// Runs for damage over time only. Applying the effect again leaves it running, so an
+    /// effect refreshed every step still ticks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ticks: Option<Timer>,
+}
+
+impl ActiveEffect {
+    fn new(effect: Effect) -> Self {
+        Self {
+            remaining: effect.duration,
+            age: 0.,
+            ticks: effect.damage_over_time.map(|dot| Timer::new(dot.interval)),
+            effect,
+            stacks: 1,
+        }
+    }
+
+    /// Swaps in a new application of the same effect, keeping the tick timer.
+    fn replace(&mut self, effect: Effect) {
+        self.remaining = effect.duration;
+        self.ticks = match (self.ticks.take(), effect.damage_over_time) {
+            (Some(mut ticks), Some(dot)) => {
+                ticks.total = dot.interval;
+                ticks.remaining = ticks.remaining.min(dot.interval);
+                Some(ticks)
+            }
+            (None, Some(dot)) => Some(Timer::new(dot.interval)),
+            (_, None) => None,
+        };
+        self.effect = effect;
+    }
+}
+
+impl Effect {
+    /// What `Stacking::StrongestWins` compares, the damage when there's no stat change.
+    fn strength(&self) -> f32 {
+        match self.damage_over_time {
+            Some(dot) if self.amount == 0. => dot.per_second(),
+            _ => self.amount.abs(),
+        }
+    }
+}
+
+/// The effects active on one entity.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct Effects(Vec<ActiveEffect>);
+
+impl Effects {
+    /// Adds `effect`, combining it with an active one of the same name by its stacking rule.
+    pub fn apply(&mut self, effect: Effect) {
+        let Some(active) = self
+            .0
+            .iter_mut()
+            .find(|active| active.effect.name == effect.name)
+        else {
+            self.0.push(ActiveEffect::new(effect));
+            return;
+        };
+        match effect.stacking {
+            Stacking::Refresh | Stacking::Building { .. } => active.replace(effect),
+            Stacking::Intensity { max_stacks } => {
+                active.stacks = (active.stacks + 1).min(max_stacks.max(1));
+                active.replace(effect);
+            }
+            Stacking::StrongestWins => {
+                let (old, new) = (active.effect.strength(), effect.strength());
+                if new > old {
+                    active.replace(effect);
+                } else if new == old {
+                    active.remaining = active.remaining.max(effect.duration);
+                }
+            }
+        }
+    }
+
+    /// Counts down the durations and drops what ran out. Returns the damage over time that
+    /// came due within `delta`, ticks past the end of an effect don't count.
+    pub fn tick(&mut self, delta: f32) -> f32 {
+        let mut damage = 0.;
+        for active in &mut self.0 {
+            let dot = active.effect.damage_over_time;
+            if let (Some(ticks), Some(dot)) = (&mut active.ticks, dot) {
+                let ticked = ticks.tick(delta.min(active.remaining));
+                damage += dot.damage * (ticked * active.stacks) as f32;
+            }
+            if let Stacking::Building {
+                max_stacks,
+                interval,
+            } = active.effect.stacking
+            {
+                active.age += delta.min(active.remaining);
+                let built = (active.age / interval.max(f32::EPSILON)) as u32 + 1;
+                active.stacks = built.min(max_stacks.max(1));
+            }
+            active.remaining -= delta;
+        }
+        self.0.retain(|active| active.remaining > 0.);
+        damage
+    }
+
+    /// Sum of the effects on `stat`, each counted once per stack.
+    pub fn total(&self, stat: Stat) -> f32 {
+        self.0
+            .iter()
+            .filter(|active| active.effect.stat == stat)
+            .map(|active| active.effect.amount * active.stacks as f32)
+            .sum()
+    }
+
+    /// `base` scaled by the effects on `stat`, never below zero.
+    pub fn scale(&self, stat: Stat, base: f32) -> f32 {
+        base * (1. + self.total(stat)).max(0.)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        for active in &self.0 {
+            let effect = &active.effect;
+            let stacks = active.stacks as f32;
+            let mut parts = vec![];
+            if effect.amount != 0. {
+                parts.push(format!(
+                    "{} {:+.0}%",
+                    effect.stat.name(),
+                    effect.amount * stacks * 100.
+                ));
+            }
+            if let Some(dot) = effect.damage_over_time {
+                parts.push(trf(
+                    "{} damage every {}",
+                    &[
+                        format!("{:.1}", dot.damage * stacks),
+                        durations::format(dot.interval),
+                    ],
+                ));
+            }
+            ui.label(trf(
+                "{}: {} for {}",
+                &[
+                    effect.name.clone(),
+                    parts.join(", "),
+                    durations::format(active.remaining),
+                ],
+            ));
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! Enemies walking down the lane towards the Excellency.
+
+use egui::Color32;
+
+use crate::combat::{DamageSpec, DamageType, HitPoints};
+use crate::crowd_control::CrowdControl;
+use crate::effects::{Effects, Stat};
+use crate::i18n::tr;
+use crate::treasure;
+use crate::world::{Components, Entity};
+
+/// Fields at least this crowded are walked on all cores with the `parallel` feature, smaller
+/// ones aren't worth the overhead.
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+const PARALLEL_THRESHOLD: usize = 1000;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct Enemy {
+    /// Handed out when it joins the field, 0 until then.
+    #[serde(default)]
+    pub id: Entity,
+    pub hp: HitPoints,
+    /// Dealt to the Excellency on arrival.
+    pub damage: f32,
+    /// Distance walked per second.
+    pub speed: f32,
+    pub distance: Distance,
+    /// Subtracted from every hit.
+    pub armor: f32,
+    /// Absorbs damage before HP does, doesn't come back.
+    pub shield: f32,
+    /// HP healed per second.
+    pub regeneration: f32,
+    pub modifiers: Vec<EliteModifier>,
+    pub kind: EnemyKind,
+    /// Name of the archetype it was spawned as, empty in saves from before archetypes.
+    #[serde(default)]
+    pub archetype: String,
+    /// Flavor name given when it joins the field, see `names`.
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub is_boss: bool,
+    /// Damage that landed on it so far, shield included.
+    #[serde(default)]
+    pub damage_taken: f32,
+    /// Walking back towards the spawn instead, gone once it gets there or after this many
+    /// more seconds, see `has_escaped`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fleeing: Option<f32>,
+}
+
+/// Bosses are worth this many regular enemies.
+pub const BOSS_BOUNTY_FACTOR: u32 = 10;
+
+/// How far (in distance units) a healer reaches its allies.
+pub const HEAL_RADIUS: f32 = 15.;
+/// HP restored per second to every ally within `HEAL_RADIUS` of a healer.
+pub const HEALING_PER_SECOND: f32 = 2.;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum EnemyKind {
+    Grunt,
+    Healer,
+    /// Never attacks, flees with its loot, see `treasure`.
+    Chest,
+}
+
+impl Enemy {
+    /// A plain grunt with `hp` at `distance`, dealing 1 damage and walking 1 a second. For
+    /// tests, benchmarks and whatever else puts enemies on the field by hand.
+    pub fn new(hp: f32, distance: f32) -> Self {
+        Self {
+            id: Entity::default(),
+            hp: HitPoints::new_full(hp),
+            damage: 1.,
+            speed: 1.,
+            distance: Distance(distance),
+            armor: 0.,
+            shield: 0.,
+            regeneration: 0.,
+            modifiers: vec![],
+            kind: EnemyKind::Grunt,
+            archetype: String::new(),
+            name: String::new(),
+            is_boss: false,
+            damage_taken: 0.,
+            fleeing: None,
+        }
+    }
+
+    /// Regenerates and walks for `delta` seconds, slowed or hastened by its `debuffs`. A
+    /// `held` enemy neither regenerates nor moves.
+    pub fn tick(&mut self, delta: f32, debuffs: Option<&Effects>, held: bool) -> EnemyAfterTick {
+        if !held {
+            self.hp.heal(self.regeneration * delta);
+        }
+        self.hp.tick(delta);
+        if !held {
+            let speed =
+                debuffs.map_or(self.speed, |debuffs| debuffs.scale(Stat::Speed, self.speed));
+            match self.fleeing {
+                Some(_) => self.distance.0 += delta * speed,
+                None => self.distance.0 -= delta * speed,
+            }
+        }
+        if let Some(left) = &mut self.fleeing {
+            *left -= delta;
+        }
+        match self.distance.0 > 0. {
+            true => EnemyAfterTick::Normal,
+            false => EnemyAfterTick::ReachedExcellency,
+        }
+    }
+
+    /// How many hits of `damage` it survives, as text since it may be never.
+    pub fn hits_to_kill(&self, damage: f32) -> String {
+        let per_hit = damage - self.armor;
+        match per_hit > 0. {
+            true => ((self.hp.current + self.shield) / per_hit)
+                .ceil()
+                .to_string(),
+            false => "∞".to_owned(),
+        }
+    }
+
+    /// Size of the enemy's dot on the battlefield, never small enough to lose track of.
+    pub fn radius(&self) -> f32 {
+        3. + 7. * (self.hp.current / self.hp.maximum).max(0.)
+    }
+
+    /// Its flavor name, or the archetype's in saves from before names.
+    pub fn display_name(&self) -> &str {
+        match self.name.is_empty() {
+            true => &self.archetype,
+            false => &self.name,
+        }
+    }
+
+    /// What its hits on the Excellency are.
+    pub fn damage_type(&self) -> DamageType {
+        match self.modifiers.contains(&EliteModifier::Piercing) {
+            true => DamageType::True,
+            false => DamageType::Normal,
+        }
+    }
+
+    /// A fleeing enemy that made it back to the spawn or ran out of time.
+    pub fn has_escaped(&self) -> bool {
+        self.fleeing
+            .is_some_and(|left| left <= 0. || self.distance >= Distance::start())
+    }
+
+    pub fn is_elite(&self) -> bool {
+        !self.modifiers.is_empty()
+    }
+
+    /// Armor reduces every hit by a flat amount, the shield then soaks up what's left.
+    /// Returns how much of the hit landed, not counting damage past zero HP.
+    pub fn take_damage(&mut self, damage: impl Into<DamageSpec>) -> f32 {
+        self.take_hit(damage.into(), DamageType::Normal)
+    }
+
+    /// Like `take_damage`, but true damage skips the armor and the shield.
+    pub fn take_hit(&mut self, damage: DamageSpec, damage_type: DamageType) -> f32 {
+        let mut damage = damage.against(&self.hp);
+        let mut absorbed = 0.;
+        if damage_type == DamageType::Normal {
+            damage = (damage - self.armor).max(0.);
+            absorbed = damage.min(self.shield);
+            self.shield -= absorbed;
+        }
+        let landed = absorbed + (damage - absorbed).min(self.hp.current);
+        self.hp.take_damage(damage - absorbed);
+        self.damage_taken += landed;
+        landed
+    }
+
+    fn bounty_factor(&self) -> u32 {
+        match (self.is_boss, self.kind) {
+            (true, _) => BOSS_BOUNTY_FACTOR,
+            (false, EnemyKind::Chest) => treasure::BOUNTY_FACTOR,
+            (false, _) => 1,
+        }
+    }
+
+    pub fn gold_bounty(&self) -> u32 {
+        (1 + 4 * self.modifiers.len() as u32) * self.bounty_factor()
+    }
+
+    pub fn experience_bounty(&self) -> u32 {
+        (1 + 2 * self.modifiers.len() as u32) * self.bounty_factor()
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum EliteModifier {
+    Fast,
+    Armored,
+    Regenerating,
+    Shielded,
+    /// Its hits are true damage, the Excellency's block doesn't help.
+    Piercing,
+}
+
+impl EliteModifier {
+    pub const ALL: [EliteModifier; 5] = [
+        EliteModifier::Fast,
+        EliteModifier::Armored,
+        EliteModifier::Regenerating,
+        EliteModifier::Shielded,
+        EliteModifier::Piercing,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            EliteModifier::Fast => "Fast",
+            EliteModifier::Armored => "Armored",
+            EliteModifier::Regenerating => "Regenerating",
+            EliteModifier::Shielded => "Shielded",
+            EliteModifier::Piercing => "Piercing",
+        })
+    }
+
+    /// Modifiers are layered on top of the base stats rolled by the spawner.
+    pub fn apply(&self, enemy: &mut Enemy) {
+        match self {
+            EliteModifier::Fast => enemy.speed *= 1.5,
+            EliteModifier::Armored => enemy.armor += 2.,
+            EliteModifier::Regenerating => enemy.regeneration += 0.1 * enemy.hp.maximum,
+            EliteModifier::Shielded => enemy.shield += 0.5 * enemy.hp.maximum,
+            // Checked when it hits, see `Enemy::damage_type`
+            EliteModifier::Piercing => {}
+        }
+    }
+}
+
+/// Ticks every enemy of the field, returns the indices of the ones that reached the
+/// Excellency in field order, however the work was split.
+pub fn advance(
+    enemies: &mut [Enemy],
+    delta: f32,
+    debuffs: &Components<Effects>,
+    crowd_control: &Components<CrowdControl>,
+) -> Vec<usize> {
+    let arrived = |(index, enemy): (usize, &mut Enemy)| {
+        let held = crowd_control
+            .get(enemy.id)
+            .is_some_and(CrowdControl::is_held);
+        match enemy.tick(delta, debuffs.get(enemy.id), held) {
+            EnemyAfterTick::Normal => None,
+            EnemyAfterTick::ReachedExcellency => Some(index),
+        }
+    };
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    if enemies.len() >= PARALLEL_THRESHOLD {
+        use rayon::prelude::*;
+        // Collecting keeps the order, so the arrivals play out the same as single-threaded
+        return enemies
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(arrived)
+            .collect();
+    }
+    enemies.iter_mut().enumerate().filter_map(arrived).collect()
+}
+
+/// Orders the field closest first, which the range queries below rely on. Cheap on a field
+/// that's already nearly sorted, as it is between steps.
+pub fn sort_by_distance(enemies: &mut [Enemy]) {
+    enemies.sort_by(|a, b| {
+        a.distance
+            .partial_cmp(&b.distance)
+            .expect("Compared two f32's")
+    });
+}
+
+/// How many enemies of a field sorted by distance are within `range` of the Excellency,
+/// they're the first ones.
+pub fn count_in_range(enemies: &[Enemy], range: f32) -> usize {
+    enemies.partition_point(|enemy| enemy.distance.0 <= range)
+}
+
+/// Indices of the enemies of a field sorted by distance that are within `radius` of
+/// `distance`.
+pub fn around(enemies: &[Enemy], distance: f32, radius: f32) -> std::ops::Range<usize> {
+    let start = enemies.partition_point(|enemy| enemy.distance.0 < distance - radius);
+    start..count_in_range(enemies, distance + radius)
+}
+
+/// Takes the enemies that escaped off the field, keeping the order of the rest.
+pub fn remove_escaped(enemies: &mut Vec<Enemy>) -> Vec<Enemy> {
+    remove_where(enemies, Enemy::has_escaped)
+}
+
+/// Takes the enemies whose HP ran out off the field, keeping the order of the rest.
+pub fn remove_dead(enemies: &mut Vec<Enemy>) -> Vec<Enemy> {
+    remove_where(enemies, |enemy| enemy.hp.is_dead())
+}
+
+/// Removes the enemies matching `removed` in place, so a crowded field isn't moved into a
+/// new buffer for the few leaving it. Only those few are copied out, and the field isn't
+/// touched at all when nobody leaves, which is most of the time.
+fn remove_where(enemies: &mut Vec<Enemy>, removed: impl Fn(&Enemy) -> bool) -> Vec<Enemy> {
+    let leaving: Vec<Enemy> = enemies
+        .iter()
+        .filter(|enemy| removed(enemy))
+        .cloned()
+        .collect();
+    if !leaving.is_empty() {
+        enemies.retain(|enemy| !removed(enemy));
+    }
+    leaving
+}
+
+pub enum EnemyAfterTick {
+    Normal,
+    /// It should deal its damage and leave the field.
+    ReachedExcellency,
+}
+
+/// How far an enemy is from the Excellency, which stands at 0.
+#[derive(serde::Deserialize, serde::Serialize, PartialEq, PartialOrd, Clone)]
+pub struct Distance(pub f32);
+
+impl Distance {
+    /// Where enemies spawn.
+    pub fn start() -> Self {
+        Self(100.)
+    }
+
+    pub fn as_progress_bar(&self, color: Color32) -> egui::ProgressBar {
+        egui::ProgressBar::new(self.0 / 100.)
+            .show_percentage()
+            .fill(color)
+    }
+}
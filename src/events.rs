@@ -0,0 +1,114 @@
+//! What happened in the simulation, published on a single bus instead of every feature
+//! hooking into `GameState::step` on its own.
+//!
+//! Each event reaches the run's statistics and plugins the moment it's emitted, then waits
+//! in a queue the UI drains once per frame for sounds and notifications.
+
+use crate::boss_phases::PhaseAction;
+use crate::combat::{AttackKind, DamageType};
+use crate::enemy::Enemy;
+use crate::i18n::tr;
+use crate::incidents::Incident;
+use crate::relics::Relic;
+
+pub enum GameEvent {
+    /// An attack went off, whether or not anything was in range. `hits` are the distances
+    /// of the enemies it damaged.
+    AttackFired {
+        kind: AttackKind,
+        hits: Vec<f32>,
+    },
+    /// Damage landed on enemies by an attack or an aura, overkill not included. A hit that
+    /// was partly true damage is emitted once for each part.
+    DamageDealt {
+        amount: f32,
+        source: DamageSource,
+        damage_type: DamageType,
+    },
+    /// Damage over time landed on one enemy at `distance`, on top of its `DamageDealt`.
+    DamageTicked {
+        amount: f32,
+        distance: f32,
+    },
+    /// Joined the field, with its handle assigned.
+    EnemySpawned(Enemy),
+    /// A boss fell into its next `phase` and does `action`, once for each action.
+    BossPhaseEntered {
+        boss: String,
+        phase: usize,
+        action: PhaseAction,
+    },
+    /// Removed from the field, its bounty already collected.
+    EnemyKilled(Enemy),
+    /// Fled off the field before it was killed, see `Enemy::fleeing`.
+    EnemyEscaped(Enemy),
+    WaveStarted {
+        wave: u32,
+    },
+    /// A dying enemy left a relic behind, already added to the collection.
+    RelicFound(Relic),
+    /// The break before `wave` was cut short for `gold`.
+    WaveCalledEarly {
+        wave: u32,
+        gold: u32,
+    },
+    /// An enemy reached the Excellency, `blocked` is what the block took off its damage.
+    PlayerHit {
+        damage: f32,
+        blocked: f32,
+        damage_type: DamageType,
+    },
+    /// An enemy reached the Excellency and missed.
+    Dodged,
+    /// The ultimate went off, `hits` are the distances of the enemies it damaged.
+    UltimateFired {
+        hits: Vec<f32>,
+    },
+    /// A random incident struck. Emitted before its effect, so the meteor's kills follow it.
+    IncidentStruck(Incident),
+    /// The Excellency was hit while the attack was winding up, its cooldown starts over.
+    AttackInterrupted {
+        kind: AttackKind,
+    },
+}
+
+/// What landed a bit of damage on the enemies.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum DamageSource {
+    Attack(AttackKind),
+    /// Dealt back to the enemies reaching the Excellency.
+    Thorns,
+    /// Burnt over time by the auras.
+    Aura,
+    Ultimate,
+    /// A meteor, see `incidents::meteor`.
+    Incident,
+    Script,
+    /// Set off by an equipped relic, see `relics`.
+    Relic,
+}
+
+impl DamageSource {
+    pub const ALL: [DamageSource; 8] = [
+        DamageSource::Attack(AttackKind::Basic),
+        DamageSource::Attack(AttackKind::Big),
+        DamageSource::Thorns,
+        DamageSource::Aura,
+        DamageSource::Ultimate,
+        DamageSource::Incident,
+        DamageSource::Script,
+        DamageSource::Relic,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DamageSource::Attack(kind) => kind.name(),
+            DamageSource::Thorns => tr("Thorns"),
+            DamageSource::Aura => tr("Auras"),
+            DamageSource::Ultimate => tr("Ultimate"),
+            DamageSource::Incident => tr("Meteor"),
+            DamageSource::Script => tr("Scripts"),
+            DamageSource::Relic => tr("Relics"),
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! The one being defended: its health, purse and attacks.
+
+use crate::ascendancy::Perk;
+use crate::auras::Aura;
+use crate::character_sheet::CharacterStat;
+use crate::combat::{AttackKind, BasicAttack, HitPoints};
+use crate::effects::Effects;
+use crate::enemy::Enemy;
+use crate::talents::Talent;
+use crate::ultimate::Ultimate;
+use crate::upgrades::Upgrade;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Excellency {
+    /// The run ends when it reaches zero.
+    pub hp: HitPoints,
+    pub gold: u32,
+    pub experience: u32,
+    /// Extra fraction of gold paid by every bounty.
+    #[serde(default)]
+    pub gold_bonus: f32,
+    /// Fraction of the damage dealt that heals it back.
+    #[serde(default)]
+    pub life_steal: f32,
+    /// Fraction of the damage taken that's dealt back to the enemy dealing it.
+    #[serde(default)]
+    pub thorns: f32,
+    /// Chance an enemy reaching it misses entirely.
+    #[serde(default)]
+    pub dodge_chance: f32,
+    /// Taken off the damage of every enemy reaching it.
+    #[serde(default)]
+    pub block: f32,
+    /// HP healed per second.
+    #[serde(default)]
+    pub regeneration: f32,
+    pub basic_attack: BasicAttack,
+    pub big_attack: BasicAttack,
+    #[serde(default)]
+    pub buffs: Effects,
+    /// Unlocked so far, all of them are always on.
+    #[serde(default)]
+    pub auras: Vec<Aura>,
+    /// Radii the player set instead of the auras' own, see `aura_radius`.
+    #[serde(default)]
+    pub aura_radii: Vec<(Aura, f32)>,
+    #[serde(default)]
+    pub ultimate: Ultimate,
+    /// Picked so far, their bonuses are already in the stats above.
+    #[serde(default)]
+    pub talents: Vec<Talent>,
+    /// Leading entries of `talents` carried over from the previous New Game+ cycle, they
+    /// don't count towards the ones earned by leveling up.
+    #[serde(default)]
+    pub inherited_talents: usize,
+    /// Talents on top of the earned ones, from the Ascendancy perk.
+    #[serde(default)]
+    pub extra_talents: usize,
+    /// What each stat a perk, talent or upgrade changed was before the first of them did,
+    /// for the character sheet. Sliders and loadouts moved afterwards don't change it.
+    #[serde(default)]
+    pub base_stats: Vec<(CharacterStat, f32)>,
+    /// Ascendancy perks the run started with and their ranks, their bonuses are already in
+    /// the stats above.
+    #[serde(default)]
+    pub perks: Vec<(Perk, u32)>,
+    /// Bought with gold in survival, their bonuses are already in the attacks.
+    #[serde(default)]
+    pub upgrades: Vec<(AttackKind, Upgrade)>,
+}
+
+/// Experience it takes to get from level `level` to the next one.
+const EXPERIENCE_PER_LEVEL: u32 = 10;
+
+/// The level reached with `experience`, starting at 1. Each level takes
+/// `EXPERIENCE_PER_LEVEL` more than the one before.
+pub fn level_for(experience: u32) -> u32 {
+    let mut level = 1;
+    let mut needed = EXPERIENCE_PER_LEVEL;
+    let mut left = experience;
+    while left >= needed {
+        left -= needed;
+        level += 1;
+        needed += EXPERIENCE_PER_LEVEL;
+    }
+    level
+}
+
+impl Excellency {
+    pub fn level(&self) -> u32 {
+        level_for(self.experience)
+    }
+
+    pub fn attack(&self, kind: AttackKind) -> &BasicAttack {
+        match kind {
+            AttackKind::Basic => &self.basic_attack,
+            AttackKind::Big => &self.big_attack,
+        }
+    }
+
+    pub fn attack_mut(&mut self, kind: AttackKind) -> &mut BasicAttack {
+        match kind {
+            AttackKind::Basic => &mut self.basic_attack,
+            AttackKind::Big => &mut self.big_attack,
+        }
+    }
+
+    /// Pays out the gold and experience a killed enemy was worth.
+    pub fn collect_bounty(&mut self, enemy: &Enemy) {
+        self.gold += (enemy.gold_bounty() as f32 * (1. + self.gold_bonus)).round() as u32;
+        self.experience += enemy.experience_bounty();
+    }
+
+    /// How many of `upgrade` `kind` got so far.
+    pub fn upgrades_bought(&self, kind: AttackKind, upgrade: Upgrade) -> usize {
+        self.upgrades
+            .iter()
+            .filter(|&&bought| bought == (kind, upgrade))
+            .count()
+    }
+
+    /// Keeps what `stat` is now as its base, unless something changed it before. Called
+    /// right before every perk, talent and upgrade changes it.
+    pub fn remember_base(&mut self, stat: CharacterStat) {
+        if self.base_stat(stat).is_none() {
+            self.base_stats.push((stat, stat.value(self)));
+        }
+    }
+
+    /// What `stat` was before the first perk, talent or upgrade changed it, none if nothing
+    /// did.
+    pub fn base_stat(&self, stat: CharacterStat) -> Option<f32> {
+        self.base_stats
+            .iter()
+            .find(|(of, _)| *of == stat)
+            .map(|(_, base)| *base)
+    }
+
+    /// Spends gold on `upgrade` for `kind`, false if it's unaffordable or maxed out.
+    pub fn buy_upgrade(&mut self, kind: AttackKind, upgrade: Upgrade) -> bool {
+        let cost = upgrade.cost(self.upgrades_bought(kind, upgrade));
+        if self.gold < cost || upgrade.is_maxed(self.attack(kind)) {
+            return false;
+        }
+        self.gold -= cost;
+        self.remember_base(upgrade.stat(kind));
+        upgrade.apply(self.attack_mut(kind));
+        self.upgrades.push((kind, upgrade));
+        true
+    }
+
+    /// Spends gold on `aura`, false if it's unaffordable or already unlocked.
+    pub fn unlock(&mut self, aura: Aura) -> bool {
+        if self.auras.contains(&aura) || self.gold < aura.cost() {
+            return false;
+        }
+        self.gold -= aura.cost();
+        self.auras.push(aura);
+        true
+    }
+
+    /// How far `aura` reaches, the player's setting if there is one.
+    pub fn aura_radius(&self, aura: Aura) -> f32 {
+        self.aura_radii
+            .iter()
+            .find(|(of, _)| *of == aura)
+            .map_or(aura.radius(), |&(_, radius)| radius)
+    }
+
+    /// Sets how far `aura` reaches, kept within its `radius_range`.
+    pub fn set_aura_radius(&mut self, aura: Aura, radius: f32) {
+        let Some(range) = aura.radius_range() else {
+            return;
+        };
+        let radius = radius.clamp(*range.start(), *range.end());
+        match self.aura_radii.iter_mut().find(|(of, _)| *of == aura) {
+            Some((_, set)) => *set = radius,
+            None => self.aura_radii.push((aura, radius)),
+        }
+    }
+}
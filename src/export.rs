@@ -0,0 +1,161 @@
+//! The run as a CSV timeline for spreadsheets: a row of stats for every simulated second and
+//! a row for every combat log line, in the order they happened.
+
+/// The run's numbers at the end of one simulated second.
+pub struct StatSample {
+    pub elapsed: f32,
+    pub wave: u32,
+    pub hp: f32,
+    pub gold: u32,
+    pub kills: u32,
+    pub enemies: usize,
+    /// Summed over every enemy on the field.
+    pub enemy_hp: f32,
+    /// Since the run started.
+    pub damage_dealt: f32,
+}
+
+/// Samples kept at most, a longer run is sampled more and more sparsely.
+pub const MAX_SAMPLES: usize = 2048;
+
+/// The samples of a whole run. Once there are `MAX_SAMPLES` of them every other one is
+/// dropped and only every other second is sampled from then on, so however long the run goes
+/// the timeline covers all of it.
+pub struct StatSamples {
+    samples: Vec<StatSample>,
+    /// Seconds from one sample to the next.
+    every: u32,
+    /// The first second the next sample may come from.
+    next: u32,
+}
+
+impl Default for StatSamples {
+    fn default() -> Self {
+        Self {
+            samples: vec![],
+            every: 1,
+            next: 0,
+        }
+    }
+}
+
+impl StatSamples {
+    /// Keeps `sample` if it's due, thinning out the older ones when there are too many.
+    pub fn record(&mut self, sample: StatSample) {
+        let second = sample.elapsed.floor() as u32;
+        if second < self.next {
+            return;
+        }
+        self.samples.push(sample);
+        if self.samples.len() >= MAX_SAMPLES {
+            let mut index = 0;
+            self.samples.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.every *= 2;
+        }
+        let last = self.samples.last().expect("Just pushed one");
+        self.next = last.elapsed.floor() as u32 + self.every;
+    }
+
+    pub fn samples(&self) -> &[StatSample] {
+        &self.samples
+    }
+}
+
+const HEADER: &str = "elapsed,wave,hp,gold,kills,enemies,enemy_hp,damage_dealt,event";
+
+/// `text` as a single CSV field, quoted when it would otherwise break the row.
+pub fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_owned(),
+    }
+}
+
+/// Samples and log lines merged by time, a sample comes before the lines of the same second.
+pub fn to_csv<'a>(samples: &[StatSample], log: impl Iterator<Item = (f32, &'a str)>) -> String {
+    let mut rows: Vec<(f32, String)> = samples
+        .iter()
+        .map(|sample| {
+            let row = format!(
+                "{:.1},{},{:.1},{},{},{},{:.1},{:.1},",
+                sample.elapsed,
+                sample.wave,
+                sample.hp,
+                sample.gold,
+                sample.kills,
+                sample.enemies,
+                sample.enemy_hp,
+                sample.damage_dealt,
+            );
+            (sample.elapsed, row)
+        })
+        .collect();
+    rows.extend(
+        log.map(|(elapsed, text)| (elapsed, format!("{elapsed:.1},,,,,,,,{}", csv_field(text)))),
+    );
+    // Stable, so the sample stays ahead
+    rows.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let mut csv = String::from(HEADER);
+    csv.push('\n');
+    for (_, row) in rows {
+        csv.push_str(&row);
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Writes `contents` to `file_name` in the working directory, returning where it went.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save(file_name: &str, contents: &str) -> Result<String, String> {
+    let path = std::env::current_dir()
+        .map_err(|err| err.to_string())?
+        .join(file_name);
+    std::fs::write(&path, contents).map_err(|err| err.to_string())?;
+    Ok(path.display().to_string())
+}
+
+/// How long the browser gets to start a download before its URL is revoked.
+#[cfg(target_arch = "wasm32")]
+const REVOKE_AFTER_MS: i32 = 10_000;
+
+/// Hands `contents` to the browser as a download named `file_name`.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save(file_name: &str, contents: &str) -> Result<String, String> {
+    use wasm_bindgen::JsCast;
+
+    use wasm_bindgen::closure::Closure;
+
+    let failed = |err: wasm_bindgen::JsValue| format!("{err:?}");
+    let window = web_sys::window().ok_or_else(|| "no window".to_owned())?;
+    let document = window.document().ok_or_else(|| "no document".to_owned())?;
+    let body = document.body().ok_or_else(|| "no body".to_owned())?;
+    let parts = js_sys::Array::of1(&wasm_bindgen::JsValue::from_str(contents));
+    let blob = web_sys::Blob::new_with_str_sequence(&parts).map_err(failed)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(failed)?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(failed)?
+        .dyn_into()
+        .map_err(|_| "not an anchor".to_owned())?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    // Some browsers ignore clicks on detached anchors, and revoking the URL right away can
+    // cancel a download that hasn't started yet
+    body.append_child(&anchor).map_err(failed)?;
+    anchor.click();
+    let clean_up = Closure::once_into_js(move || {
+        anchor.remove();
+        let _ = web_sys::Url::revoke_object_url(&url);
+    });
+    window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            clean_up.unchecked_ref(),
+            REVOKE_AFTER_MS,
+        )
+        .map_err(failed)?;
+    Ok(file_name.to_owned())
+}
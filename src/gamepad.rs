@@ -0,0 +1,96 @@
+//! Controller input through gilrs, compiled only with the `gamepad` feature.
+//!
+//! Bound buttons trigger their actions. The rest drive egui's keyboard navigation, so menus
+//! work with a controller without any widget knowing about it.
+
+use crate::settings::{Action, GamepadBindings, PadButton};
+
+/// The controller backend is opened lazily, so headless uses of `GameState` never touch it.
+#[derive(Default)]
+pub enum Gamepad {
+    #[default]
+    Unopened,
+    Open(Box<gilrs::Gilrs>),
+    Unavailable,
+}
+
+impl Gamepad {
+    /// Drains this frame's controller events, returning the bound actions and turning the
+    /// navigation buttons into key presses in `raw_input`.
+    pub fn poll(
+        &mut self,
+        bindings: &GamepadBindings,
+        raw_input: &mut egui::RawInput,
+    ) -> Vec<Action> {
+        if let Gamepad::Unopened = self {
+            *self = match gilrs::Gilrs::new() {
+                Ok(gilrs) => Gamepad::Open(Box::new(gilrs)),
+                Err(err) => {
+                    log::warn!("No controller support: {err}");
+                    Gamepad::Unavailable
+                }
+            };
+        }
+        let Gamepad::Open(gilrs) = self else {
+            return vec![];
+        };
+
+        let mut actions = vec![];
+        while let Some(event) = gilrs.next_event() {
+            let gilrs::EventType::ButtonPressed(button, _) = event.event else {
+                continue;
+            };
+            let Some(button) = pad_button(button) else {
+                continue;
+            };
+            if let Some(action) = bindings.action(button) {
+                actions.push(action);
+            } else if let Some(key) = navigation_key(button) {
+                for pressed in [true, false] {
+                    raw_input.events.push(egui::Event::Key {
+                        key,
+                        physical_key: None,
+                        pressed,
+                        repeat: false,
+                        modifiers: egui::Modifiers::NONE,
+                    });
+                }
+            }
+        }
+        actions
+    }
+}
+
+fn pad_button(button: gilrs::Button) -> Option<PadButton> {
+    use gilrs::Button;
+
+    Some(match button {
+        Button::South => PadButton::South,
+        Button::East => PadButton::East,
+        Button::North => PadButton::North,
+        Button::West => PadButton::West,
+        Button::LeftTrigger => PadButton::LeftBumper,
+        Button::RightTrigger => PadButton::RightBumper,
+        Button::LeftTrigger2 => PadButton::LeftTrigger,
+        Button::RightTrigger2 => PadButton::RightTrigger,
+        Button::Select => PadButton::Select,
+        Button::Start => PadButton::Start,
+        Button::DPadUp => PadButton::DPadUp,
+        Button::DPadDown => PadButton::DPadDown,
+        Button::DPadLeft => PadButton::DPadLeft,
+        Button::DPadRight => PadButton::DPadRight,
+        _ => return None,
+    })
+}
+
+fn navigation_key(button: PadButton) -> Option<egui::Key> {
+    Some(match button {
+        PadButton::DPadUp => egui::Key::ArrowUp,
+        PadButton::DPadDown => egui::Key::ArrowDown,
+        PadButton::DPadLeft => egui::Key::ArrowLeft,
+        PadButton::DPadRight => egui::Key::ArrowRight,
+        PadButton::South => egui::Key::Enter,
+        PadButton::East => egui::Key::Escape,
+        _ => return None,
+    })
+}
@@ -0,0 +1,180 @@
+//! Time series sampled from the simulation for the "Graphs" section.
+
+use std::collections::VecDeque;
+
+use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints};
+
+use crate::events::DamageSource;
+use crate::i18n::{tr, trf};
+
+/// Damage dealt and taken over the course of one wave.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct WaveDamage {
+    pub dealt: f32,
+    pub taken: f32,
+}
+
+/// Damage dealt over the whole run, summed up per source in the order they first dealt any.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+pub struct DamageBySource(Vec<(DamageSource, f32)>);
+
+impl DamageBySource {
+    pub fn add(&mut self, source: DamageSource, amount: f32) {
+        match self.0.iter_mut().find(|(of, _)| *of == source) {
+            Some((_, total)) => *total += amount,
+            None => self.0.push((source, amount)),
+        }
+    }
+
+    pub fn get(&self, source: DamageSource) -> f32 {
+        self.0
+            .iter()
+            .find(|(of, _)| *of == source)
+            .map_or(0., |&(_, total)| total)
+    }
+
+    pub fn total(&self) -> f32 {
+        self.0.iter().map(|(_, total)| total).sum()
+    }
+
+    /// One stacked bar split by source, so the share of each is visible at a glance.
+    /// `true_damage` is the part of each source that ignored mitigation.
+    pub fn show(&self, ui: &mut egui::Ui, true_damage: &DamageBySource) {
+        let total = self.total();
+        if total <= 0. {
+            ui.label(tr("No damage dealt yet."));
+            return;
+        }
+        let mut charts: Vec<BarChart> = vec![];
+        for &(source, damage) in &self.0 {
+            let share = damage / total * 100.;
+            let part = true_damage.get(source);
+            let name = match part > 0. {
+                true => trf(
+                    "{} {}% ({}% true)",
+                    &[
+                        source.name().to_owned(),
+                        format!("{share:.0}"),
+                        format!("{:.0}", part / damage * 100.),
+                    ],
+                ),
+                false => format!("{} {share:.0}%", source.name()),
+            };
+            let below: Vec<&BarChart> = charts.iter().collect();
+            let chart = BarChart::new(vec![Bar::new(0., damage as f64)])
+                .horizontal()
+                .width(0.6)
+                .color(source_color(source))
+                .name(name)
+                .stack_on(&below);
+            charts.push(chart);
+        }
+        Plot::new("damage_by_source")
+            .height(80.)
+            .legend(Legend::default())
+            .include_x(0.)
+            .show_y(false)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .allow_zoom(false)
+            .x_axis_label(tr("Damage dealt"))
+            .show(ui, |plot| {
+                for chart in charts {
+                    plot.bar_chart(chart);
+                }
+            });
+    }
+}
+
+/// The same for a source in every run, indexed like `DamageSource::ALL`.
+const SOURCE_COLORS: [egui::Color32; 8] = [
+    egui::Color32::LIGHT_BLUE,
+    egui::Color32::GOLD,
+    egui::Color32::LIGHT_GREEN,
+    egui::Color32::from_rgb(255, 120, 40),
+    egui::Color32::from_rgb(200, 120, 255),
+    egui::Color32::LIGHT_RED,
+    egui::Color32::GRAY,
+    egui::Color32::from_rgb(255, 200, 120),
+];
+
+fn source_color(source: DamageSource) -> egui::Color32 {
+    let index = DamageSource::ALL.iter().position(|&of| of == source);
+    SOURCE_COLORS[index.unwrap_or_default()]
+}
+
+/// Five minutes of history at one sample per second.
+const CAPACITY: usize = 300;
+
+/// Excellency HP and the summed HP of all enemies, one sample per simulated second.
+#[derive(Default)]
+pub struct HpHistory {
+    excellency: VecDeque<[f64; 2]>,
+    enemies: VecDeque<[f64; 2]>,
+}
+
+impl HpHistory {
+    pub fn record(&mut self, elapsed: f32, excellency_hp: f32, enemy_hp: f32) {
+        for (series, hp) in [
+            (&mut self.excellency, excellency_hp),
+            (&mut self.enemies, enemy_hp),
+        ] {
+            if series.len() == CAPACITY {
+                series.pop_front();
+            }
+            series.push_back([elapsed as f64, hp as f64]);
+        }
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        let line = |series: &VecDeque<[f64; 2]>, name: &str| {
+            Line::new(series.iter().copied().collect::<PlotPoints>()).name(name)
+        };
+        Plot::new("hp_history")
+            .height(160.)
+            .legend(Legend::default())
+            .include_y(0.)
+            .allow_drag(false)
+            .allow_scroll(false)
+            .allow_zoom(false)
+            .x_axis_label(tr("Elapsed (s)"))
+            .show(ui, |plot| {
+                plot.line(line(&self.excellency, tr("Excellency HP")));
+                plot.line(line(&self.enemies, tr("Total enemy HP")));
+            });
+    }
+}
+
+/// Dealt and taken damage side by side for every wave so far.
+pub fn show_wave_damage(ui: &mut egui::Ui, waves: &[WaveDamage]) {
+    let chart = |name: &str, offset: f64, color: egui::Color32, damage: fn(&WaveDamage) -> f32| {
+        let bars = waves
+            .iter()
+            .enumerate()
+            .map(|(index, wave)| Bar::new((index + 1) as f64 + offset, damage(wave) as f64))
+            .collect();
+        BarChart::new(bars).width(0.4).color(color).name(name)
+    };
+    Plot::new("wave_damage")
+        .height(160.)
+        .legend(Legend::default())
+        .include_y(0.)
+        .allow_drag(false)
+        .allow_scroll(false)
+        .allow_zoom(false)
+        .x_axis_label(tr("Wave"))
+        .show(ui, |plot| {
+            plot.bar_chart(chart(
+                tr("Damage dealt"),
+                -0.2,
+                egui::Color32::LIGHT_BLUE,
+                |wave| wave.dealt,
+            ));
+            plot.bar_chart(chart(
+                tr("Damage taken"),
+                0.2,
+                egui::Color32::LIGHT_RED,
+                |wave| wave.taken,
+            ));
+        });
+}
@@ -0,0 +1,72 @@
+//! Requests for the online features, run on a background thread and reported back through a
+//! channel, so a slow or missing server never blocks a frame. The browser build has no
+//! blocking HTTP client, there every request fails right away.
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn agent() -> ureq::Agent {
+        ureq::AgentBuilder::new().timeout(TIMEOUT).build()
+    }
+
+    pub fn post(url: &str, body: &impl serde::Serialize) -> Result<(), String> {
+        agent()
+            .post(url)
+            .send_json(body)
+            .map(drop)
+            .map_err(|err| err.to_string())
+    }
+
+    #[cfg(feature = "leaderboard")]
+    pub fn get<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, String> {
+        agent()
+            .get(url)
+            .call()
+            .map_err(|err| err.to_string())
+            .and_then(|response| response.into_json().map_err(|err| err.to_string()))
+    }
+
+    /// Runs `request` on its own thread.
+    pub fn spawn<T: Send + 'static>(
+        request: impl FnOnce() -> Result<T, String> + Send + 'static,
+    ) -> std::sync::mpsc::Receiver<Result<T, String>> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::spawn(move || sender.send(request()).ok());
+        receiver
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use crate::i18n::tr;
+
+    fn unavailable() -> String {
+        tr("not available in the browser").to_owned()
+    }
+
+    pub fn post(_url: &str, _body: &impl serde::Serialize) -> Result<(), String> {
+        Err(unavailable())
+    }
+
+    #[cfg(feature = "leaderboard")]
+    pub fn get<T: serde::de::DeserializeOwned>(_url: &str) -> Result<T, String> {
+        Err(unavailable())
+    }
+
+    /// There are no threads on the web, `request` fails immediately anyway.
+    pub fn spawn<T: Send + 'static>(
+        request: impl FnOnce() -> Result<T, String> + Send + 'static,
+    ) -> std::sync::mpsc::Receiver<Result<T, String>> {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        sender.send(request()).ok();
+        receiver
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::*;
+#[cfg(target_arch = "wasm32")]
+pub use web::*;
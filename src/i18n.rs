@@ -0,0 +1,608 @@
+//! Minimal gettext-style localization.
+//!
+//! UI strings are written in English and looked up in the table of the active language,
+//! falling back to the English text when there's no translation yet.
+
+use std::cell::Cell;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Slovak,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Slovak];
+
+    /// Always in the language itself, so players can find their way back.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Slovak => "Slovenčina",
+        }
+    }
+}
+
+thread_local! {
+    static LANGUAGE: Cell<Language> = const { Cell::new(Language::English) };
+}
+
+pub fn set_language(language: Language) {
+    LANGUAGE.with(|current| current.set(language));
+}
+
+/// Translates `text` into the active language.
+pub fn tr(text: &'static str) -> &'static str {
+    let translation = match LANGUAGE.with(Cell::get) {
+        Language::English => None,
+        Language::Slovak => slovak(text),
+    };
+    translation.unwrap_or(text)
+}
+
+/// Translates `template` and fills its `{}` placeholders with `args`, in order.
+pub fn trf(template: &'static str, args: &[String]) -> String {
+    let mut parts = tr(template).split("{}");
+    let mut result = parts.next().unwrap_or_default().to_owned();
+    for (part, arg) in parts.zip(args.iter().map(String::as_str).chain(std::iter::repeat(""))) {
+        result.push_str(arg);
+        result.push_str(part);
+    }
+    result
+}
+
+fn slovak(text: &str) -> Option<&'static str> {
+    Some(match text {
+        // Run summary
+        "Run summary" => "Prehľad hry",
+        "Seed: {}" => "Seed: {}",
+        "Elapsed: {}" => "Uplynulo: {}",
+        "Next seed:" => "Ďalší seed:",
+        "Randomize" => "Náhodný",
+        "Restart" => "Reštart",
+        "Pause" => "Pauza",
+        "Speed: x{}" => "Rýchlosť: x{}",
+        "Settings" => "Nastavenia",
+        "Dismiss" => "Zavrieť",
+        "Previous session couldn't be loaded: {}" => "Predchádzajúcu hru sa nepodarilo načítať: {}",
+
+        // Saves
+        "Save / Load" => "Uložiť / Načítať",
+        "Name:" => "Názov:",
+        "Save" => "Uložiť",
+        "Load" => "Načítať",
+        "Delete" => "Zmazať",
+        "{}. <empty>" => "{}. <prázdne>",
+        "{}. {} - wave {}, {} played, {} gold, saved {}" => "{}. {} - vlna {}, hrané {}, {} zlata, uložené {}",
+        "Backup or share the whole run as JSON:" => "Zálohujte alebo zdieľajte celú hru ako JSON:",
+        "Copy save to clipboard" => "Kopírovať do schránky",
+        "Paste an exported save here" => "Sem vložte exportovanú hru",
+        "Import" => "Importovať",
+        "Slot {}: {}" => "Slot {}: {}",
+        "Export failed: {}" => "Export zlyhal: {}",
+        "Import failed: {}" => "Import zlyhal: {}",
+        "just now" => "práve teraz",
+        "{}m ago" => "pred {} min",
+        "{}h {}m ago" => "pred {} h {} min",
+        "{}d ago" => "pred {} dňami",
+
+        // Settings
+        "Language" => "Jazyk",
+        "Hotkeys" => "Klávesové skratky",
+        "Press a key..." => "Stlačte kláves...",
+        "Unbound" => "Nepriradené",
+        "Clear" => "Zrušiť",
+        "Press Escape to cancel." => "Escape zruší priradenie.",
+        "Cast basic attack" => "Základný útok",
+        "Focus fire" => "Sústrediť paľbu",
+        "never" => "nikdy",
+        "Reaches the Excellency in" => "Dorazí k Excelencii o",
+        "What can hit it" => "Čo ho môže zasiahnuť",
+        "Disabled by a mutator" => "Vypnuté mutátorom",
+        "Out of range by {}" => "Mimo dosahu o {}",
+        "Can't get through its armor" => "Neprerazí jeho brnenie",
+        "In range, {} hits to kill" => "V dosahu, {} zásahov na zabitie",
+        "Within reach" => "V dosahu",
+        "Out of reach" => "Mimo dosahu",
+        "Stop focusing" => "Prestať sústreďovať",
+        "Inspect" => "Preskúmať",
+        "Mark" => "Označiť",
+        "Unmark" => "Zrušiť označenie",
+        "Kill" => "Zabiť",
+        "Heal to full" => "Vyliečiť naplno",
+        "Enemy" => "Nepriateľ",
+        "FOCUSED" => "CIEĽ",
+        "MARKED" => "OZNAČENÝ",
+        "Cooldown: {}" => "Čakanie: {}",
+        "Charge: {} / {}" => "Nabitie: {} / {}",
+        "Scripted, casts on its own." => "Skriptovaná, zosiela sa sama.",
+        "Cast big attack" => "Veľký útok",
+        "Speed up" => "Zrýchliť",
+        "Speed down" => "Spomaliť",
+        "Theme" => "Vzhľad",
+        "Dark" => "Tmavý",
+        "Light" => "Svetlý",
+        "HP bars" => "Ukazovatele HP",
+        "Cooldown bars" => "Ukazovatele čakania",
+        "Enemy bars" => "Ukazovatele nepriateľov",
+        "Reset colors" => "Obnoviť farby",
+        "Accessibility" => "Prístupnosť",
+        "UI scale:" => "Mierka rozhrania:",
+        "Large text" => "Veľké písmo",
+        "Sound" => "Zvuk",
+        "Mute" => "Stlmiť",
+        "Basic" => "Základný",
+        "Big" => "Veľký",
+        "{} in range: {}" => "{} v dosahu: {}",
+        "Master" => "Celková",
+        "Effects" => "Efekty",
+        "Music" => "Hudba",
+        "Mute when the window is in the background" => "Stlmiť, keď okno nie je aktívne",
+
+        // Spawner and enemies
+        "Enemy spawner" => "Generátor nepriateľov",
+        "Damage:" => "Poškodenie:",
+        "Maximum HP:" => "Maximálne HP:",
+        "Speed:" => "Rýchlosť:",
+        "Elite chance:" => "Šanca na elitu:",
+        "Healer chance:" => "Šanca na liečiteľa:",
+        "Scaling" => "Škálovanie",
+        "HP" => "HP",
+        "Speed" => "Rýchlosť",
+        "Damage" => "Poškodenie",
+        "Spawn rate" => "Frekvencia",
+        "{}: x{}" => "{}: x{}",
+        "Projection" => "Projekcia",
+        "(max x{} in {} min)" => "(max x{} za {} min)",
+        "Linear" => "Lineárna",
+        "Exponential" => "Exponenciálna",
+        "Polynomial" => "Polynomiálna",
+        " / min" => " / min",
+        "growth" => "rast",
+        "coef" => "koef",
+        "exp" => "exp",
+        "Enemies" => "Nepriatelia",
+        "HOVERED" => "POD KURZOROM",
+        "HEALER" => "LIEČITEĽ",
+        "ELITE: {}" => "ELITA: {}",
+        "Distance: {}" => "Vzdialenosť: {}",
+        "HP: {}" => "HP: {}",
+        "ELITE" => "ELITA",
+        "{} more enemies" => "ďalší nepriatelia: {}",
+        "Distance" => "Vzdialenosť",
+        "All" => "Všetci",
+        "In basic attack range" => "V dosahu základného útoku",
+        "Elites" => "Elitní",
+        "Sort by:" => "Zoradiť podľa:",
+        "Show:" => "Zobraziť:",
+        "HP:" => "HP:",
+        "Damage: {}" => "Poškodenie: {}",
+        "Speed: {}" => "Rýchlosť: {}",
+        "Armor: {}" => "Brnenie: {}",
+        "Shield: {}" => "Štít: {}",
+        "Fast" => "Rýchly",
+        "Armored" => "Obrnený",
+        "Regenerating" => "Regenerujúci",
+        "Shielded" => "So štítom",
+        "Piercing" => "Prierazný",
+
+        // Excellency
+        "Player stuff" => "Hráč",
+        "Reset HP" => "Obnoviť HP",
+        "Gold: {}" => "Zlato: {}",
+        "Experience: {}" => "Skúsenosti: {}",
+        "Basic Attack" => "Základný útok",
+        "Big Attack" => "Veľký útok",
+        "Enabled" => "Zapnutý",
+        "Reset cooldown" => "Obnoviť čakanie",
+        "Cooldown:" => "Čakanie:",
+        "Range:" => "Dosah:",
+        "Max Targets:" => "Max. cieľov:",
+        "Targeting:" => "Zameriavanie:",
+        "Closest" => "Najbližší",
+        "Lowest HP" => "Najmenej HP",
+        "Healers first" => "Najprv liečitelia",
+        "Focused" => "Vybraný cieľ",
+        "Total kills" => "Zabití celkovo",
+        "Most kills" => "Najviac zabití",
+        "Longest survival" => "Najdlhšie prežitie",
+        "Highest wave" => "Najvyššia vlna",
+        "Kills" => "Zabití",
+        "Survived" => "Prežité",
+        "Wave reached" => "Dosiahnutá vlna",
+        "New best!" => "Nový rekord!",
+        "Defeat" => "Porážka",
+        "The Excellency has fallen." => "Excelencia padla.",
+        "New run" => "Nová hra",
+        "Kills: {}" => "Zabití: {}",
+        "Records" => "Rekordy",
+        "Run history" => "História hier",
+        "No finished runs yet." => "Zatiaľ žiadne dokončené hry.",
+        "Seed" => "Seed",
+        "Build" => "Zostava",
+        "Replay seed" => "Hrať znova",
+        "Finished" => "Dokončené",
+        "Duration" => "Trvanie",
+        "Attack" => "Útok",
+        "Range" => "Dosah",
+        "Targets" => "Ciele",
+        "Cooldown" => "Čakanie",
+        "Leaderboard" => "Rebríček",
+        "Defeated" => "Porazený",
+        "Fighting" => "V boji",
+        "Between waves" => "Medzi vlnami",
+        "Wave {}" => "Vlna {}",
+        "Server:" => "Server:",
+        "Player name:" => "Meno hráča:",
+        "Player" => "Hráč",
+        "Spawner" => "Generátor",
+        "Battlefield" => "Bojisko",
+        "Layout:" => "Rozloženie:",
+        "Automatic" => "Automatické",
+        "Desktop" => "Počítač",
+        "Touch" => "Dotykové",
+        "Controller" => "Ovládač",
+        "BOSS" => "BOSS",
+        "Scripted abilities" => "Skriptované schopnosti",
+        "Run statistics" => "Štatistiky hry",
+        "Content reloaded" => "Obsah znovu načítaný",
+        "Saved to slot {}" => "Uložené do slotu {}",
+        "Save copied to the clipboard" => "Uložená hra skopírovaná do schránky",
+        "Content not reloaded: {}" => "Obsah nebol znovu načítaný: {}",
+        "Scripts reloaded: {}" => "Skripty znovu načítané: {}",
+        "Wave {} started" => "Začala vlna {}",
+        "Attack speed" => "Rýchlosť útokov",
+        "{}: {} for {}" => "{}: {} na {}",
+        "{} damage every {}" => "{} poškodenia každých {}",
+        "Frost aura" => "Mrazivá aura",
+        "Burning aura" => "Horiaca aura",
+        "Slows enemies inside it by 10% more every second they stay, up to 30%. A wider radius slows less." => "Spomaľuje nepriateľov v nej o 10 % viac za každú sekundu, ktorú v nej zostanú, najviac o 30 %. Väčší polomer spomaľuje menej.",
+        "Burns enemies within 10 distance for 3 HP per second." => "Páli nepriateľov do vzdialenosti 10 za 3 HP za sekundu.",
+        "Auras" => "Aury",
+        "Unlocked" => "Odomknuté",
+        "Unlock ({} gold)" => "Odomknúť ({} zlata)",
+        "Spends {} gold of this run, the aura stays on until it ends." => "Minie {} zlata z tohto behu, aura zostane zapnutá až do jeho konca.",
+        "Needs {} gold, kills pay it." => "Treba {} zlata, platia ho zabitia.",
+        "Life steal:" => "Vysávanie života:",
+        "Fraction of the damage dealt to enemies that heals the Excellency." => "Podiel poškodenia udeleného nepriateľom, ktorý vylieči Excelenciu.",
+        "Healing per second" => "Liečenie za sekundu",
+        "Thorns:" => "Tŕne:",
+        "Dodge chance:" => "Šanca na úhyb:",
+        "Chance an enemy reaching the Excellency misses and deals no damage." => "Šanca, že nepriateľ, ktorý sa dostane k Excelencii, minie a neudelí žiadne poškodenie.",
+        "Effective HP" => "Efektívne HP",
+        "Block:" => "Blok:",
+        "Taken off the damage of every enemy that reaches the Excellency." => "Odpočíta sa od poškodenia každého nepriateľa, ktorý sa dostane k Excelencii.",
+        "Took {} damage, blocked {}" => "Utŕžené poškodenie {}, zablokované {}",
+        "Took {} damage" => "Utŕžené poškodenie {}",
+        "Dodged a hit" => "Uhnutie zásahu",
+        "Slew the boss {}" => "Porazený boss {}",
+        "Nothing happened yet." => "Zatiaľ sa nič nestalo.",
+        "Dodged!" => "Uhnuté!",
+        "Blocked {}" => "Zablokované {}",
+        "Combat log" => "Záznam boja",
+        "Carry overkill" => "Prenášať nadbytočné poškodenie",
+        "Screen shake:" => "Otrasy obrazovky:",
+        "Flash the HP bar on heavy hits" => "Zablikať HP pri silných zásahoch",
+        "Reduced motion" => "Obmedzený pohyb",
+        "Hit feedback" => "Odozva zásahov",
+        "Charging:" => "Nabíjanie:",
+        "Wind-up:" => "Nápriah:",
+        "Seconds the attack charges after its cooldown before going off. Getting hit while charging starts the cooldown over." => "Sekundy, počas ktorých sa útok po uplynutí čakania nabíja, kým vyrazí. Zásah počas nabíjania spustí čakanie odznova.",
+        "{} interrupted" => "{} prerušený",
+        "Hold" => "Podržať",
+        "Once charged, wait for a cast instead of firing on its own." => "Po nabití čakať na zoslanie namiesto samostatného výstrelu.",
+        "Ready, waiting to be cast." => "Pripravený, čaká na zoslanie.",
+        "Charge:" => "Nabitie:",
+        "Charged by kills ({} each) and by damage dealt ({} per point)." => "Nabíja sa zabitiami ({} za každé) a udeleným poškodením ({} za bod).",
+        "Kills every enemy on the field, bosses lose a quarter of their maximum HP and are stunned." => "Zabije všetkých nepriateľov na bojisku, bossovia stratia štvrtinu svojho maximálneho HP a sú omráčení.",
+        "Unleash the ultimate" => "Rozpútať ultimátnu schopnosť",
+        "Unleashed the ultimate on {} enemies" => "Ultimátna schopnosť rozpútaná na {} nepriateľov",
+        "Ultimate" => "Ultimátna schopnosť",
+        "Choose a talent" => "Vyber si talent",
+        "Level {} reached, pick one to keep for the rest of the run." => "Dosiahnutá úroveň {}, vyber si jeden na zvyšok behu.",
+        "Level: {}" => "Úroveň: {}",
+        "Talents" => "Talenty",
+        "Sharp edge" => "Ostrá čepeľ",
+        "Heavy hand" => "Ťažká ruka",
+        "Long reach" => "Dlhý dosah",
+        "Quick draw" => "Rýchle tasenie",
+        "Regeneration" => "Regenerácia",
+        "Toughness" => "Húževnatosť",
+        "Vampirism" => "Upírstvo",
+        "Bulwark" => "Bašta",
+        "Evasion" => "Vyhýbanie",
+        "+15% basic attack damage" => "+15 % poškodenia základného útoku",
+        "+15% big attack damage" => "+15 % poškodenia veľkého útoku",
+        "+5 basic attack range" => "+5 dosahu základného útoku",
+        "-10% basic attack cooldown" => "-10 % čakania základného útoku",
+        "+1 HP regenerated per second" => "+1 HP obnovené za sekundu",
+        "+20 maximum HP" => "+20 maximálneho HP",
+        "+5% life steal" => "+5 % vysávania života",
+        "+2 block" => "+2 bloku",
+        "+5% dodge chance" => "+5 % šance na úhyb",
+        "A meteor crashes into the enemies!" => "Meteor dopadol medzi nepriateľov!",
+        "Frenzy! Enemies spawn twice as fast for 30s." => "Šialenstvo! Nepriatelia sa 30 s objavujú dvakrát rýchlejšie.",
+        "A blessing heals the Excellency." => "Požehnanie lieči Excelenciu.",
+        "Frenzy: {} left" => "Šialenstvo: zostáva {}",
+        "Enemies 30% faster" => "Nepriatelia o 30 % rýchlejší",
+        "Enemy HP doubled" => "Dvojnásobné HP nepriateľov",
+        "Enemies spawn 50% more often" => "Nepriatelia sa objavujú o 50 % častejšie",
+        "Excellency HP halved" => "Polovičné HP Excelencie",
+        "Gold doubled" => "Dvojnásobné zlato",
+        "Daily challenge" => "Denná výzva",
+        "Daily challenge of {}" => "Denná výzva z {}",
+        "Completed, best: wave {}, {} kills" => "Splnená, najlepšie: vlna {}, {} zabití",
+        "Not completed yet" => "Zatiaľ nesplnená",
+        "Play today's daily" => "Hrať dnešnú výzvu",
+        "Next daily in {}h {}m" => "Ďalšia výzva o {} h {} min",
+        "No big attack" => "Bez veľkého útoku",
+        "Attack range halved" => "Polovičný dosah útokov",
+        "Mutators" => "Mutátory",
+        "Used by the next restart." => "Použijú sa pri ďalšom reštarte.",
+        "{} (+{}% gold)" => "{} (+{} % zlata)",
+        "Deepest New Game+" => "Najhlbšia Nová hra+",
+        "New Game+ {}" => "Nová hra+ {}",
+        "Start New Game+ {}" => "Začať Novú hru+ {}",
+        "Enemies get tougher, the Excellency keeps its auras and talents." => "Nepriatelia zosilnejú, Excelencia si ponechá aury a talenty.",
+        "The milestone boss fell, New Game+ is unlocked." => "Míľnikový boss padol, Nová hra+ je odomknutá.",
+        "Profile: {}" => "Profil: {}",
+        "Profiles" => "Profily",
+        "Every profile has its own saves, settings and records." => "Každý profil má vlastné uložené hry, nastavenia a rekordy.",
+        "Playing" => "Hrá",
+        "Play" => "Hrať",
+        "Add" => "Pridať",
+        "Stat sheet" => "Prehľad štatistík",
+        "Character sheet" => "Karta postavy",
+        "Next wave in {}" => "Ďalšia vlna o {}",
+        "Call wave early (+{} gold)" => "Privolať vlnu skôr (+{} zlata)",
+        "Skips the rest of the break for gold." => "Preskočí zvyšok prestávky za zlato.",
+        "Only between waves." => "Iba medzi vlnami.",
+        "Called wave {} early for {} gold" => "Vlna {} privolaná skôr za {} zlata",
+        "Call the next wave early" => "Privolať ďalšiu vlnu skôr",
+        "HP fell below {}%, auto-continue stopped." => "Životy klesli pod {} %, automatické pokračovanie zastavené.",
+        "Auto-continue stopped, HP fell too low." => "Automatické pokračovanie zastavené, životy klesli príliš nízko.",
+        "Auto-continue" => "Automaticky pokračovať",
+        "Starts the next stage on its own after a win." => "Po víťazstve sám spustí ďalšiu etapu.",
+        "Stop below HP" => "Zastaviť pod životmi",
+        "Pauses the run instead once HP falls below this." => "Keď životy klesnú pod túto hranicu, hru namiesto toho pozastaví.",
+        "Automation" => "Automatizácia",
+        "Laurels: {}" => "Vavríny: {}",
+        "On" => "Zapnuté",
+        "Unlock ({} laurels)" => "Odomknúť ({} vavrínov)",
+        "Victories earn laurels." => "Vavríny sa získavajú víťazstvami.",
+        "Auto-cast the ultimate" => "Automaticky použiť ultimátnu schopnosť",
+        "Auto-buy upgrades" => "Automaticky kupovať vylepšenia",
+        "Auto-call waves" => "Automaticky privolávať vlny",
+        "Unleashes the ultimate as soon as it's charged." => "Použije ultimátnu schopnosť hneď, ako je nabitá.",
+        "Spends gold on the cheapest upgrade it can afford." => "Minie zlato na najlacnejšie dostupné vylepšenie.",
+        "Cuts every break short, collecting the early bonus." => "Skráti každú prestávku a zinkasuje bonus za skoršiu vlnu.",
+        "Ascendancy" => "Povznesenie",
+        "Ascendancy shards: {}" => "Úlomky povznesenia: {}",
+        "+{} Ascendancy shards" => "+{} úlomkov povznesenia",
+        "Runs reaching wave {} or deeper pay shards." => "Hry, ktoré dosiahnu vlnu {} alebo ďalej, platia úlomkami.",
+        "Shards the rank costs" => "Koľko úlomkov stojí táto úroveň",
+        "Perks apply from the next run on." => "Výhody platia od ďalšej hry.",
+        "Starting gold" => "Počiatočné zlato",
+        "Base damage" => "Základné poškodenie",
+        "Extra talent slot" => "Talent navyše",
+        "+25 gold at the start of every run per rank." => "+25 zlata na začiatku každej hry za úroveň.",
+        "+5% damage for both attacks per rank." => "+5 % poškodenia oboch útokov za úroveň.",
+        "A talent to pick right at the start of every run." => "Talent na výber hneď na začiatku každej hry.",
+        "Relics" => "Relikvie",
+        "Found a relic: {}" => "Nájdená relikvia: {}",
+        "Equipped: {} of {}" => "Vybavené: {} z {}",
+        "Elites and bosses sometimes drop relics." => "Elitní nepriatelia a bossovia občas zanechajú relikviu.",
+        "Every slot is taken." => "Všetky miesta sú obsadené.",
+        "{} hits twice, the second time for {}%" => "{} zasiahne dvakrát, druhýkrát za {} %",
+        "Enemies explode on death for {}% of their max HP" => "Nepriatelia pri smrti vybuchnú za {} % svojich maximálnych životov",
+        "Every kill heals {} HP" => "Každé zabitie vylieči {} životov",
+        "Every kill pays {} more gold" => "Každé zabitie vynesie o {} zlata viac",
+        "{} got away" => "{} ušiel",
+        "A treasure chest appeared, break it before it flees!" => "Objavila sa truhlica s pokladom, rozbi ju, kým ujde!",
+        "{} is enraged" => "{} zúri",
+        "{} calls for help" => "{} volá o pomoc",
+        "Phase {} of {}" => "Fáza {} z {}",
+        "Slew {}" => "Porazený {}",
+        "Stat variance:" => "Rozptyl vlastností:",
+        "How far each spawned enemy's HP, speed and damage may land either way of the values above, as a fraction of them." => "Ako ďaleko na obe strany od hodnôt vyššie môže padnúť HP, rýchlosť a poškodenie každého nepriateľa, ako ich zlomok.",
+        "HP range" => "Rozsah HP",
+        "Radius" => "Polomer",
+        "Stunned" => "Omráčený",
+        "Frozen" => "Zmrazený",
+        "{} for {}" => "{} na {}",
+        "Crowd control resistance: next hold lasts {}%" => "Odolnosť voči znehybneniu: ďalšie trvá {} %",
+        "True damage" => "Čisté poškodenie",
+        "Hits go straight through armor and shields." => "Zásahy prejdú priamo cez brnenie aj štíty.",
+        "Took {} true damage" => "Utŕžené čisté poškodenie {}",
+        "{} {}% ({}% true)" => "{} {}% ({}% čisté)",
+        "Damage by source" => "Poškodenie podľa zdroja",
+        "Export CSV" => "Exportovať CSV",
+        "Telemetry" => "Telemetria",
+        "Send anonymous statistics" => "Posielať anonymné štatistiky",
+        "Sends the wave reached, the run's length and the final build of every finished run. Nothing identifies you." => "Posiela dosiahnutú vlnu, dĺžku hry a konečnú zostavu každej dohranej hry. Nič vás neidentifikuje.",
+        "Reports waiting to be sent: {}" => "Hlásenia čakajúce na odoslanie: {}",
+        "Telemetry unavailable: {}" => "Telemetria nedostupná: {}",
+        "Exported to {}" => "Exportované do {}",
+        "No damage dealt yet." => "Zatiaľ žiadne udelené poškodenie.",
+        "Meteor" => "Meteor",
+        "Scripts" => "Skripty",
+        "Max HP" => "Max. HP",
+        "Block" => "Blok",
+        "Dodge chance" => "Šanca na úhyb",
+        "Life steal" => "Vysávanie života",
+        "Thorns" => "Tŕne",
+        "Gold bonus" => "Bonus zlata",
+        "Buffs" => "Posilnenia",
+        "Upgrade {}" => "Vylepšenie {}",
+        "{} base" => "{} základ",
+        "Defense" => "Obrana",
+        "None unlocked yet." => "Zatiaľ žiadne odomknuté.",
+        "Time to kill" => "Čas na zabitie",
+        "Shots in range" => "Výstrely v dosahu",
+        "Enemy HP" => "HP nepriateľa",
+        "Enemies get through" => "Nepriatelia prejdú",
+        "Yes" => "Áno",
+        "No" => "Nie",
+        "HP lost per minute" => "Stratené HP za minútu",
+        "Toggle the profiler" => "Prepnúť profiler",
+        "Profiler" => "Profiler",
+        "Frame" => "Snímka",
+        "Simulation" => "Simulácia",
+        "UI" => "UI",
+        "Enemies: {}" => "Nepriatelia: {}",
+        "Enemy cap:" => "Limit nepriateľov:",
+        "Enemies on the field at most. Bosses still join past it, other spawns overflow." => "Najviac nepriateľov na poli. Bossovia prídu aj nad limit, ostatní pretečú.",
+        "Overflow:" => "Pretečenie:",
+        "Queue" => "Fronta",
+        "Merge" => "Zlúčiť",
+        "Queued: {}" => "Vo fronte: {}",
+        "Pattern:" => "Vzor:",
+        "Steady" => "Rovnomerný",
+        "Burst" => "Dávka",
+        "Accelerating" => "Zrýchľujúci",
+        "Jitter" => "Rozptyl",
+        "Spawns per minute" => "Nepriatelia za minútu",
+        "Spawns size enemies at once every period seconds." => "Po každej perióde príde naraz daný počet nepriateľov.",
+        "size" => "počet",
+        "period" => "perióda",
+        "Every gap is shorter by accel than the one before, the stream starts over once it drops below floor of the interval." => "Každá medzera je o zrýchlenie kratšia než predošlá, prúd začne odznova, keď klesne pod spodok intervalu.",
+        "accel" => "zrýchl.",
+        "floor" => "spodok",
+        "Gaps are rolled within the interval ± spread of it." => "Medzery sa náhodne volia v rozsahu interval ± rozptyl z neho.",
+        "spread" => "rozptyl",
+        "Spawner {}" => "Generátor {}",
+        "Remove" => "Odstrániť",
+        "Add spawner" => "Pridať generátor",
+        "Adds a spawner with the main one's stats, on its own timer." => "Pridá generátor so štatistikami hlavného, s vlastným časovačom.",
+        "Enemy kind:" => "Druh nepriateľa:",
+        "By wave" => "Podľa vlny",
+        "Preset:" => "Predvoľba:",
+        "Loadouts" => "Výbavy",
+        "Saves both attacks under this name, replacing a loadout of the same name." => "Uloží oba útoky pod týmto názvom, výbavu s rovnakým názvom nahradí.",
+        "Loadout: {}" => "Výbava: {}",
+        "{} (modified)" => "{} (upravená)",
+        "Custom" => "Vlastná",
+        "Undo" => "Späť",
+        "Redo" => "Znova",
+        "Reverts the last change to the attacks or spawners (Ctrl+Z)." => "Vráti poslednú zmenu útokov alebo generátorov (Ctrl+Z).",
+        "Brings back the last undone change (Ctrl+Shift+Z)." => "Obnoví poslednú vrátenú zmenu (Ctrl+Shift+Z).",
+        "Survival" => "Prežitie",
+        "Sandbox" => "Pieskovisko",
+        "Attacks improve only through upgrades bought with gold, the run counts towards the records." => "Útoky sa zlepšujú len vylepšeniami za zlato, hra sa počíta do rekordov.",
+        "Every slider is free to change, the run doesn't count towards records or leaderboards." => "Všetky posuvníky sa dajú voľne meniť, hra sa nepočíta do rekordov ani rebríčkov.",
+        "Next mode:" => "Ďalší režim:",
+        "The spawners can only be changed in sandbox." => "Generátory sa dajú meniť len v pieskovisku.",
+        "+15% damage" => "+15 % poškodenia",
+        "+3 range" => "+3 dosah",
+        "-10% cooldown" => "-10 % čakania",
+        "+1 target" => "+1 cieľ",
+        "{} ({} gold)" => "{} ({} zlata)",
+        "Clear wave {}" => "Vyčisti vlnu {}",
+        "Survive for {}" => "Prežij {}",
+        "Victory" => "Víťazstvo",
+        "Victorious" => "Víťazný",
+        "Goal reached: {}" => "Cieľ splnený: {}",
+        "Goal: {}" => "Cieľ: {}",
+        "Next stage" => "Ďalšia etapa",
+        "Campaign" => "Kampaň",
+        "Campaign stage: {}" => "Etapa kampane: {}",
+        "The campaign has no stages." => "Kampaň nemá žiadne etapy.",
+        "Stage" => "Etapa",
+        "Goal" => "Cieľ",
+        "Stars" => "Hviezdy",
+        "Fastest" => "Najrýchlejšie",
+        "Win the stage before to unlock it." => "Vyhraj predchádzajúcu etapu, aby sa odomkla.",
+        "Kill the final boss" => "Zabi posledného bossa",
+        "Endless" => "Nekonečná",
+        "Survive" => "Prežiť",
+        "Clear wave" => "Vyčistiť vlnu",
+        " min" => " min",
+        "Win condition:" => "Podmienka víťazstva:",
+        "Victories" => "Víťazstvá",
+        "Laurels" => "Vavríny",
+        "+{} laurels" => "+{} vavrínov",
+        "Durations" => "Trvanie",
+        "Seconds (1.5s)" => "Sekundy (1.5s)",
+        "Clock (m:ss)" => "Hodiny (m:ss)",
+        "Frames (90f)" => "Snímky (90f)",
+        "When unfocused:" => "Keď okno nie je aktívne:",
+        "Uncapped" => "Neobmedzené",
+        "Low power when minimized" => "Úsporný režim po minimalizovaní",
+        "Simulates only once a second while minimized, so idling in the background barely uses the CPU. Runs outside Survival also take coarser steps then, which plays their seed out differently." => "Po minimalizovaní simuluje len raz za sekundu, takže hra na pozadí takmer nezaťažuje procesor. Behy mimo Prežitia vtedy aj robia hrubšie kroky, takže ich seed sa odohrá inak.",
+        "Performance" => "Výkon",
+        "Frame rate:" => "Snímková frekvencia:",
+        "Idle-friendly" => "Šetriť pri nečinnosti",
+        "Repaints only a few times per second while paused or after the run ended." => "Prekresľuje len niekoľkokrát za sekundu počas pauzy alebo po skončení hry.",
+        "Keep simulating" => "Pokračovať v simulácii",
+        "Saves all spawners under this name, replacing a preset of the same name." => "Uloží všetky generátory pod týmto názvom, predvoľbu s rovnakým názvom nahradí.",
+        "Damage left over from a kill hits the next enemy in range, even past the max targets." => "Poškodenie, ktoré zostane po zabití, zasiahne ďalšieho nepriateľa v dosahu, aj nad najvyšší počet cieľov.",
+        "Fraction of the damage taken that's dealt back to the enemy that reached the Excellency, before its armor and shield. Killing it pays its bounty." => "Podiel utŕženého poškodenia, ktorý sa vráti nepriateľovi, čo sa dostal k Excelencii, pred jeho brnením a štítom. Jeho zabitie vyplatí jeho odmenu.",
+        "Most enemies at once: {}" => "Najviac nepriateľov naraz: {}",
+        "Bosses slain: {}" => "Porazení bossovia: {}",
+        "Archetype" => "Typ",
+        "Spawned" => "Vytvorení",
+        "Killed" => "Zabití",
+        "Unknown" => "Neznámy",
+        "Put .rhai scripts in {} to add abilities." => "Pridajte schopnosti ako .rhai skripty do {}.",
+        "Not loaded: {}" => "Nenačítané: {}",
+        "Fix the scripts and restart the game to load them." => "Opravte skripty a reštartujte hru, aby sa načítali.",
+        "Fix the scripts, saved scripts reload automatically." => "Opravte skripty, uložené skripty sa načítajú automaticky.",
+        "Some content couldn't be loaded, the built-in version is used instead: {}" => "Časť obsahu sa nepodarilo načítať, používa sa vstavaná verzia: {}",
+        "The D-pad moves between widgets, South presses and East closes." => "Smerový ovládač presúva medzi prvkami, South stláča a East zatvára.",
+        "South (A)" => "South (A)",
+        "East (B)" => "East (B)",
+        "North (Y)" => "North (Y)",
+        "West (X)" => "West (X)",
+        "Left bumper" => "Ľavé tlačidlo",
+        "Right bumper" => "Pravé tlačidlo",
+        "Left trigger" => "Ľavá spúšť",
+        "Right trigger" => "Pravá spúšť",
+        "Select" => "Select",
+        "Start" => "Start",
+        "D-pad up" => "Smerový ovládač hore",
+        "D-pad down" => "Smerový ovládač dole",
+        "D-pad left" => "Smerový ovládač vľavo",
+        "D-pad right" => "Smerový ovládač vpravo",
+        "Refresh" => "Obnoviť",
+        "Set a leaderboard server in the settings." => "Nastavte server rebríčka v nastaveniach.",
+        "Leaderboard unavailable: {}" => "Rebríček nie je dostupný: {}",
+        "not available in the browser" => "nie je dostupný v prehliadači",
+        "Wave {} - {} left" => "Vlna {} - zostáva {}",
+        "Wave {} in {}" => "Vlna {} o {}",
+        "Wave" => "Vlna",
+        "Damage dealt" => "Udelené poškodenie",
+        "Damage taken" => "Prijaté poškodenie",
+        "Graphs" => "Grafy",
+        "Elapsed (s)" => "Uplynulo (s)",
+        "Excellency HP" => "HP Excelencie",
+        "Total enemy HP" => "Celkové HP nepriateľov",
+        "Scaled now" => "Aktuálne so škálovaním",
+        "Time to arrive" => "Čas do príchodu",
+        "Elites per minute" => "Elít za minútu",
+        "Healers per minute" => "Liečiteľov za minútu",
+        "Damage an enemy deals to the Excellency when it arrives, before scaling." => "Poškodenie, ktoré nepriateľ po príchode spôsobí Excelencii, pred škálovaním.",
+        "HP of a freshly spawned enemy, before scaling. Healers get 70% of it." => "HP nového nepriateľa pred škálovaním. Liečitelia majú 70 % z neho.",
+        "Distance an enemy walks per second, before scaling. Healers walk at 60% of it." => "Vzdialenosť, ktorú nepriateľ prejde za sekundu, pred škálovaním. Liečitelia chodia 60 % z nej.",
+        "Chance that a spawned enemy is an elite with one or two modifiers." => "Šanca, že nový nepriateľ bude elita s jedným alebo dvoma modifikátormi.",
+        "Chance that a spawned enemy is a healer that mends the enemies around it." => "Šanca, že nový nepriateľ bude liečiteľ, ktorý lieči nepriateľov okolo seba.",
+        "After 10 minutes" => "Po 10 minútach",
+        "After 30 minutes" => "Po 30 minútach",
+        "Multiplier is 1 + rate × minutes." => "Násobok je 1 + tempo × minúty.",
+        "Multiplier is (1 + growth) ^ minutes." => "Násobok je (1 + rast) ^ minúty.",
+        "Multiplier is 1 + coef × minutes ^ exp." => "Násobok je 1 + koef × minúty ^ exp.",
+        "Effective DPS" => "Efektívne DPS",
+        "Attacks per second" => "Útokov za sekundu",
+        "Time until the attack fires again." => "Čas do ďalšieho výstrelu.",
+        "Damage dealt to each target, reduced by its armor and absorbed by its shield." => "Poškodenie každého cieľa, znížené jeho brnením a pohltené jeho štítom.",
+        "Only enemies at most this far away can be targeted." => "Zamerať sa dá len na nepriateľov najviac takto ďaleko.",
+        "Share of the lane" => "Podiel dráhy",
+        "How many enemies in range each attack hits." => "Koľko nepriateľov v dosahu každý útok zasiahne.",
+        "Enemies that reach the Excellency deal their damage to it." => "Nepriatelia, ktorí dorazia k Excelencii, jej spôsobia svoje poškodenie.",
+        "Damage on the field" => "Poškodenie na bojisku",
+        "How far the enemy still has to walk." => "Koľko ešte musí nepriateľ prejsť.",
+        "The enemy dies when its HP reaches zero." => "Nepriateľ zomrie, keď jeho HP klesne na nulu.",
+        "Basic attack hits to kill" => "Zásahov základným útokom na zabitie",
+        _ => return None,
+    })
+}
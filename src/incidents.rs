@@ -0,0 +1,100 @@
+//! Random incidents shaking up a run every minute or so: a meteor, a frenzy of spawns or a
+//! blessing. When they strike and which one it is comes from the run's RNG, so a seed
+//! replays the same incidents.
+
+use crate::combat::AttackOutcome;
+use crate::enemy::{remove_dead, Enemy};
+use crate::i18n::tr;
+use crate::rng::GameRng;
+use crate::timer::Timer;
+
+/// Seconds between incidents, rolled anew after each one.
+const MIN_INTERVAL: f32 = 45.;
+const MAX_INTERVAL: f32 = 90.;
+/// Dealt to every enemy on the field, armor and shields apply.
+pub const METEOR_DAMAGE: f32 = 15.;
+/// Seconds a frenzy lasts.
+pub const FRENZY_DURATION: f32 = 30.;
+/// Multiplier on the spawn rate during a frenzy.
+pub const FRENZY_SPAWN_RATE: f32 = 2.;
+/// Fraction of the Excellency's maximum HP a blessing heals.
+pub const BLESSING_HEAL: f32 = 0.3;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Incident {
+    Meteor,
+    Frenzy,
+    Blessing,
+}
+
+impl Incident {
+    pub const ALL: [Incident; 3] = [Incident::Meteor, Incident::Frenzy, Incident::Blessing];
+
+    /// Announces it to the player.
+    pub fn announcement(&self) -> &'static str {
+        tr(match self {
+            Incident::Meteor => "A meteor crashes into the enemies!",
+            Incident::Frenzy => "Frenzy! Enemies spawn twice as fast for 30s.",
+            Incident::Blessing => "A blessing heals the Excellency.",
+        })
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Incidents {
+    /// Counts down to the next incident.
+    pub timer: Timer,
+    /// Seconds left of the running frenzy.
+    pub frenzy: f32,
+}
+
+impl Default for Incidents {
+    fn default() -> Self {
+        Self {
+            timer: Timer::new(MAX_INTERVAL),
+            frenzy: 0.,
+        }
+    }
+}
+
+impl Incidents {
+    /// Returns the incident striking within `delta`, if any.
+    pub fn tick(&mut self, delta: f32, rng: &mut GameRng) -> Option<Incident> {
+        self.frenzy = (self.frenzy - delta).max(0.);
+        if self.timer.tick(delta) == 0 {
+            return None;
+        }
+        self.timer = Timer::new(MIN_INTERVAL + rng.next_f32() * (MAX_INTERVAL - MIN_INTERVAL));
+        let incident = Incident::ALL[rng.next_index(Incident::ALL.len())];
+        if incident == Incident::Frenzy {
+            self.frenzy = FRENZY_DURATION;
+        }
+        Some(incident)
+    }
+
+    /// Multiplier on the spawn rate right now.
+    pub fn spawn_rate(&self) -> f32 {
+        match self.frenzy > 0. {
+            true => FRENZY_SPAWN_RATE,
+            false => 1.,
+        }
+    }
+}
+
+/// Hits every enemy on the field with `METEOR_DAMAGE` and removes the ones that died.
+pub fn meteor(enemies: &mut Vec<Enemy>) -> AttackOutcome {
+    let mut damage_dealt = 0.;
+    let mut hits = vec![];
+    for enemy in enemies.iter_mut() {
+        damage_dealt += enemy.take_damage(METEOR_DAMAGE);
+        hits.push(enemy.distance.0);
+    }
+    let killed = remove_dead(enemies);
+    AttackOutcome {
+        killed,
+        damage_dealt,
+        true_damage: 0.,
+        hits,
+    }
+}
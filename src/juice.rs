@@ -0,0 +1,108 @@
+//! Feedback on heavy hits taken: the battlefield shakes and the HP bar flashes. The
+//! ultimate and meteors shake it too. Purely visual and driven by frame time, like the particles.
+
+use egui::{Color32, Rgba, Vec2};
+
+use crate::events::GameEvent;
+use crate::i18n::tr;
+use crate::incidents::Incident;
+use crate::rng::GameRng;
+
+/// Hits taking at least this fraction of the maximum HP count as heavy.
+const HEAVY_HIT: f32 = 0.05;
+/// Points the battlefield moves at full trauma and intensity.
+const MAX_SHAKE: f32 = 12.;
+/// Trauma lost per second, a full shake settles in this long.
+const TRAUMA_DECAY: f32 = 1.5;
+const FLASH_SECONDS: f32 = 0.3;
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct JuiceSettings {
+    /// Multiplier on how far the battlefield shakes, in `0..=2`.
+    pub shake_intensity: f32,
+    pub flash: bool,
+}
+
+impl Default for JuiceSettings {
+    fn default() -> Self {
+        Self {
+            shake_intensity: 1.,
+            flash: true,
+        }
+    }
+}
+
+impl JuiceSettings {
+    pub fn show_editor(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Screen shake:"));
+            // Reduced motion turns it off whatever is set here
+            ui.add(
+                egui::Slider::new(&mut self.shake_intensity, 0.0..=2.)
+                    .custom_formatter(|intensity, _| format!("{:.0}%", intensity * 100.)),
+            );
+        });
+        ui.checkbox(&mut self.flash, tr("Flash the HP bar on heavy hits"));
+    }
+}
+
+pub struct Juice {
+    /// In `0..=1`, the shake grows with its square so small hits barely register.
+    trauma: f32,
+    /// In `0..=1`, fades out over `FLASH_SECONDS`.
+    flash: f32,
+    /// Its own generator, shaking mustn't move the run's.
+    rng: GameRng,
+}
+
+impl Default for Juice {
+    fn default() -> Self {
+        Self {
+            trauma: 0.,
+            flash: 0.,
+            rng: GameRng::new(0),
+        }
+    }
+}
+
+impl Juice {
+    /// Reacts to heavy hits and the ultimate, `maximum_hp` is the Excellency's.
+    pub fn record(&mut self, event: &GameEvent, maximum_hp: f32) {
+        match event {
+            GameEvent::PlayerHit { damage, .. } => {
+                let fraction = damage / maximum_hp.max(1.);
+                if fraction >= HEAVY_HIT {
+                    self.trauma = (self.trauma + fraction * 4.).min(1.);
+                    self.flash = 1.;
+                }
+            }
+            GameEvent::UltimateFired { .. } => self.trauma = 1.,
+            GameEvent::IncidentStruck(Incident::Meteor) => self.trauma = self.trauma.max(0.6),
+            _ => {}
+        }
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        self.trauma = (self.trauma - TRAUMA_DECAY * delta).max(0.);
+        self.flash = (self.flash - delta / FLASH_SECONDS).max(0.);
+    }
+
+    /// How far to move the battlefield this frame, nothing with reduced motion.
+    pub fn shake(&mut self, settings: &JuiceSettings, reduced_motion: bool) -> Vec2 {
+        if reduced_motion || self.trauma <= 0. {
+            return Vec2::ZERO;
+        }
+        let amplitude = MAX_SHAKE * settings.shake_intensity * self.trauma * self.trauma;
+        let angle = self.rng.next_f32() * std::f32::consts::TAU;
+        Vec2::angled(angle) * amplitude
+    }
+
+    /// `color` brightened by the current flash.
+    pub fn flash(&self, settings: &JuiceSettings, color: Color32) -> Color32 {
+        if !settings.flash || self.flash <= 0. {
+            return color;
+        }
+        egui::lerp(Rgba::from(color)..=Rgba::WHITE, self.flash).into()
+    }
+}
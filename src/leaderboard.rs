@@ -0,0 +1,157 @@
+//! Optional online leaderboard, compiled only with the `leaderboard` feature.
+//!
+//! Requests go through `http` in the background, being offline only shows up as a status
+//! message.
+
+use std::sync::mpsc::Receiver;
+
+use crate::durations;
+use crate::http;
+use crate::i18n::{tr, trf};
+
+/// How many entries the global table asks for.
+const TOP_ENTRIES: usize = 100;
+
+/// A finished run as sent to the server.
+#[derive(serde::Serialize)]
+pub struct Submission {
+    pub player: String,
+    pub seed: u64,
+    pub wave: u32,
+    /// Seconds.
+    pub duration: f32,
+    pub checksum: u64,
+}
+
+impl Submission {
+    pub fn new(player: String, seed: u64, wave: u32, duration: f32) -> Self {
+        let checksum = checksum(&player, seed, wave, duration);
+        Self {
+            player,
+            seed,
+            wave,
+            duration,
+            checksum,
+        }
+    }
+}
+
+/// FNV-1a over the submitted fields, catches accidental corruption and casual edits.
+fn checksum(player: &str, seed: u64, wave: u32, duration: f32) -> u64 {
+    let bytes = player
+        .bytes()
+        .chain(seed.to_le_bytes())
+        .chain(wave.to_le_bytes())
+        .chain(duration.to_bits().to_le_bytes());
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// A row of the global table.
+#[derive(serde::Deserialize)]
+pub struct Entry {
+    pub player: String,
+    pub seed: u64,
+    pub wave: u32,
+    pub duration: f32,
+}
+
+enum Status {
+    Idle,
+    Waiting,
+    Failed(String),
+}
+
+#[derive(Default)]
+pub struct Leaderboard {
+    top: Vec<Entry>,
+    status: Option<Status>,
+    fetch: Option<Receiver<Result<Vec<Entry>, String>>>,
+    submit: Option<Receiver<Result<(), String>>>,
+}
+
+impl Leaderboard {
+    /// Sends a run in the background, does nothing without a configured endpoint.
+    pub fn submit(&mut self, url: &str, submission: Submission) {
+        if url.is_empty() {
+            return;
+        }
+        let url = format!("{}/runs", url.trim_end_matches('/'));
+        self.submit = Some(http::spawn(move || http::post(&url, &submission)));
+    }
+
+    pub fn refresh(&mut self, url: &str) {
+        let url = format!("{}/top?limit={TOP_ENTRIES}", url.trim_end_matches('/'));
+        self.fetch = Some(http::spawn(move || http::get(&url)));
+        self.status = Some(Status::Waiting);
+    }
+
+    /// Picks up finished requests, call once per frame.
+    pub fn poll(&mut self) {
+        if let Some(Ok(result)) = self.fetch.as_ref().map(Receiver::try_recv) {
+            self.fetch = None;
+            self.status = Some(match result {
+                Ok(top) => {
+                    self.top = top;
+                    Status::Idle
+                }
+                Err(err) => Status::Failed(err),
+            });
+        }
+        if let Some(Ok(result)) = self.submit.as_ref().map(Receiver::try_recv) {
+            self.submit = None;
+            if let Err(err) = result {
+                log::warn!("Failed to submit the run: {err}");
+                self.status = Some(Status::Failed(err));
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, url: &str) {
+        ui.horizontal(|ui| {
+            let can_refresh = !url.is_empty() && self.fetch.is_none();
+            if ui
+                .add_enabled(can_refresh, egui::Button::new(tr("Refresh")))
+                .clicked()
+            {
+                self.refresh(url);
+            }
+            match &self.status {
+                _ if url.is_empty() => {
+                    ui.label(tr("Set a leaderboard server in the settings."));
+                }
+                Some(Status::Waiting) => {
+                    ui.spinner();
+                }
+                Some(Status::Failed(err)) => {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        trf("Leaderboard unavailable: {}", std::slice::from_ref(err)),
+                    );
+                }
+                Some(Status::Idle) | None => {}
+            }
+        });
+        if self.status.is_none() && !url.is_empty() {
+            self.refresh(url);
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("leaderboard").striped(true).show(ui, |ui| {
+                for header in ["#", tr("Player"), tr("Wave"), tr("Duration"), tr("Seed")] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+                for (rank, entry) in self.top.iter().enumerate() {
+                    ui.label((rank + 1).to_string());
+                    ui.label(&entry.player);
+                    ui.label(entry.wave.to_string());
+                    ui.label(durations::format(entry.duration));
+                    ui.label(entry.seed.to_string());
+                    ui.end_row();
+                }
+            });
+        });
+    }
+}
@@ -1,4 +1,72 @@
+//! The simulation behind the game and the eframe app around it.
+//!
+//! `GameState` is both: `step` advances the run without any UI, which is what tests and
+//! benches drive, and it implements `eframe::App` for the binary. The types a run is made
+//! of live in the public modules below.
+
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
-pub use app::GameState;
+pub mod ascendancy;
+mod audio;
+pub mod auras;
+pub mod automation;
+pub mod boss_phases;
+pub mod character_sheet;
+pub mod charge;
+pub mod combat;
+mod combat_log;
+pub mod content;
+pub mod crowd_control;
+pub mod daily;
+#[cfg(feature = "discord")]
+mod discord;
+pub mod durations;
+mod dying;
+pub mod effects;
+pub mod enemy;
+pub mod events;
+pub mod excellency;
+pub mod export;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+mod graphs;
+#[cfg(any(feature = "leaderboard", feature = "telemetry"))]
+mod http;
+mod i18n;
+pub mod incidents;
+mod juice;
+#[cfg(feature = "leaderboard")]
+mod leaderboard;
+pub mod loadouts;
+pub mod modes;
+pub mod mutators;
+pub mod names;
+pub mod new_game_plus;
+mod particles;
+mod profiler;
+pub mod profiles;
+mod records;
+pub mod relics;
+pub mod rng;
+pub mod saves;
+mod settings;
+pub mod snapshot;
+pub mod spawner;
+pub mod stat_sheet;
+pub mod talents;
+#[cfg(feature = "telemetry")]
+mod telemetry;
+mod theme;
+pub mod timer;
+mod toasts;
+pub mod treasure;
+mod ui;
+pub mod ultimate;
+pub mod undo;
+pub mod upgrades;
+pub mod victory;
+pub mod waves;
+mod widgets;
+pub mod world;
+pub use app::{GamePlugin, GameState, SaveError, SAVE_VERSION};
@@ -0,0 +1,73 @@
+//! Named attack builds the player can save and swap between, even in the middle of a run.
+//! They're kept with the settings, so they carry over to every run.
+
+use crate::combat::{BasicAttack, CastMode, DamageType, TargetingStrategy};
+use crate::excellency::Excellency;
+
+/// Everything about one attack a loadout sets, leaving its progress through the cooldown alone.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct AttackSetup {
+    pub damage: f32,
+    pub range: f32,
+    pub cooldown: f32,
+    pub max_targets: usize,
+    pub targeting: TargetingStrategy,
+    #[serde(default)]
+    pub overkill_carry: bool,
+    #[serde(default)]
+    pub wind_up: f32,
+    #[serde(default)]
+    pub cast_mode: CastMode,
+    #[serde(default)]
+    pub damage_type: DamageType,
+}
+
+impl AttackSetup {
+    pub fn capture(attack: &BasicAttack) -> Self {
+        Self {
+            damage: attack.damage,
+            range: attack.range,
+            cooldown: attack.cooldown_timer.total,
+            max_targets: attack.max_targets,
+            targeting: attack.targeting,
+            overkill_carry: attack.overkill_carry,
+            wind_up: attack.wind_up,
+            cast_mode: attack.cast_mode,
+            damage_type: attack.damage_type,
+        }
+    }
+
+    /// A cooldown already running keeps going, cut short if the new one is shorter.
+    pub fn apply(&self, attack: &mut BasicAttack) {
+        attack.damage = self.damage;
+        attack.range = self.range;
+        attack.cooldown_timer.total = self.cooldown;
+        attack.cooldown_timer.remaining = attack.cooldown_timer.remaining.min(self.cooldown);
+        attack.max_targets = self.max_targets;
+        attack.targeting = self.targeting;
+        attack.overkill_carry = self.overkill_carry;
+        attack.wind_up = self.wind_up;
+        attack.cast_mode = self.cast_mode;
+        attack.damage_type = self.damage_type;
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct Loadout {
+    pub basic: AttackSetup,
+    pub big: AttackSetup,
+}
+
+impl Loadout {
+    pub fn capture(excellency: &Excellency) -> Self {
+        Self {
+            basic: AttackSetup::capture(&excellency.basic_attack),
+            big: AttackSetup::capture(&excellency.big_attack),
+        }
+    }
+
+    pub fn apply(&self, excellency: &mut Excellency) {
+        self.basic.apply(&mut excellency.basic_attack);
+        self.big.apply(&mut excellency.big_attack);
+    }
+}
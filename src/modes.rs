@@ -0,0 +1,54 @@
+//! How a run is played. Survival is the game proper: the attacks only get better through
+//! gold and the run counts towards the records. Sandbox frees every slider for testing and
+//! experimenting, and stays out of the records and leaderboards.
+
+use egui::Color32;
+
+use crate::i18n::tr;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum GameMode {
+    #[default]
+    Survival,
+    Sandbox,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 2] = [GameMode::Survival, GameMode::Sandbox];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            GameMode::Survival => "Survival",
+            GameMode::Sandbox => "Sandbox",
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        tr(match self {
+            GameMode::Survival => "Attacks improve only through upgrades bought with gold, the run counts towards the records.",
+            GameMode::Sandbox => "Every slider is free to change, the run doesn't count towards records or leaderboards.",
+        })
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            GameMode::Survival => Color32::GOLD,
+            GameMode::Sandbox => Color32::LIGHT_BLUE,
+        }
+    }
+
+    /// Whether the attack and spawner sliders can be changed freely.
+    pub fn free_sliders(&self) -> bool {
+        *self == GameMode::Sandbox
+    }
+
+    /// Whether finished runs count towards the records and leaderboards.
+    pub fn tracks_records(&self) -> bool {
+        *self == GameMode::Survival
+    }
+}
+
+/// Saves from before there were modes were played with free sliders.
+pub fn before_modes() -> GameMode {
+    GameMode::Sandbox
+}
@@ -0,0 +1,86 @@
+//! Rule changes picked when a run starts, applied once to the fresh state and then kept
+//! with the run so it's clear what it was played under. The ones making a run harder pay
+//! for it with extra gold from every bounty.
+
+use crate::app::GameState;
+use crate::combat::AttackKind;
+use crate::i18n::tr;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mutator {
+    /// Enemies walk 30% faster.
+    FastEnemies,
+    /// Enemies have twice the HP.
+    ToughEnemies,
+    /// Enemies spawn 50% more often.
+    Swarm,
+    /// The Excellency starts with half its HP.
+    Fragile,
+    /// Bounties pay twice the gold.
+    DoubleGold,
+    /// The big attack never fires.
+    NoBigAttack,
+    /// Both attacks reach half as far.
+    HalfRange,
+}
+
+impl Mutator {
+    pub const ALL: [Mutator; 7] = [
+        Mutator::FastEnemies,
+        Mutator::ToughEnemies,
+        Mutator::Swarm,
+        Mutator::Fragile,
+        Mutator::DoubleGold,
+        Mutator::NoBigAttack,
+        Mutator::HalfRange,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Mutator::FastEnemies => "Enemies 30% faster",
+            Mutator::ToughEnemies => "Enemy HP doubled",
+            Mutator::Swarm => "Enemies spawn 50% more often",
+            Mutator::Fragile => "Excellency HP halved",
+            Mutator::DoubleGold => "Gold doubled",
+            Mutator::NoBigAttack => "No big attack",
+            Mutator::HalfRange => "Attack range halved",
+        })
+    }
+
+    /// Extra fraction of gold every bounty pays for playing under it.
+    pub fn reward(&self) -> f32 {
+        match self {
+            Mutator::FastEnemies | Mutator::Swarm => 0.25,
+            Mutator::ToughEnemies | Mutator::Fragile | Mutator::HalfRange => 0.5,
+            Mutator::NoBigAttack => 0.75,
+            Mutator::DoubleGold => 0.,
+        }
+    }
+
+    /// Whether `kind` never fires under it.
+    pub fn disables(&self, kind: AttackKind) -> bool {
+        *self == Mutator::NoBigAttack && kind == AttackKind::Big
+    }
+
+    /// Changes a run that's about to start.
+    pub fn apply(&self, state: &mut GameState) {
+        let spawner = &mut state.enemy_spawner;
+        match self {
+            Mutator::FastEnemies => spawner.speed *= 1.3,
+            Mutator::ToughEnemies => spawner.maximum_hp *= 2.,
+            Mutator::Swarm => spawner.interval /= 1.5,
+            Mutator::Fragile => {
+                let hp = &mut state.excellency.hp;
+                hp.maximum /= 2.;
+                hp.reset();
+            }
+            Mutator::DoubleGold => state.excellency.gold_bonus += 1.,
+            Mutator::NoBigAttack => {}
+            Mutator::HalfRange => {
+                state.excellency.basic_attack.range /= 2.;
+                state.excellency.big_attack.range /= 2.;
+            }
+        }
+        state.excellency.gold_bonus += self.reward();
+    }
+}
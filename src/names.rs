@@ -0,0 +1,83 @@
+//! Flavor names for enemies, like "Grizzled Marauder" or "Swift Gnawer", so the enemy list
+//! and the combat log read as more than a row of stats.
+//!
+//! Regular enemies get an epithet and a noun from word lists, the epithet matching an elite
+//! modifier when they have one. Bosses get a proper name strung together from syllables.
+//! Names aren't translated, they're made up either way.
+
+use crate::enemy::{EliteModifier, Enemy, EnemyKind};
+use crate::rng::GameRng;
+
+const EPITHETS: [&str; 12] = [
+    "Grizzled", "Mangy", "Rabid", "Gaunt", "Hulking", "Scarred", "Sullen", "Wretched", "Feral",
+    "Hollow", "Crooked", "Restless",
+];
+const GRUNTS: [&str; 10] = [
+    "Marauder",
+    "Gnawer",
+    "Brute",
+    "Raider",
+    "Cutthroat",
+    "Ravager",
+    "Skulker",
+    "Looter",
+    "Bandit",
+    "Thug",
+];
+const HEALERS: [&str; 5] = ["Mender", "Hedge Witch", "Bonesetter", "Herbalist", "Shaman"];
+const CHESTS: [&str; 4] = ["Coffer", "Strongbox", "Chest", "Hoard"];
+const CHEST_EPITHETS: [&str; 4] = ["Gilded", "Skittish", "Jangling", "Bulging"];
+const SYLLABLES: [&str; 12] = [
+    "gor", "mak", "thra", "vul", "esh", "krag", "nor", "zil", "dum", "rak", "ash", "ul",
+];
+const TITLES: [&str; 6] = [
+    "the Dreaded",
+    "the Unbowed",
+    "the Devourer",
+    "Oathbreaker",
+    "the Pale",
+    "Ironjaw",
+];
+
+fn epithets(modifier: EliteModifier) -> &'static [&'static str] {
+    match modifier {
+        EliteModifier::Fast => &["Swift", "Fleet", "Darting"],
+        EliteModifier::Armored => &["Ironclad", "Plated", "Stonehide"],
+        EliteModifier::Regenerating => &["Undying", "Festering", "Knitting"],
+        EliteModifier::Shielded => &["Warded", "Shrouded", "Glimmering"],
+        EliteModifier::Piercing => &["Barbed", "Vicious", "Keen"],
+    }
+}
+
+fn pick<'a>(words: &[&'a str], rng: &mut GameRng) -> &'a str {
+    words[rng.next_index(words.len())]
+}
+
+/// A name fitting `enemy`, the same for the same `rng`.
+pub fn generate(enemy: &Enemy, rng: &mut GameRng) -> String {
+    if enemy.is_boss {
+        let mut name = String::new();
+        for _ in 0..2 + rng.next_index(2) {
+            name.push_str(pick(&SYLLABLES, rng));
+        }
+        let mut letters = name.chars();
+        let first = letters.next().map(|first| first.to_ascii_uppercase());
+        return format!(
+            "{}{} {}",
+            first.unwrap_or_default(),
+            letters.as_str(),
+            pick(&TITLES, rng)
+        );
+    }
+    let epithet = match (enemy.kind, enemy.modifiers.first()) {
+        (EnemyKind::Chest, _) => pick(&CHEST_EPITHETS, rng),
+        (_, Some(&modifier)) => pick(epithets(modifier), rng),
+        (_, None) => pick(&EPITHETS, rng),
+    };
+    let noun = match enemy.kind {
+        EnemyKind::Grunt => pick(&GRUNTS, rng),
+        EnemyKind::Healer => pick(&HEALERS, rng),
+        EnemyKind::Chest => pick(&CHESTS, rng),
+    };
+    format!("{epithet} {noun}")
+}
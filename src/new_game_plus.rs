@@ -0,0 +1,22 @@
+//! New Game+: once the milestone boss falls, the run can start over one cycle deeper. The
+//! Excellency keeps its auras and talents, enemies get tougher with every cycle.
+
+use crate::content::Content;
+use crate::spawner::EnemySpawner;
+
+/// Multiplier on enemy HP per cycle.
+pub const HP_SCALING: f32 = 1.5;
+/// Multiplier on enemy damage per cycle.
+pub const DAMAGE_SCALING: f32 = 1.25;
+
+/// The wave of the last boss in `content`, killing it unlocks the next cycle.
+pub fn milestone_wave(content: &Content) -> Option<u32> {
+    content.bosses.iter().map(|boss| boss.wave).max()
+}
+
+/// Makes a fresh spawner's enemies as tough as cycle `depth` calls for.
+pub fn scale(spawner: &mut EnemySpawner, depth: u32) {
+    let depth = depth as i32;
+    spawner.maximum_hp *= HP_SCALING.powi(depth);
+    spawner.damage *= DAMAGE_SCALING.powi(depth);
+}
@@ -0,0 +1,144 @@
+//! Sparks, explosions and blood over the battlefield, spawned from game events and moved by
+//! frame time. Purely visual, they draw from their own generator so a run's seed plays out
+//! the same with or without them.
+
+use egui::{Color32, Pos2, Vec2};
+
+use crate::combat::AttackKind;
+use crate::events::GameEvent;
+use crate::rng::GameRng;
+
+/// Older particles are dropped past this, swarms hit by big attacks get busy.
+const MAX_PARTICLES: usize = 600;
+/// Points per second squared pulling particles back down.
+const GRAVITY: f32 = 240.;
+
+struct Particle {
+    /// Where along the lane it was spawned, in distance units like the enemies.
+    distance: f32,
+    /// From where it was spawned, in points with y pointing up.
+    offset: Vec2,
+    /// Points per second.
+    velocity: Vec2,
+    color: Color32,
+    size: f32,
+    age: f32,
+    lifetime: f32,
+}
+
+/// How a burst of particles looks.
+struct Burst {
+    count: usize,
+    color: Color32,
+    size: f32,
+    /// Highest speed, in points per second.
+    speed: f32,
+    lifetime: f32,
+}
+
+const SPARK: Burst = Burst {
+    count: 4,
+    color: Color32::from_rgb(255, 240, 170),
+    size: 1.5,
+    speed: 90.,
+    lifetime: 0.25,
+};
+const EXPLOSION: Burst = Burst {
+    count: 14,
+    color: Color32::from_rgb(255, 140, 30),
+    size: 2.5,
+    speed: 160.,
+    lifetime: 0.5,
+};
+const EMBERS: Burst = Burst {
+    count: 3,
+    color: Color32::from_rgb(255, 120, 40),
+    size: 1.5,
+    speed: 50.,
+    lifetime: 0.4,
+};
+const BLOOD: Burst = Burst {
+    count: 16,
+    color: Color32::from_rgb(170, 0, 0),
+    size: 2.,
+    speed: 120.,
+    lifetime: 0.7,
+};
+
+pub struct Particles {
+    particles: Vec<Particle>,
+    rng: GameRng,
+}
+
+impl Default for Particles {
+    fn default() -> Self {
+        Self {
+            particles: vec![],
+            rng: GameRng::new(0),
+        }
+    }
+}
+
+impl Particles {
+    /// Spawns the particles `event` makes, if any.
+    pub fn record(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::AttackFired { kind, hits } => {
+                let burst = match kind {
+                    AttackKind::Basic => &SPARK,
+                    AttackKind::Big => &EXPLOSION,
+                };
+                for &distance in hits {
+                    self.burst(burst, distance);
+                }
+            }
+            GameEvent::UltimateFired { hits } => {
+                for &distance in hits {
+                    self.burst(&EXPLOSION, distance);
+                }
+            }
+            GameEvent::DamageTicked { distance, .. } => self.burst(&EMBERS, *distance),
+            GameEvent::PlayerHit { .. } => self.burst(&BLOOD, 0.),
+            _ => {}
+        }
+        let excess = self.particles.len().saturating_sub(MAX_PARTICLES);
+        self.particles.drain(..excess);
+    }
+
+    fn burst(&mut self, burst: &Burst, distance: f32) {
+        for _ in 0..burst.count {
+            let angle = self.rng.next_f32() * std::f32::consts::TAU;
+            let speed = burst.speed * (0.3 + 0.7 * self.rng.next_f32());
+            self.particles.push(Particle {
+                distance,
+                offset: Vec2::ZERO,
+                velocity: Vec2::angled(angle) * speed,
+                color: burst.color,
+                size: burst.size,
+                age: 0.,
+                lifetime: burst.lifetime * (0.6 + 0.4 * self.rng.next_f32()),
+            });
+        }
+    }
+
+    /// Moves the particles by a frame's `delta` and drops the expired ones.
+    pub fn tick(&mut self, delta: f32) {
+        for particle in &mut self.particles {
+            particle.age += delta;
+            particle.velocity.y -= GRAVITY * delta;
+            particle.offset += particle.velocity * delta;
+        }
+        self.particles
+            .retain(|particle| particle.age < particle.lifetime);
+    }
+
+    /// Draws the particles, `position` maps a distance onto the battlefield.
+    pub fn paint(&self, painter: &egui::Painter, position: impl Fn(f32) -> Pos2) {
+        for particle in &self.particles {
+            let offset = egui::vec2(particle.offset.x, -particle.offset.y);
+            let center = position(particle.distance) + offset;
+            let alpha = 1. - particle.age / particle.lifetime;
+            painter.circle_filled(center, particle.size, particle.color.gamma_multiply(alpha));
+        }
+    }
+}
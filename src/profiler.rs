@@ -0,0 +1,98 @@
+//! Debug overlay with the last few seconds of frame timings, to spot performance regressions
+//! with crowded fields. Toggled by a hotkey, and measured even while hidden so the plot is
+//! already filled when it's opened.
+
+use std::collections::VecDeque;
+
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+
+use crate::i18n::{tr, trf};
+
+/// Four seconds at 60 frames per second.
+const CAPACITY: usize = 240;
+
+/// One frame, times in milliseconds.
+struct Sample {
+    frame: f32,
+    tick: f32,
+    ui: f32,
+    enemies: usize,
+}
+
+#[derive(Default)]
+pub struct Profiler {
+    pub visible: bool,
+    samples: VecDeque<Sample>,
+}
+
+impl Profiler {
+    /// `frame_time` in seconds, `tick` and `ui` as measured by the frame.
+    pub fn record(
+        &mut self,
+        frame_time: f32,
+        tick: std::time::Duration,
+        ui: std::time::Duration,
+        enemies: usize,
+    ) {
+        if self.samples.len() == CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample {
+            frame: frame_time * 1000.,
+            tick: tick.as_secs_f32() * 1000.,
+            ui: ui.as_secs_f32() * 1000.,
+            enemies,
+        });
+    }
+
+    pub fn show(&self, ctx: &egui::Context) {
+        if !self.visible {
+            return;
+        }
+        let Some(last) = self.samples.back() else {
+            return;
+        };
+        let average = |time: fn(&Sample) -> f32| {
+            self.samples.iter().map(time).sum::<f32>() / self.samples.len() as f32
+        };
+        egui::Window::new(tr("Profiler"))
+            .id(egui::Id::new("profiler_window"))
+            .anchor(egui::Align2::LEFT_TOP, [8., 8.])
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("profiler").show(ui, |ui| {
+                    for (name, time) in [
+                        (tr("Frame"), average(|sample| sample.frame)),
+                        (tr("Simulation"), average(|sample| sample.tick)),
+                        (tr("UI"), average(|sample| sample.ui)),
+                    ] {
+                        ui.label(name);
+                        ui.label(format!("{time:.2} ms"));
+                        ui.end_row();
+                    }
+                });
+                ui.label(trf("Enemies: {}", &[last.enemies.to_string()]));
+                let line = |name: &str, time: fn(&Sample) -> f32| {
+                    let points: PlotPoints = (self.samples.iter().enumerate())
+                        .map(|(index, sample)| [index as f64, time(sample) as f64])
+                        .collect();
+                    Line::new(points).name(name)
+                };
+                Plot::new("profiler_plot")
+                    .width(240.)
+                    .height(100.)
+                    .legend(Legend::default())
+                    .include_y(0.)
+                    .include_x(CAPACITY as f64)
+                    .show_axes([false, true])
+                    .allow_drag(false)
+                    .allow_scroll(false)
+                    .allow_zoom(false)
+                    .show(ui, |plot| {
+                        plot.line(line(tr("Frame"), |sample| sample.frame));
+                        plot.line(line(tr("Simulation"), |sample| sample.tick));
+                        plot.line(line(tr("UI"), |sample| sample.ui));
+                    });
+            });
+    }
+}
@@ -0,0 +1,85 @@
+//! Named profiles sharing one storage, e.g. for members of a household. Each keeps its own
+//! session, settings, records and save slots under keys namespaced by its id.
+
+use crate::ascendancy::ASCENDANCY_KEY;
+use crate::records::RECORDS_KEY;
+use crate::saves;
+use crate::settings::SETTINGS_KEY;
+
+pub const PROFILES_KEY: &str = "profiles";
+/// What every profile stores under its own keys, besides the save slots.
+pub const KEYS: [&str; 4] = [eframe::APP_KEY, SETTINGS_KEY, RECORDS_KEY, ASCENDANCY_KEY];
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Profiles {
+    pub list: Vec<Profile>,
+    /// Played last, loaded on startup.
+    pub current: u32,
+    next_id: u32,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            list: vec![Profile {
+                id: 0,
+                name: "Default".to_owned(),
+            }],
+            current: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl Profiles {
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        storage
+            .and_then(|storage| eframe::get_value(storage, PROFILES_KEY))
+            .unwrap_or_default()
+    }
+
+    /// Returns the id of the new profile.
+    pub fn add(&mut self, name: String) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.list.push(Profile { id, name });
+        id
+    }
+
+    /// Forgets profile `id` and everything it stored, the current one can't be removed.
+    pub fn remove(&mut self, storage: &mut dyn eframe::Storage, id: u32) {
+        if id == self.current {
+            return;
+        }
+        self.list.retain(|profile| profile.id != id);
+        for key in KEYS {
+            storage.set_string(&key_of(id, key), String::new());
+        }
+        for slot in 0..saves::SLOT_COUNT {
+            saves::clear(storage, id, slot);
+        }
+    }
+
+    pub fn name(&self, id: u32) -> &str {
+        self.list
+            .iter()
+            .find(|profile| profile.id == id)
+            .map_or("", |profile| &profile.name)
+    }
+}
+
+/// Where profile `id` stores `key`. The first profile uses the bare keys, so whatever was
+/// stored before profiles existed stays its.
+pub fn key_of(id: u32, key: &str) -> String {
+    match id {
+        0 => key.to_owned(),
+        id => format!("profile_{id}_{key}"),
+    }
+}
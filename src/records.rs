@@ -0,0 +1,269 @@
+//! Personal bests across all runs, persisted separately from the run like the settings.
+
+use std::collections::BTreeMap;
+
+use crate::automation::Automations;
+use crate::content::Stage;
+use crate::durations;
+use crate::i18n::tr;
+use crate::mutators::Mutator;
+use crate::relics::Relics;
+
+pub const RECORDS_KEY: &str = "records";
+
+/// Finished runs kept in the history, the oldest are dropped first.
+const HISTORY_LENGTH: usize = 100;
+
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+pub struct Records {
+    /// Kills summed over every finished run.
+    pub total_kills: u64,
+    pub most_kills: u32,
+    /// Seconds.
+    pub longest_survival: f32,
+    pub highest_wave: u32,
+    /// Most New Game+ cycles started in a row.
+    pub deepest_new_game_plus: u32,
+    /// Finished runs, oldest first.
+    pub history: Vec<RunRecord>,
+    /// Best finished run of each daily challenge played, by day.
+    pub dailies: BTreeMap<u64, DailyRecord>,
+    pub victories: u32,
+    /// Earned by victories, see `Victory::laurels`.
+    pub laurels: u32,
+    /// Best victory in each campaign stage won, by stage name.
+    pub stages: BTreeMap<String, StageRecord>,
+    /// Automations bought with laurels and whether they're switched on.
+    pub automations: Automations,
+    /// Every relic found, in every run.
+    pub relics: Relics,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct StageRecord {
+    pub stars: u32,
+    /// Seconds the quickest victory took.
+    pub fastest: f32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct DailyRecord {
+    pub wave: u32,
+    pub kills: u32,
+}
+
+/// One attack's stats at the end of a run.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct AttackSnapshot {
+    pub damage: f32,
+    pub range: f32,
+    pub max_targets: usize,
+    /// Seconds.
+    pub cooldown: f32,
+}
+
+/// A finished run in the history.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct RunRecord {
+    pub seed: u64,
+    /// Unix timestamp in seconds.
+    pub finished_at: u64,
+    pub wave: u32,
+    /// Seconds.
+    pub duration: f32,
+    pub kills: u32,
+    pub basic_attack: AttackSnapshot,
+    pub big_attack: AttackSnapshot,
+    #[serde(default)]
+    pub mutators: Vec<Mutator>,
+}
+
+impl RunRecord {
+    pub fn show_build(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("run_build").show(ui, |ui| {
+            for text in [
+                tr("Attack"),
+                tr("Damage"),
+                tr("Range"),
+                tr("Targets"),
+                tr("Cooldown"),
+            ] {
+                ui.strong(text);
+            }
+            ui.end_row();
+            for (name, attack) in [
+                (tr("Basic"), &self.basic_attack),
+                (tr("Big"), &self.big_attack),
+            ] {
+                ui.label(name);
+                ui.label(format!("{:.1}", attack.damage));
+                ui.label(format!("{:.1}", attack.range));
+                ui.label(attack.max_targets.to_string());
+                ui.label(durations::format(attack.cooldown));
+                ui.end_row();
+            }
+        });
+    }
+}
+
+/// Column the run history is ordered by, always best (or newest) first.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum HistorySort {
+    #[default]
+    Date,
+    Wave,
+    Duration,
+    Kills,
+}
+
+impl HistorySort {
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            HistorySort::Date => "Finished",
+            HistorySort::Wave => "Wave",
+            HistorySort::Duration => "Duration",
+            HistorySort::Kills => "Kills",
+        })
+    }
+
+    /// Indices into `history` in display order.
+    pub fn order(&self, history: &[RunRecord]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..history.len()).collect();
+        let key = |index: usize| {
+            let run = &history[index];
+            match self {
+                HistorySort::Date => run.finished_at as f64,
+                HistorySort::Wave => run.wave as f64,
+                HistorySort::Duration => run.duration as f64,
+                HistorySort::Kills => run.kills as f64,
+            }
+        };
+        order.sort_by(|&a, &b| key(b).partial_cmp(&key(a)).expect("Compared two f64's"));
+        order
+    }
+}
+
+/// What a finished run achieved.
+pub struct RunResult {
+    pub kills: u32,
+    pub survival: f32,
+    pub wave: u32,
+}
+
+/// Which records the last finished run broke.
+#[derive(Default)]
+pub struct NewBests {
+    pub kills: bool,
+    pub survival: bool,
+    pub wave: bool,
+}
+
+impl Records {
+    pub fn submit(&mut self, run: &RunResult) -> NewBests {
+        self.total_kills += u64::from(run.kills);
+        let new_bests = NewBests {
+            kills: run.kills > self.most_kills,
+            survival: run.survival > self.longest_survival,
+            wave: run.wave > self.highest_wave,
+        };
+        self.most_kills = self.most_kills.max(run.kills);
+        self.longest_survival = self.longest_survival.max(run.survival);
+        self.highest_wave = self.highest_wave.max(run.wave);
+        new_bests
+    }
+
+    /// Marks `day`'s daily challenge as done, keeping the furthest run.
+    pub fn complete_daily(&mut self, day: u64, run: &RunResult) {
+        let record = DailyRecord {
+            wave: run.wave,
+            kills: run.kills,
+        };
+        self.dailies
+            .entry(day)
+            .and_modify(|best| {
+                if (record.wave, record.kills) > (best.wave, best.kills) {
+                    *best = record;
+                }
+            })
+            .or_insert(record);
+    }
+
+    /// Records a victory in `stage`, keeping the most stars and the quickest time apart.
+    pub fn complete_stage(&mut self, stage: &str, stars: u32, time: f32) {
+        let record = StageRecord {
+            stars,
+            fastest: time,
+        };
+        self.stages
+            .entry(stage.to_owned())
+            .and_modify(|best| {
+                best.stars = best.stars.max(stars);
+                best.fastest = best.fastest.min(time);
+            })
+            .or_insert(record);
+    }
+
+    /// Whether the stage at `index` of `stages` can be played, after winning the one before.
+    pub fn is_unlocked(&self, stages: &[Stage], index: usize) -> bool {
+        index == 0
+            || stages
+                .get(index - 1)
+                .is_some_and(|previous| self.stages.contains_key(&previous.name))
+    }
+
+    pub fn remember(&mut self, run: RunRecord) {
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.remove(0);
+        }
+        self.history.push(run);
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("records").show(ui, |ui| {
+            for (name, value) in [
+                (tr("Total kills"), self.total_kills.to_string()),
+                (tr("Most kills"), self.most_kills.to_string()),
+                (
+                    tr("Longest survival"),
+                    durations::format(self.longest_survival),
+                ),
+                (tr("Highest wave"), self.highest_wave.to_string()),
+                (
+                    tr("Deepest New Game+"),
+                    self.deepest_new_game_plus.to_string(),
+                ),
+                (tr("Victories"), self.victories.to_string()),
+                (tr("Laurels"), self.laurels.to_string()),
+            ] {
+                ui.label(name);
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+    }
+}
+
+impl RunResult {
+    /// The run's numbers, with the records it broke highlighted.
+    pub fn show(&self, ui: &mut egui::Ui, new_bests: &NewBests) {
+        egui::Grid::new("run_result").show(ui, |ui| {
+            for (name, value, is_best) in [
+                (tr("Kills"), self.kills.to_string(), new_bests.kills),
+                (
+                    tr("Survived"),
+                    durations::format(self.survival),
+                    new_bests.survival,
+                ),
+                (tr("Wave reached"), self.wave.to_string(), new_bests.wave),
+            ] {
+                ui.label(name);
+                ui.strong(value);
+                if is_best {
+                    ui.colored_label(egui::Color32::GOLD, tr("New best!"));
+                }
+                ui.end_row();
+            }
+        });
+    }
+}
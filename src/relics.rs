@@ -0,0 +1,173 @@
+//! Relics: rare drops off elites and bosses with a randomly rolled effect. The collection is
+//! kept in the records across runs, and only the relics equipped in its `SLOTS` do anything.
+//!
+//! A relic never touches the simulation itself. It answers the events of the run with a
+//! `Reaction`, and `GameState::step` carries the reactions out where the enemies are at hand.
+
+use crate::combat::AttackKind;
+use crate::enemy::{Enemy, EnemyKind};
+use crate::events::GameEvent;
+use crate::i18n::trf;
+use crate::rng::GameRng;
+
+/// Relics doing something at once.
+pub const SLOTS: usize = 3;
+/// How far from a dying enemy its explosion reaches.
+pub const EXPLOSION_RADIUS: f32 = 5.;
+/// Offsets the seed of the drop rolls from the one the names are rolled with.
+pub const DROP_STREAM: u64 = 1 << 32;
+const BOSS_DROP_CHANCE: f32 = 0.5;
+const ELITE_DROP_CHANCE: f32 = 0.05;
+
+/// Chance of `enemy` dropping a relic when it dies, chests always do. Only rolled in runs
+/// counting towards the records, the collection is kept there.
+pub fn drop_chance(enemy: &Enemy) -> f32 {
+    if enemy.kind == EnemyKind::Chest {
+        1.
+    } else if enemy.is_boss {
+        BOSS_DROP_CHANCE
+    } else if enemy.is_elite() {
+        ELITE_DROP_CHANCE
+    } else {
+        0.
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum RelicEffect {
+    /// The attack hits a second time for `power` of its damage.
+    Echo(AttackKind),
+    /// Dying enemies explode for `power` of their maximum HP.
+    Volatile,
+    /// Every kill heals `power` HP.
+    Vampiric,
+    /// Every kill pays `power` more gold.
+    Greedy,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub struct Relic {
+    pub effect: RelicEffect,
+    /// How strong the effect rolled, its meaning depends on the effect.
+    pub power: f32,
+}
+
+/// What an equipped relic does in answer to an event.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Reaction {
+    /// Fire the attack once more for `damage` of its damage.
+    Echo {
+        kind: AttackKind,
+        damage: f32,
+    },
+    /// Damage every enemy within `EXPLOSION_RADIUS` of `distance`.
+    Explode {
+        distance: f32,
+        damage: f32,
+    },
+    Heal(f32),
+    Gold(u32),
+}
+
+impl Relic {
+    /// A relic with a random effect rolled somewhere within its range.
+    pub fn roll(rng: &mut GameRng) -> Self {
+        let (effect, low, high) = match rng.next_index(5) {
+            0 => (RelicEffect::Echo(AttackKind::Basic), 0.3, 0.6),
+            1 => (RelicEffect::Echo(AttackKind::Big), 0.5, 1.),
+            2 => (RelicEffect::Volatile, 0.05, 0.15),
+            3 => (RelicEffect::Vampiric, 1., 3.),
+            _ => (RelicEffect::Greedy, 1., 5.),
+        };
+        let power = low + (high - low) * rng.next_f32();
+        // Whole numbers read better for HP and gold, whole percents for the rest
+        let power = match effect {
+            RelicEffect::Vampiric | RelicEffect::Greedy => power.round(),
+            _ => (power * 100.).round() / 100.,
+        };
+        Self { effect, power }
+    }
+
+    pub fn describe(&self) -> String {
+        let percent = format!("{:.0}", self.power * 100.);
+        match self.effect {
+            RelicEffect::Echo(kind) => trf(
+                "{} hits twice, the second time for {}%",
+                &[kind.name().to_owned(), percent],
+            ),
+            RelicEffect::Volatile => trf(
+                "Enemies explode on death for {}% of their max HP",
+                &[percent],
+            ),
+            RelicEffect::Vampiric => trf("Every kill heals {} HP", &[self.power.to_string()]),
+            RelicEffect::Greedy => trf("Every kill pays {} more gold", &[self.power.to_string()]),
+        }
+    }
+
+    /// What it does in answer to `event`, if anything.
+    pub fn react(&self, event: &GameEvent) -> Option<Reaction> {
+        match (self.effect, event) {
+            (RelicEffect::Echo(echoed), GameEvent::AttackFired { kind, .. }) if echoed == *kind => {
+                Some(Reaction::Echo {
+                    kind: echoed,
+                    damage: self.power,
+                })
+            }
+            (RelicEffect::Volatile, GameEvent::EnemyKilled(enemy)) => Some(Reaction::Explode {
+                distance: enemy.distance.0,
+                damage: enemy.hp.maximum * self.power,
+            }),
+            (RelicEffect::Vampiric, GameEvent::EnemyKilled(_)) => Some(Reaction::Heal(self.power)),
+            (RelicEffect::Greedy, GameEvent::EnemyKilled(_)) => {
+                Some(Reaction::Gold(self.power as u32))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Every relic found so far and which of them are equipped, kept in the records.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Relics {
+    owned: Vec<Relic>,
+    /// Indices into `owned`, at most `SLOTS` of them.
+    equipped: Vec<usize>,
+}
+
+impl Relics {
+    /// Adds `relic` to the collection, equipping it right away if a slot is free.
+    pub fn add(&mut self, relic: Relic) {
+        self.owned.push(relic);
+        if self.equipped.len() < SLOTS {
+            self.equipped.push(self.owned.len() - 1);
+        }
+    }
+
+    pub fn owned(&self) -> &[Relic] {
+        &self.owned
+    }
+
+    pub fn is_equipped(&self, index: usize) -> bool {
+        self.equipped.contains(&index)
+    }
+
+    /// Equips the relic at `index` or takes it off, false if every slot is taken.
+    pub fn toggle(&mut self, index: usize) -> bool {
+        if self.is_equipped(index) {
+            self.equipped.retain(|&equipped| equipped != index);
+            return true;
+        }
+        if self.equipped.len() >= SLOTS || index >= self.owned.len() {
+            return false;
+        }
+        self.equipped.push(index);
+        true
+    }
+
+    pub fn equipped(&self) -> impl Iterator<Item = &Relic> {
+        self.equipped
+            .iter()
+            .filter_map(|&index| self.owned.get(index))
+    }
+}
@@ -0,0 +1,85 @@
+//! Named save slots, each stored under its own key in the eframe storage.
+
+use crate::i18n::{tr, trf};
+use crate::profiles;
+use crate::{GameState, SaveError};
+
+pub const SLOT_COUNT: usize = 3;
+
+/// Cheap to load description of a slot, stored next to the full state.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct SlotSummary {
+    pub name: String,
+    /// Unix timestamp in seconds.
+    pub saved_at: u64,
+    /// Run time in seconds.
+    pub playtime: f32,
+    pub gold: u32,
+    #[serde(default)]
+    pub wave: u32,
+}
+
+fn state_key(profile: u32, slot: usize) -> String {
+    profiles::key_of(profile, &format!("save_slot_{slot}"))
+}
+
+fn summary_key(profile: u32, slot: usize) -> String {
+    profiles::key_of(profile, &format!("save_slot_{slot}_summary"))
+}
+
+pub fn summary(storage: &dyn eframe::Storage, profile: u32, slot: usize) -> Option<SlotSummary> {
+    eframe::get_value(storage, &summary_key(profile, slot))
+}
+
+pub fn load(
+    storage: &dyn eframe::Storage,
+    profile: u32,
+    slot: usize,
+) -> Option<Result<GameState, SaveError>> {
+    storage
+        .get_string(&state_key(profile, slot))
+        .filter(|save| !save.is_empty())
+        .map(|save| GameState::from_save(&save))
+}
+
+pub fn save(
+    storage: &mut dyn eframe::Storage,
+    profile: u32,
+    slot: usize,
+    summary: &SlotSummary,
+    state: &GameState,
+) {
+    eframe::set_value(storage, &state_key(profile, slot), state);
+    eframe::set_value(storage, &summary_key(profile, slot), summary);
+    storage.flush();
+}
+
+/// Storage has no removal, an empty string simply fails to deserialize.
+pub fn clear(storage: &mut dyn eframe::Storage, profile: u32, slot: usize) {
+    storage.set_string(&state_key(profile, slot), String::new());
+    storage.set_string(&summary_key(profile, slot), String::new());
+    storage.flush();
+}
+
+pub fn now() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+pub fn format_age(saved_at: u64) -> String {
+    let seconds = now().saturating_sub(saved_at);
+    match seconds {
+        0..=59 => tr("just now").to_owned(),
+        60..=3599 => trf("{}m ago", &[(seconds / 60).to_string()]),
+        3600..=86399 => trf(
+            "{}h {}m ago",
+            &[
+                (seconds / 3600).to_string(),
+                (seconds % 3600 / 60).to_string(),
+            ],
+        ),
+        _ => trf("{}d ago", &[(seconds / 86400).to_string()]),
+    }
+}
@@ -0,0 +1,538 @@
+//! Player preferences, persisted separately from the run so loading a save doesn't touch them.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use egui::Key;
+
+use crate::audio::SoundSettings;
+use crate::combat::HitPoints;
+use crate::durations::DurationFormat;
+use crate::i18n::{tr, Language};
+use crate::juice::JuiceSettings;
+use crate::loadouts::Loadout;
+use crate::spawner::SpawnerPreset;
+use crate::theme::Theme;
+use crate::widgets;
+
+pub const SETTINGS_KEY: &str = "settings";
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    pub keybindings: Keybindings,
+    pub language: Language,
+    pub theme: Theme,
+    /// Zoom applied on top of the display's native scale.
+    pub ui_scale: f32,
+    /// Bigger fonts and roomier widgets for readability.
+    pub large_text: bool,
+    /// Keeps the screen from shaking.
+    pub reduced_motion: bool,
+    pub layout: LayoutMode,
+    pub duration_format: DurationFormat,
+    pub when_unfocused: WhenUnfocused,
+    pub frame_rate: FrameRate,
+    /// Drops to `IDLE_FPS` while nothing is being simulated.
+    pub idle_friendly: bool,
+    /// Simulates at `BACKGROUND_FPS` while minimized, in coarse steps unless the run tracks
+    /// records.
+    pub low_power_background: bool,
+    pub sound: SoundSettings,
+    pub juice: JuiceSettings,
+    pub leaderboard: LeaderboardSettings,
+    pub telemetry: TelemetrySettings,
+    /// Only used with the `gamepad` feature.
+    pub gamepad: GamepadBindings,
+    pub spawner_presets: BTreeMap<String, SpawnerPreset>,
+    pub loadouts: BTreeMap<String, Loadout>,
+    pub auto_continue: AutoContinue,
+}
+
+/// Moves a campaign on to the next stage by itself after a win, for idle play.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AutoContinue {
+    pub enabled: bool,
+    /// Fraction of the maximum HP below which it stops and pauses instead.
+    pub min_hp: f32,
+}
+
+impl Default for AutoContinue {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_hp: 0.3,
+        }
+    }
+}
+
+impl AutoContinue {
+    /// Whether `hp` has fallen low enough to stop.
+    pub fn is_unsafe(&self, hp: &HitPoints) -> bool {
+        hp.current < hp.maximum * self.min_hp
+    }
+}
+
+/// Where finished runs are submitted, only used with the `leaderboard` feature.
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+pub struct LeaderboardSettings {
+    /// Base URL of the server, empty keeps the game offline.
+    pub url: String,
+    pub player_name: String,
+}
+
+/// Anonymous statistics about finished runs, only used with the `telemetry` feature.
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    /// Off until the player opts in.
+    pub enabled: bool,
+    /// Base URL of the server the reports go to.
+    pub url: String,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            keybindings: Keybindings::default(),
+            language: Language::default(),
+            theme: Theme::default(),
+            ui_scale: 1.,
+            large_text: false,
+            reduced_motion: false,
+            layout: LayoutMode::default(),
+            duration_format: DurationFormat::default(),
+            when_unfocused: WhenUnfocused::default(),
+            frame_rate: FrameRate::default(),
+            idle_friendly: true,
+            low_power_background: true,
+            sound: SoundSettings::default(),
+            juice: JuiceSettings::default(),
+            leaderboard: LeaderboardSettings::default(),
+            telemetry: TelemetrySettings::default(),
+            gamepad: GamepadBindings::default(),
+            spawner_presets: BTreeMap::new(),
+            loadouts: BTreeMap::new(),
+            auto_continue: AutoContinue::default(),
+        }
+    }
+}
+
+/// Whether the panels are laid out for a desktop or a phone.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default)]
+pub enum LayoutMode {
+    /// Touch layout on narrow screens.
+    #[default]
+    Auto,
+    Desktop,
+    Touch,
+}
+
+impl LayoutMode {
+    pub const ALL: [LayoutMode; 3] = [LayoutMode::Auto, LayoutMode::Desktop, LayoutMode::Touch];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            LayoutMode::Auto => "Automatic",
+            LayoutMode::Desktop => "Desktop",
+            LayoutMode::Touch => "Touch",
+        })
+    }
+}
+
+/// What the simulation does while the window isn't focused.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default)]
+pub enum WhenUnfocused {
+    /// Pauses until the window is focused again, so alt-tabbing can't lose the run.
+    #[default]
+    Pause,
+    /// Idles on as if the window were focused.
+    KeepSimulating,
+}
+
+impl WhenUnfocused {
+    pub const ALL: [WhenUnfocused; 2] = [WhenUnfocused::Pause, WhenUnfocused::KeepSimulating];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            WhenUnfocused::Pause => "Pause",
+            WhenUnfocused::KeepSimulating => "Keep simulating",
+        })
+    }
+}
+
+/// How often the window repaints while the game runs.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default)]
+pub enum FrameRate {
+    Fps30,
+    #[default]
+    Fps60,
+    Fps120,
+    /// As often as the display allows.
+    Uncapped,
+}
+
+impl FrameRate {
+    pub const ALL: [FrameRate; 4] = [
+        FrameRate::Fps30,
+        FrameRate::Fps60,
+        FrameRate::Fps120,
+        FrameRate::Uncapped,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FrameRate::Fps30 => "30",
+            FrameRate::Fps60 => "60",
+            FrameRate::Fps120 => "120",
+            FrameRate::Uncapped => tr("Uncapped"),
+        }
+    }
+
+    fn fps(&self) -> Option<f32> {
+        match self {
+            FrameRate::Fps30 => Some(30.),
+            FrameRate::Fps60 => Some(60.),
+            FrameRate::Fps120 => Some(120.),
+            FrameRate::Uncapped => None,
+        }
+    }
+}
+
+/// Frame rate of `Settings::idle_friendly` while nothing is being simulated.
+const IDLE_FPS: f32 = 5.;
+/// Frame rate of `Settings::low_power_background` while minimized.
+const BACKGROUND_FPS: f32 = 1.;
+
+const LARGE_TEXT_FACTOR: f32 = 1.3;
+/// Screens narrower than this, in points, get the touch layout in `LayoutMode::Auto`.
+const TOUCH_MAX_WIDTH: f32 = 600.;
+/// Minimum height of anything tappable in the touch layout, roughly a fingertip.
+const TOUCH_TARGET: f32 = 36.;
+
+impl Settings {
+    /// Whether the touch layout should be used for the current screen.
+    pub fn touch_layout(&self, ctx: &egui::Context) -> bool {
+        match self.layout {
+            LayoutMode::Auto => ctx.screen_rect().width() < TOUCH_MAX_WIDTH,
+            LayoutMode::Desktop => false,
+            LayoutMode::Touch => true,
+        }
+    }
+
+    /// Pushes the appearance settings into egui; call whenever one of them or the layout changes.
+    pub fn apply(&self, ctx: &egui::Context, touch: bool) {
+        let mut style = egui::Style {
+            visuals: self.theme.visuals(),
+            ..Default::default()
+        };
+        if self.large_text {
+            for font in style.text_styles.values_mut() {
+                font.size *= LARGE_TEXT_FACTOR;
+            }
+            style.spacing.item_spacing *= LARGE_TEXT_FACTOR;
+            style.spacing.button_padding *= LARGE_TEXT_FACTOR;
+            style.spacing.interact_size *= LARGE_TEXT_FACTOR;
+            style.spacing.slider_width *= LARGE_TEXT_FACTOR;
+        }
+        if touch {
+            let spacing = &mut style.spacing;
+            spacing.interact_size.y = spacing.interact_size.y.max(TOUCH_TARGET);
+            spacing.item_spacing.y *= 1.5;
+            spacing.icon_width *= 1.5;
+            spacing.icon_width_inner *= 1.5;
+            spacing.slider_width = spacing.slider_width.max(160.);
+            spacing.scroll.bar_width *= 2.;
+        }
+        widgets::set_touch(ctx, touch);
+        ctx.set_style(style);
+        ctx.set_zoom_factor(self.ui_scale);
+    }
+
+    /// Returns whether the scale or text size changed.
+    pub fn show_accessibility_editor(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(tr("UI scale:"));
+            let response = ui.add(egui::Slider::new(&mut self.ui_scale, 0.5..=3.).step_by(0.05));
+            // Rescaling mid-drag would move the slider out from under the pointer
+            changed |= response.drag_stopped() || (response.changed() && !response.dragged());
+        });
+        changed |= ui
+            .checkbox(&mut self.large_text, tr("Large text"))
+            .changed();
+        ui.checkbox(&mut self.reduced_motion, tr("Reduced motion"));
+        ui.horizontal(|ui| {
+            ui.label(tr("Layout:"));
+            for layout in LayoutMode::ALL {
+                changed |= ui
+                    .selectable_value(&mut self.layout, layout, layout.name())
+                    .changed();
+            }
+        });
+        changed
+    }
+
+    /// How long to wait before the next repaint, none to repaint right away.
+    pub fn repaint_after(&self, idle: bool, low_power: bool) -> Option<Duration> {
+        let fps = match (idle && self.idle_friendly, low_power) {
+            (_, true) => Some(BACKGROUND_FPS),
+            (true, false) => Some(IDLE_FPS),
+            (false, false) => self.frame_rate.fps(),
+        };
+        fps.map(|fps| Duration::from_secs_f32(1. / fps))
+    }
+
+    pub fn show_performance_editor(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Frame rate:"));
+            for frame_rate in FrameRate::ALL {
+                ui.selectable_value(&mut self.frame_rate, frame_rate, frame_rate.name());
+            }
+        });
+        ui.checkbox(&mut self.idle_friendly, tr("Idle-friendly"))
+            .on_hover_text(tr(
+                "Repaints only a few times per second while paused or after the run ended.",
+            ));
+        ui.checkbox(&mut self.low_power_background, tr("Low power when minimized"))
+            .on_hover_text(tr(
+                "Simulates only once a second while minimized, so idling in the background barely uses the CPU. Runs outside Survival also take coarser steps then, which plays their seed out differently.",
+            ));
+        ui.horizontal(|ui| {
+            ui.label(tr("When unfocused:"));
+            for behavior in WhenUnfocused::ALL {
+                ui.selectable_value(&mut self.when_unfocused, behavior, behavior.name());
+            }
+        });
+    }
+}
+
+/// Something the player can trigger with a key or controller button.
+#[derive(
+    serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Action {
+    Pause,
+    CastBasicAttack,
+    CastBigAttack,
+    CastUltimate,
+    SpeedUp,
+    SpeedDown,
+    ToggleProfiler,
+    CallWave,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::Pause,
+        Action::CastBasicAttack,
+        Action::CastBigAttack,
+        Action::CastUltimate,
+        Action::SpeedUp,
+        Action::SpeedDown,
+        Action::ToggleProfiler,
+        Action::CallWave,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Action::Pause => "Pause",
+            Action::CastBasicAttack => "Cast basic attack",
+            Action::CastBigAttack => "Cast big attack",
+            Action::CastUltimate => "Unleash the ultimate",
+            Action::SpeedUp => "Speed up",
+            Action::SpeedDown => "Speed down",
+            Action::ToggleProfiler => "Toggle the profiler",
+            Action::CallWave => "Call the next wave early",
+        })
+    }
+}
+
+/// Settings saved before an action existed get its default key on load, unless they already
+/// bound that key to something else. An action unbound on purpose gets it back the same way.
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(from = "BTreeMap<Action, Key>")]
+pub struct Keybindings(BTreeMap<Action, Key>);
+
+impl From<BTreeMap<Action, Key>> for Keybindings {
+    fn from(mut bindings: BTreeMap<Action, Key>) -> Self {
+        for (action, key) in Self::default().0 {
+            if !bindings.values().any(|bound| *bound == key) {
+                bindings.entry(action).or_insert(key);
+            }
+        }
+        Self(bindings)
+    }
+}
+
+impl Keybindings {
+    pub fn key(&self, action: Action) -> Option<Key> {
+        self.0.get(&action).copied()
+    }
+
+    /// Binds `key` to `action`, taking it away from whatever action had it before.
+    pub fn bind(&mut self, action: Action, key: Key) {
+        self.0.retain(|_, bound| *bound != key);
+        self.0.insert(action, key);
+    }
+
+    pub fn unbind(&mut self, action: Action) {
+        self.0.remove(&action);
+    }
+
+    /// Actions whose key was pressed this frame.
+    pub fn pressed(&self, input: &egui::InputState) -> Vec<Action> {
+        self.0
+            .iter()
+            .filter(|(_, key)| input.key_pressed(**key))
+            .map(|(action, _)| *action)
+            .collect()
+    }
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self(BTreeMap::from([
+            (Action::Pause, Key::Space),
+            (Action::CastBasicAttack, Key::Num1),
+            (Action::CastBigAttack, Key::Num2),
+            (Action::CastUltimate, Key::Num3),
+            (Action::SpeedUp, Key::Plus),
+            (Action::SpeedDown, Key::Minus),
+            (Action::ToggleProfiler, Key::F3),
+            (Action::CallWave, Key::N),
+        ]))
+    }
+}
+
+/// A controller button, named after its position like gilrs does.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum PadButton {
+    South,
+    East,
+    North,
+    West,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+impl PadButton {
+    pub const ALL: [PadButton; 14] = [
+        PadButton::South,
+        PadButton::East,
+        PadButton::North,
+        PadButton::West,
+        PadButton::LeftBumper,
+        PadButton::RightBumper,
+        PadButton::LeftTrigger,
+        PadButton::RightTrigger,
+        PadButton::Select,
+        PadButton::Start,
+        PadButton::DPadUp,
+        PadButton::DPadDown,
+        PadButton::DPadLeft,
+        PadButton::DPadRight,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            PadButton::South => "South (A)",
+            PadButton::East => "East (B)",
+            PadButton::North => "North (Y)",
+            PadButton::West => "West (X)",
+            PadButton::LeftBumper => "Left bumper",
+            PadButton::RightBumper => "Right bumper",
+            PadButton::LeftTrigger => "Left trigger",
+            PadButton::RightTrigger => "Right trigger",
+            PadButton::Select => "Select",
+            PadButton::Start => "Start",
+            PadButton::DPadUp => "D-pad up",
+            PadButton::DPadDown => "D-pad down",
+            PadButton::DPadLeft => "D-pad left",
+            PadButton::DPadRight => "D-pad right",
+        })
+    }
+}
+
+/// Controller buttons bound to actions, unbound buttons navigate the menus.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct GamepadBindings(BTreeMap<Action, PadButton>);
+
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+impl GamepadBindings {
+    pub fn button(&self, action: Action) -> Option<PadButton> {
+        self.0.get(&action).copied()
+    }
+
+    /// Binds `button` to `action`, taking it away from whatever action had it before.
+    pub fn bind(&mut self, action: Action, button: PadButton) {
+        self.0.retain(|_, bound| *bound != button);
+        self.0.insert(action, button);
+    }
+
+    pub fn unbind(&mut self, action: Action) {
+        self.0.remove(&action);
+    }
+
+    pub fn action(&self, button: PadButton) -> Option<Action> {
+        self.0
+            .iter()
+            .find(|(_, bound)| **bound == button)
+            .map(|(action, _)| *action)
+    }
+
+    pub fn show_editor(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("gamepad_bindings").show(ui, |ui| {
+            for action in Action::ALL {
+                ui.label(action.name());
+                let current = self.button(action);
+                egui::ComboBox::from_id_source(("gamepad_binding", action))
+                    .selected_text(current.map_or(tr("Unbound"), |button| button.name()))
+                    .show_ui(ui, |ui| {
+                        if ui
+                            .selectable_label(current.is_none(), tr("Unbound"))
+                            .clicked()
+                        {
+                            self.unbind(action);
+                        }
+                        for button in PadButton::ALL {
+                            if ui
+                                .selectable_label(current == Some(button), button.name())
+                                .clicked()
+                            {
+                                self.bind(action, button);
+                            }
+                        }
+                    });
+                ui.end_row();
+            }
+        });
+        ui.label(tr(
+            "The D-pad moves between widgets, South presses and East closes.",
+        ));
+    }
+}
+
+impl Default for GamepadBindings {
+    fn default() -> Self {
+        Self(BTreeMap::from([
+            (Action::Pause, PadButton::Start),
+            (Action::CastBasicAttack, PadButton::South),
+            (Action::CastBigAttack, PadButton::West),
+            (Action::CastUltimate, PadButton::North),
+            (Action::SpeedUp, PadButton::RightBumper),
+            (Action::SpeedDown, PadButton::LeftBumper),
+        ]))
+    }
+}
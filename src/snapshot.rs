@@ -0,0 +1,19 @@
+//! A plain copy of the numbers that matter in a run, for tests and tools stepping the
+//! simulation without a window. Two runs that played out the same have equal snapshots.
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Snapshot {
+    /// Simulated seconds.
+    pub elapsed: f32,
+    pub wave: u32,
+    pub hp: f32,
+    pub max_hp: f32,
+    pub gold: u32,
+    pub experience: u32,
+    pub kills: u32,
+    pub enemies: usize,
+    /// Summed over every enemy on the field.
+    pub enemy_hp: f32,
+    pub defeated: bool,
+    pub victorious: bool,
+}
@@ -0,0 +1,647 @@
+//! Where enemies come from and how they get tougher over a run.
+
+use std::collections::VecDeque;
+
+use egui::Color32;
+
+use crate::combat::HitPoints;
+use crate::content::{Archetype, Content};
+use crate::durations;
+use crate::enemy::{Distance, EliteModifier, Enemy, EnemyKind};
+use crate::i18n::{tr, trf};
+use crate::rng::GameRng;
+use crate::timer::Timer;
+use crate::treasure;
+use crate::widgets::{stat_slider, stat_tooltip};
+
+/// Spawns enemies following its pattern, their base stats multiplied by the archetype's and
+/// scaled with the elapsed time.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct EnemySpawner {
+    pub timer: Timer,
+    /// Seconds between spawns before scaling kicks in.
+    pub interval: f32,
+    pub scaling: SpawnerScaling,
+    pub maximum_hp: f32,
+    pub speed: f32,
+    pub damage: f32,
+    pub elite_chance: f32,
+    pub healer_chance: f32,
+    /// Enemies on the field at most, bosses can still join past it.
+    #[serde(default = "default_max_alive")]
+    pub max_alive: usize,
+    #[serde(default)]
+    pub overflow: Overflow,
+    /// Spawns waiting for room on the field, oldest first.
+    #[serde(default)]
+    pub queue: VecDeque<Enemy>,
+    #[serde(default)]
+    pub pattern: SpawnPattern,
+    /// Spawns into the current accelerating stream.
+    #[serde(default)]
+    pub streak: u32,
+    /// Spawns only this archetype when set, otherwise the wave's composition decides.
+    #[serde(default)]
+    pub archetype: Option<String>,
+    /// Regular spawns roll their HP, speed and damage each within this fraction either way
+    /// of the spawner's, 0 spawns them all alike.
+    #[serde(default)]
+    pub variance: f32,
+}
+
+fn default_max_alive() -> usize {
+    2000
+}
+
+/// Every spawner of a scenario, saved under a name to switch back to it later.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct SpawnerPreset {
+    pub main: EnemySpawner,
+    #[serde(default)]
+    pub extras: Vec<EnemySpawner>,
+}
+
+impl SpawnerPreset {
+    /// The configuration of the spawners, without their progress through the run.
+    pub fn capture(main: &EnemySpawner, extras: &[EnemySpawner]) -> Self {
+        let configuration = |spawner: &EnemySpawner| {
+            let mut spawner = spawner.clone();
+            spawner.queue.clear();
+            spawner.streak = 0;
+            spawner.timer = Timer::new(spawner.interval);
+            spawner
+        };
+        Self {
+            main: configuration(main),
+            extras: extras.iter().map(configuration).collect(),
+        }
+    }
+}
+
+/// What happens to spawns while the field is full.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default)]
+pub enum Overflow {
+    /// They wait for room, up to another `max_alive` of them, the rest merge.
+    #[default]
+    Queue,
+    /// Their HP is added to the farthest enemy on the field.
+    Merge,
+}
+
+impl Overflow {
+    pub const ALL: [Overflow; 2] = [Overflow::Queue, Overflow::Merge];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Overflow::Queue => "Queue",
+            Overflow::Merge => "Merge",
+        })
+    }
+}
+
+/// Whether `enemy` may absorb a spawn or be absorbed into another enemy.
+fn mergeable(enemy: &Enemy) -> bool {
+    !enemy.is_boss && enemy.kind != EnemyKind::Chest
+}
+
+impl EnemySpawner {
+    /// Lets `spawned` onto the field as far as `max_alive` allows, queued spawns first.
+    /// `joining` enemies are already on their way onto the field this step. Returns the
+    /// ones joining, the rest wait in the queue or are merged into `enemies`. Bosses and
+    /// chests keep their own HP, they're never merged.
+    pub fn admit(
+        &mut self,
+        spawned: Vec<Enemy>,
+        enemies: &mut [Enemy],
+        joining: usize,
+    ) -> Vec<Enemy> {
+        self.queue.extend(spawned);
+        let room = self.max_alive.saturating_sub(enemies.len() + joining);
+        let joining: Vec<Enemy> = self.queue.drain(..room.min(self.queue.len())).collect();
+        let queue_limit = match self.overflow {
+            Overflow::Queue => self.max_alive,
+            Overflow::Merge => 0,
+        };
+        while self.queue.len() > queue_limit {
+            let Some(farthest) = enemies.iter_mut().rev().find(|enemy| mergeable(enemy)) else {
+                break;
+            };
+            let Some(index) = self.queue.iter().rposition(mergeable) else {
+                break;
+            };
+            let merged = self
+                .queue
+                .remove(index)
+                .expect("The index is within the queue");
+            farthest.hp.maximum += merged.hp.maximum;
+            farthest.hp.current += merged.hp.current;
+        }
+        joining
+    }
+
+    /// Spawns per timer completion as the pattern says, going through every completion
+    /// within `delta` so long frames don't swallow spawns. `rate` multiplies the spawn rate
+    /// on top of the scaling, 1 leaves it alone.
+    pub fn tick(
+        &mut self,
+        delta: f32,
+        elapsed: f32,
+        rate: f32,
+        wave: u32,
+        content: &Content,
+        rng: &mut GameRng,
+    ) -> Vec<Enemy> {
+        let rate = self.scaling.spawn_rate.multiplier(elapsed) * rate;
+        let longest = self.pattern.longest_gap(self.interval) / rate;
+        self.timer.remaining = self.timer.remaining.min(longest);
+        let mut spawned = vec![];
+        if self.timer.is_paused() {
+            return spawned;
+        }
+        self.timer.remaining -= delta;
+        while self.timer.remaining <= 0. {
+            // Enemies spawned earlier within the delta have already been walking for a while
+            let age = -self.timer.remaining;
+            for _ in 0..self.pattern.size() {
+                let (name, archetype) = self.pick(wave, content, rng);
+                let mut enemy = self.spawn(elapsed, name, archetype, rng);
+                enemy.distance.0 -= age * enemy.speed;
+                spawned.push(enemy);
+            }
+            self.timer.total = self.pattern.next_gap(self.interval, &mut self.streak, rng) / rate;
+            if self.timer.total <= 0. {
+                self.timer.remaining = 0.;
+                break;
+            }
+            self.timer.remaining += self.timer.total;
+        }
+        spawned
+    }
+
+    /// The fixed archetype if it still exists, otherwise a roll from the composition.
+    fn pick<'a>(
+        &self,
+        wave: u32,
+        content: &'a Content,
+        rng: &mut GameRng,
+    ) -> (&'a str, &'a Archetype) {
+        let fixed = self
+            .archetype
+            .as_ref()
+            .and_then(|name| content.archetypes.get_key_value(name));
+        match fixed {
+            Some((name, archetype)) => (name, archetype),
+            None => content.pick(wave, rng),
+        }
+    }
+
+    pub fn spawns_per_minute(&self, elapsed: f32) -> f32 {
+        60. * self.pattern.size() as f32 / self.pattern.mean_gap(self.interval)
+            * self.scaling.spawn_rate.multiplier(elapsed)
+    }
+
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, elapsed: f32) {
+        let damage = self.damage * self.scaling.damage.multiplier(elapsed);
+        stat_slider(
+            ui,
+            tr("Damage:"),
+            &mut self.damage,
+            0. ..=20.,
+            tr("Damage an enemy deals to the Excellency when it arrives, before scaling."),
+            &[(tr("Scaled now"), format!("{damage:.1}"))],
+        );
+        let maximum_hp = self.maximum_hp * self.scaling.hp.multiplier(elapsed);
+        stat_slider(
+            ui,
+            tr("Maximum HP:"),
+            &mut self.maximum_hp,
+            0. ..=100.,
+            tr("HP of a freshly spawned enemy, before scaling. Healers get 70% of it."),
+            &[(tr("Scaled now"), format!("{maximum_hp:.1}"))],
+        );
+        let speed = self.speed * self.scaling.speed.multiplier(elapsed);
+        stat_slider(
+            ui,
+            tr("Speed:"),
+            &mut self.speed,
+            0. ..=20.,
+            tr("Distance an enemy walks per second, before scaling. Healers walk at 60% of it."),
+            &[
+                (tr("Scaled now"), format!("{speed:.1}")),
+                (
+                    tr("Time to arrive"),
+                    durations::format(Distance::start().0 / speed),
+                ),
+            ],
+        );
+        let spawns_per_minute = self.spawns_per_minute(elapsed);
+        let elites_per_minute = spawns_per_minute * self.elite_chance;
+        let healers_per_minute = spawns_per_minute * self.healer_chance;
+        stat_slider(
+            ui,
+            tr("Elite chance:"),
+            &mut self.elite_chance,
+            0. ..=1.,
+            tr("Chance that a spawned enemy is an elite with one or two modifiers."),
+            &[(tr("Elites per minute"), format!("{elites_per_minute:.1}"))],
+        );
+        stat_slider(
+            ui,
+            tr("Healer chance:"),
+            &mut self.healer_chance,
+            0. ..=1.,
+            tr("Chance that a spawned enemy is a healer that mends the enemies around it."),
+            &[(tr("Healers per minute"), format!("{healers_per_minute:.1}"))],
+        );
+        let hp_range = format!(
+            "{:.1}-{:.1}",
+            maximum_hp * (1. - self.variance),
+            maximum_hp * (1. + self.variance)
+        );
+        stat_slider(
+            ui,
+            tr("Stat variance:"),
+            &mut self.variance,
+            0. ..=0.5,
+            tr("How far each spawned enemy's HP, speed and damage may land either way of the values above, as a fraction of them."),
+            &[(tr("HP range"), hp_range)],
+        );
+        stat_slider(
+            ui,
+            tr("Enemy cap:"),
+            &mut self.max_alive,
+            10..=10_000,
+            tr("Enemies on the field at most. Bosses still join past it, other spawns overflow."),
+            &[],
+        );
+        ui.horizontal(|ui| {
+            ui.label(tr("Overflow:"));
+            egui::ComboBox::from_id_source("spawner_overflow")
+                .selected_text(self.overflow.name())
+                .show_ui(ui, |ui| {
+                    for overflow in Overflow::ALL {
+                        ui.selectable_value(&mut self.overflow, overflow, overflow.name());
+                    }
+                });
+        });
+        ui.label(trf("Queued: {}", &[self.queue.len().to_string()]));
+        ui.horizontal(|ui| {
+            ui.label(tr("Pattern:"));
+            if self.pattern.show_editor(ui, self.interval) {
+                self.streak = 0;
+            }
+        });
+    }
+
+    /// `archetype` with the spawner's stats, varied by `rng` if given. The variance is rolled
+    /// before the archetype's modifiers apply, so the ones scaling off the HP follow the roll.
+    fn base(
+        &self,
+        elapsed: f32,
+        name: &str,
+        archetype: &Archetype,
+        rng: Option<&mut GameRng>,
+    ) -> Enemy {
+        let maximum_hp = archetype.hp * self.maximum_hp * self.scaling.hp.multiplier(elapsed);
+        let mut enemy = Enemy {
+            damage: archetype.damage * self.damage * self.scaling.damage.multiplier(elapsed),
+            speed: archetype.speed * self.speed * self.scaling.speed.multiplier(elapsed),
+            armor: archetype.armor,
+            kind: archetype.kind,
+            archetype: name.to_owned(),
+            ..Enemy::new(maximum_hp, Distance::start().0)
+        };
+        // Only rolled when it's on, so seeds replay the same without it
+        if let Some(rng) = rng.filter(|_| self.variance > 0.) {
+            let mut vary = || 1. + self.variance * (2. * rng.next_f32() - 1.);
+            enemy.hp = HitPoints::new_full(enemy.hp.maximum * vary());
+            enemy.speed *= vary();
+            enemy.damage *= vary();
+        }
+        for modifier in &archetype.modifiers {
+            modifier.apply(&mut enemy);
+            enemy.modifiers.push(*modifier);
+        }
+        enemy
+    }
+
+    /// A regular enemy, which may roll into a healer or an elite.
+    pub fn spawn(
+        &self,
+        elapsed: f32,
+        name: &str,
+        archetype: &Archetype,
+        rng: &mut GameRng,
+    ) -> Enemy {
+        let mut enemy = self.base(elapsed, name, archetype, Some(rng));
+        if rng.chance(self.healer_chance) && enemy.kind == EnemyKind::Grunt {
+            // Healers are frail and hang back behind the rest of the pack
+            enemy.kind = EnemyKind::Healer;
+            enemy.hp = HitPoints::new_full(0.7 * enemy.hp.maximum);
+            enemy.speed *= 0.6;
+        }
+        if rng.chance(self.elite_chance) {
+            let modifier_count =
+                (enemy.modifiers.len() + 1 + rng.next_index(2)).min(EliteModifier::ALL.len());
+            while enemy.modifiers.len() < modifier_count {
+                let modifier = EliteModifier::ALL[rng.next_index(EliteModifier::ALL.len())];
+                if !enemy.modifiers.contains(&modifier) {
+                    modifier.apply(&mut enemy);
+                    enemy.modifiers.push(modifier);
+                }
+            }
+        }
+        enemy
+    }
+
+    /// A treasure chest with the spawner's current stats, already fleeing, see `treasure`.
+    pub fn spawn_chest(&self, elapsed: f32) -> Enemy {
+        let archetype = Archetype {
+            hp: treasure::HP_FACTOR,
+            kind: EnemyKind::Chest,
+            ..Archetype::default()
+        };
+        let mut chest = self.base(elapsed, "", &archetype, None);
+        chest.damage = 0.;
+        chest.distance = Distance(treasure::START);
+        chest.fleeing = Some(treasure::LIFETIME);
+        chest
+    }
+
+    /// Bosses skip the healer and elite rolls, their archetype alone decides what they are.
+    pub fn spawn_boss(&self, elapsed: f32, name: &str, archetype: &Archetype) -> Enemy {
+        Enemy {
+            is_boss: true,
+            ..self.base(elapsed, name, archetype, None)
+        }
+    }
+}
+
+/// How each spawned stat grows with the elapsed run time.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct SpawnerScaling {
+    pub hp: ScalingCurve,
+    pub speed: ScalingCurve,
+    pub damage: ScalingCurve,
+    pub spawn_rate: ScalingCurve,
+}
+
+impl SpawnerScaling {
+    /// Every curve with its name and the color it's plotted in.
+    pub fn curves(&self) -> [(&'static str, ScalingCurve, Color32); 4] {
+        [
+            (tr("HP"), self.hp, Color32::DARK_RED),
+            (tr("Speed"), self.speed, Color32::LIGHT_BLUE),
+            (tr("Damage"), self.damage, Color32::GOLD),
+            (tr("Spawn rate"), self.spawn_rate, Color32::GREEN),
+        ]
+    }
+}
+
+/// How spawns are spread over time, the gaps between them before any spawn rate scaling.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Default)]
+pub enum SpawnPattern {
+    /// One enemy every `interval`.
+    #[default]
+    Steady,
+    /// `size` enemies at once every `period` seconds.
+    Burst { size: u32, period: f32 },
+    /// Every gap `acceleration` shorter than the one before, starting over from `interval`
+    /// once a gap would drop below `floor` of it.
+    Accelerating { acceleration: f32, floor: f32 },
+    /// Gaps rolled anywhere within `interval` ± `spread` of it.
+    Jitter { spread: f32 },
+}
+
+impl SpawnPattern {
+    const KINDS: [SpawnPattern; 4] = [
+        SpawnPattern::Steady,
+        SpawnPattern::Burst {
+            size: 5,
+            period: 5.,
+        },
+        SpawnPattern::Accelerating {
+            acceleration: 0.1,
+            floor: 0.2,
+        },
+        SpawnPattern::Jitter { spread: 0.5 },
+    ];
+
+    pub fn kind_name(&self) -> &'static str {
+        tr(match self {
+            SpawnPattern::Steady => "Steady",
+            SpawnPattern::Burst { .. } => "Burst",
+            SpawnPattern::Accelerating { .. } => "Accelerating",
+            SpawnPattern::Jitter { .. } => "Jitter",
+        })
+    }
+
+    /// Enemies spawned at once.
+    pub fn size(&self) -> u32 {
+        match *self {
+            SpawnPattern::Burst { size, .. } => size,
+            _ => 1,
+        }
+    }
+
+    /// The gap after the spawn `streak` enemies into the stream, moving the streak along.
+    pub fn next_gap(&self, interval: f32, streak: &mut u32, rng: &mut GameRng) -> f32 {
+        match *self {
+            SpawnPattern::Steady => interval,
+            SpawnPattern::Burst { period, .. } => period,
+            SpawnPattern::Accelerating {
+                acceleration,
+                floor,
+            } => {
+                *streak += 1;
+                let factor = (1. - acceleration).powi(*streak as i32);
+                if factor < floor {
+                    *streak = 0;
+                    return interval;
+                }
+                interval * factor
+            }
+            SpawnPattern::Jitter { spread } => {
+                interval * (1. + spread * (2. * rng.next_f32() - 1.))
+            }
+        }
+    }
+
+    /// The longest gap the pattern can roll.
+    fn longest_gap(&self, interval: f32) -> f32 {
+        match *self {
+            SpawnPattern::Burst { period, .. } => period,
+            SpawnPattern::Jitter { spread } => interval * (1. + spread),
+            SpawnPattern::Steady | SpawnPattern::Accelerating { .. } => interval,
+        }
+    }
+
+    /// The average gap over a long run.
+    pub fn mean_gap(&self, interval: f32) -> f32 {
+        match *self {
+            SpawnPattern::Accelerating {
+                acceleration,
+                floor,
+            } => {
+                // One whole stream, from `interval` down to the floor
+                let (mut streak, mut rng) = (0, GameRng::new(0));
+                let (mut total, mut gaps) = (0., 0);
+                loop {
+                    total += self.next_gap(interval, &mut streak, &mut rng);
+                    gaps += 1;
+                    if streak == 0 || gaps >= 1000 || acceleration <= 0. || floor <= 0. {
+                        break;
+                    }
+                }
+                total / gaps as f32
+            }
+            SpawnPattern::Burst { period, .. } => period,
+            SpawnPattern::Steady | SpawnPattern::Jitter { .. } => interval,
+        }
+    }
+
+    /// Returns whether the pattern changed kind.
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, interval: f32) -> bool {
+        let mut changed = false;
+        egui::ComboBox::from_id_source("spawn_pattern")
+            .selected_text(self.kind_name())
+            .show_ui(ui, |ui| {
+                for kind in SpawnPattern::KINDS {
+                    let is_selected = std::mem::discriminant(self) == std::mem::discriminant(&kind);
+                    if ui.selectable_label(is_selected, kind.kind_name()).clicked() && !is_selected
+                    {
+                        *self = kind;
+                        changed = true;
+                    }
+                }
+            });
+        let derived = [(
+            tr("Spawns per minute"),
+            format!("{:.1}", 60. * self.size() as f32 / self.mean_gap(interval)),
+        )];
+        match self {
+            SpawnPattern::Steady => {}
+            SpawnPattern::Burst { size, period } => {
+                let explanation = tr("Spawns size enemies at once every period seconds.");
+                let slider = ui.add(egui::Slider::new(size, 1..=50).text(tr("size")));
+                stat_tooltip(slider, explanation, &derived);
+                let slider = ui.add(
+                    egui::Slider::new(period, 0.5..=60.)
+                        .text(tr("period"))
+                        .suffix("s"),
+                );
+                stat_tooltip(slider, explanation, &derived);
+            }
+            SpawnPattern::Accelerating {
+                acceleration,
+                floor,
+            } => {
+                let explanation = tr("Every gap is shorter by accel than the one before, the stream starts over once it drops below floor of the interval.");
+                let slider = ui.add(egui::Slider::new(acceleration, 0.01..=0.5).text(tr("accel")));
+                stat_tooltip(slider, explanation, &derived);
+                let slider = ui.add(egui::Slider::new(floor, 0.05..=0.9).text(tr("floor")));
+                stat_tooltip(slider, explanation, &derived);
+            }
+            SpawnPattern::Jitter { spread } => {
+                let slider = ui.add(egui::Slider::new(spread, 0. ..=1.).text(tr("spread")));
+                stat_tooltip(
+                    slider,
+                    tr("Gaps are rolled within the interval ± spread of it."),
+                    &derived,
+                );
+            }
+        }
+        changed
+    }
+}
+
+/// A multiplier that starts at 1 and grows with the elapsed run time (in minutes).
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum ScalingCurve {
+    /// `1 + per_minute * t`
+    Linear { per_minute: f32 },
+    /// `(1 + growth)^t`
+    Exponential { growth: f32 },
+    /// `1 + coefficient * t^exponent`
+    Polynomial { coefficient: f32, exponent: f32 },
+}
+
+impl ScalingCurve {
+    const KINDS: [ScalingCurve; 3] = [
+        ScalingCurve::Linear { per_minute: 0.1 },
+        ScalingCurve::Exponential { growth: 0.05 },
+        ScalingCurve::Polynomial {
+            coefficient: 0.01,
+            exponent: 2.,
+        },
+    ];
+
+    pub fn kind_name(&self) -> &'static str {
+        tr(match self {
+            ScalingCurve::Linear { .. } => "Linear",
+            ScalingCurve::Exponential { .. } => "Exponential",
+            ScalingCurve::Polynomial { .. } => "Polynomial",
+        })
+    }
+
+    /// The multiplier `elapsed` seconds into the run.
+    pub fn multiplier(&self, elapsed: f32) -> f32 {
+        let minutes = elapsed / 60.;
+        match *self {
+            ScalingCurve::Linear { per_minute } => 1. + per_minute * minutes,
+            ScalingCurve::Exponential { growth } => (1. + growth).powf(minutes),
+            ScalingCurve::Polynomial {
+                coefficient,
+                exponent,
+            } => 1. + coefficient * minutes.powf(exponent),
+        }
+    }
+
+    pub fn show_editor(&mut self, ui: &mut egui::Ui, id_source: &str) {
+        egui::ComboBox::from_id_source(id_source)
+            .selected_text(self.kind_name())
+            .show_ui(ui, |ui| {
+                for kind in ScalingCurve::KINDS {
+                    let is_selected = std::mem::discriminant(self) == std::mem::discriminant(&kind);
+                    if ui.selectable_label(is_selected, kind.kind_name()).clicked() && !is_selected
+                    {
+                        *self = kind;
+                    }
+                }
+            });
+        let derived = [
+            (
+                tr("After 10 minutes"),
+                format!("x{:.2}", self.multiplier(600.)),
+            ),
+            (
+                tr("After 30 minutes"),
+                format!("x{:.2}", self.multiplier(1800.)),
+            ),
+        ];
+        match self {
+            ScalingCurve::Linear { per_minute } => {
+                let slider = ui.add(egui::Slider::new(per_minute, 0. ..=1.).suffix(tr(" / min")));
+                stat_tooltip(slider, tr("Multiplier is 1 + rate × minutes."), &derived);
+            }
+            ScalingCurve::Exponential { growth } => {
+                let slider = ui.add(egui::Slider::new(growth, 0. ..=0.5).text(tr("growth")));
+                stat_tooltip(
+                    slider,
+                    tr("Multiplier is (1 + growth) ^ minutes."),
+                    &derived,
+                );
+            }
+            ScalingCurve::Polynomial {
+                coefficient,
+                exponent,
+            } => {
+                let explanation = tr("Multiplier is 1 + coef × minutes ^ exp.");
+                let slider = ui.add(egui::Slider::new(coefficient, 0. ..=0.5).text(tr("coef")));
+                stat_tooltip(slider, explanation, &derived);
+                let slider = ui.add(egui::Slider::new(exponent, 0.5..=4.).text(tr("exp")));
+                stat_tooltip(slider, explanation, &derived);
+            }
+        }
+    }
+}
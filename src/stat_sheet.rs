@@ -0,0 +1,132 @@
+//! Numbers derived from the Excellency and the spawner, for tuning the sliders. They're
+//! estimates for a single enemy with the spawner's current stats, ignoring buffs, armor,
+//! elites and archetypes.
+
+use crate::combat::AttackKind;
+use crate::durations;
+use crate::enemy::Distance;
+use crate::excellency::Excellency;
+use crate::i18n::{tr, trf};
+use crate::spawner::EnemySpawner;
+
+pub struct AttackStats {
+    pub kind: AttackKind,
+    pub dps: f32,
+    /// Seconds to kill one enemy on its own, infinite if the attack deals no damage.
+    pub time_to_kill: f32,
+    /// Shots fired while an enemy walks through the range.
+    pub shots_in_range: f32,
+}
+
+pub struct StatSheet {
+    pub attacks: Vec<AttackStats>,
+    pub enemy_hp: f32,
+    /// Seconds from spawning to reaching the Excellency.
+    pub time_to_reach: f32,
+    /// Whether an enemy walking in alone survives every shot on its way.
+    pub enemy_gets_through: bool,
+    /// HP lost per minute if enemies get through, after dodge and block.
+    pub intake_per_minute: f32,
+    /// The loadout last swapped in, none while the attacks are set up by hand.
+    pub loadout: Option<String>,
+    /// Whether the attacks changed since the loadout was swapped in.
+    pub loadout_modified: bool,
+}
+
+impl StatSheet {
+    pub fn compute(excellency: &Excellency, spawner: &EnemySpawner, elapsed: f32) -> Self {
+        let scaling = &spawner.scaling;
+        let enemy_hp = spawner.maximum_hp * scaling.hp.multiplier(elapsed);
+        let speed = spawner.speed * scaling.speed.multiplier(elapsed);
+        let damage = spawner.damage * scaling.damage.multiplier(elapsed);
+        let start = Distance::start().0;
+
+        let mut damage_on_the_way = 0.;
+        let attacks = AttackKind::ALL
+            .into_iter()
+            .map(|kind| {
+                let attack = excellency.attack(kind);
+                let shots_in_range = attack.range.min(start) / speed / attack.cycle();
+                damage_on_the_way += shots_in_range.floor() * attack.damage;
+                AttackStats {
+                    kind,
+                    dps: attack.effective_dps(),
+                    time_to_kill: match attack.damage > 0. {
+                        true => (enemy_hp / attack.damage).ceil() * attack.cycle(),
+                        false => f32::INFINITY,
+                    },
+                    shots_in_range,
+                }
+            })
+            .collect();
+        let enemy_gets_through = damage_on_the_way < enemy_hp;
+        let intake_per_minute = match enemy_gets_through {
+            true => {
+                spawner.spawns_per_minute(elapsed)
+                    * (damage - excellency.block).max(0.)
+                    * (1. - excellency.dodge_chance)
+            }
+            false => 0.,
+        };
+        Self {
+            attacks,
+            enemy_hp,
+            time_to_reach: start / speed,
+            enemy_gets_through,
+            intake_per_minute,
+            loadout: None,
+            loadout_modified: false,
+        }
+    }
+
+    pub fn show(&self, ui: &mut egui::Ui) {
+        let loadout = match (&self.loadout, self.loadout_modified) {
+            (Some(name), false) => name.clone(),
+            (Some(name), true) => trf("{} (modified)", std::slice::from_ref(name)),
+            (None, _) => tr("Custom").to_owned(),
+        };
+        ui.label(trf("Loadout: {}", &[loadout]));
+        ui.separator();
+        egui::Grid::new("stat_sheet_attacks").show(ui, |ui| {
+            for text in [
+                tr("Attack"),
+                tr("Effective DPS"),
+                tr("Time to kill"),
+                tr("Shots in range"),
+            ] {
+                ui.strong(text);
+            }
+            ui.end_row();
+            for attack in &self.attacks {
+                ui.label(attack.kind.name());
+                ui.label(format!("{:.1}", attack.dps));
+                ui.label(durations::format(attack.time_to_kill));
+                ui.label(format!("{:.1}", attack.shots_in_range));
+                ui.end_row();
+            }
+        });
+        ui.separator();
+        egui::Grid::new("stat_sheet_enemies").show(ui, |ui| {
+            for (name, value) in [
+                (tr("Enemy HP"), format!("{:.1}", self.enemy_hp)),
+                (tr("Time to arrive"), durations::format(self.time_to_reach)),
+                (
+                    tr("Enemies get through"),
+                    match self.enemy_gets_through {
+                        true => tr("Yes"),
+                        false => tr("No"),
+                    }
+                    .to_owned(),
+                ),
+                (
+                    tr("HP lost per minute"),
+                    format!("{:.1}", self.intake_per_minute),
+                ),
+            ] {
+                ui.label(name);
+                ui.label(value);
+                ui.end_row();
+            }
+        });
+    }
+}
@@ -0,0 +1,110 @@
+//! Passive talents, one picked out of three every `LEVELS_PER_TALENT` levels. A picked
+//! talent changes the Excellency's stats right away and stays for the rest of the run.
+
+use crate::character_sheet::{CharacterStat, Modifier};
+use crate::combat::AttackKind;
+use crate::excellency::Excellency;
+use crate::i18n::tr;
+use crate::rng::GameRng;
+
+pub const LEVELS_PER_TALENT: u32 = 5;
+/// Talents offered in one choice.
+pub const CHOICES: usize = 3;
+/// Max HP added by `Talent::Toughness`, healed right away too.
+const TOUGHNESS_HP: f32 = 20.;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Talent {
+    SharpEdge,
+    HeavyHand,
+    LongReach,
+    QuickDraw,
+    Regeneration,
+    Toughness,
+    Vampirism,
+    Bulwark,
+    Evasion,
+}
+
+impl Talent {
+    pub const ALL: [Talent; 9] = [
+        Talent::SharpEdge,
+        Talent::HeavyHand,
+        Talent::LongReach,
+        Talent::QuickDraw,
+        Talent::Regeneration,
+        Talent::Toughness,
+        Talent::Vampirism,
+        Talent::Bulwark,
+        Talent::Evasion,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Talent::SharpEdge => "Sharp edge",
+            Talent::HeavyHand => "Heavy hand",
+            Talent::LongReach => "Long reach",
+            Talent::QuickDraw => "Quick draw",
+            Talent::Regeneration => "Regeneration",
+            Talent::Toughness => "Toughness",
+            Talent::Vampirism => "Vampirism",
+            Talent::Bulwark => "Bulwark",
+            Talent::Evasion => "Evasion",
+        })
+    }
+
+    pub fn description(&self) -> &'static str {
+        tr(match self {
+            Talent::SharpEdge => "+15% basic attack damage",
+            Talent::HeavyHand => "+15% big attack damage",
+            Talent::LongReach => "+5 basic attack range",
+            Talent::QuickDraw => "-10% basic attack cooldown",
+            Talent::Regeneration => "+1 HP regenerated per second",
+            Talent::Toughness => "+20 maximum HP",
+            Talent::Vampirism => "+5% life steal",
+            Talent::Bulwark => "+2 block",
+            Talent::Evasion => "+5% dodge chance",
+        })
+    }
+
+    /// The one stat it changes and how.
+    pub fn modifier(&self) -> (CharacterStat, Modifier) {
+        match self {
+            Talent::SharpEdge => (
+                CharacterStat::Damage(AttackKind::Basic),
+                Modifier::Multiply(1.15),
+            ),
+            Talent::HeavyHand => (
+                CharacterStat::Damage(AttackKind::Big),
+                Modifier::Multiply(1.15),
+            ),
+            Talent::LongReach => (CharacterStat::Range(AttackKind::Basic), Modifier::Add(5.)),
+            Talent::QuickDraw => (
+                CharacterStat::Cooldown(AttackKind::Basic),
+                Modifier::Multiply(0.9),
+            ),
+            Talent::Regeneration => (CharacterStat::Regeneration, Modifier::Add(1.)),
+            Talent::Toughness => (CharacterStat::MaxHp, Modifier::Add(TOUGHNESS_HP)),
+            Talent::Vampirism => (CharacterStat::LifeSteal, Modifier::Add(0.05)),
+            Talent::Bulwark => (CharacterStat::Block, Modifier::Add(2.)),
+            Talent::Evasion => (CharacterStat::Dodge, Modifier::Add(0.05)),
+        }
+    }
+
+    pub fn apply(&self, excellency: &mut Excellency) {
+        let (stat, modifier) = self.modifier();
+        excellency.remember_base(stat);
+        stat.set(excellency, modifier.apply(stat.value(excellency)));
+        if *self == Talent::Toughness {
+            excellency.hp.heal(TOUGHNESS_HP);
+        }
+    }
+
+    /// `CHOICES` different talents drawn from the whole pool.
+    pub fn roll_choice(rng: &mut GameRng) -> Vec<Talent> {
+        let mut pool = Talent::ALL.to_vec();
+        (0..CHOICES)
+            .map(|_| pool.swap_remove(rng.next_index(pool.len())))
+            .collect()
+    }
+}
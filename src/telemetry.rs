@@ -0,0 +1,143 @@
+//! Opt-in anonymous gameplay statistics, compiled only with the `telemetry` feature.
+//!
+//! Every finished run is queued as a report without anything identifying the player, and
+//! the queue goes out in batches through `http`. Reports that fail to send stay queued,
+//! across sessions too, and are retried a minute later. Nothing is queued or sent until
+//! the player turns it on in the settings, turning it off drops what's queued.
+
+use std::sync::mpsc::Receiver;
+
+use crate::auras::Aura;
+use crate::http;
+use crate::i18n::{tr, trf};
+use crate::mutators::Mutator;
+use crate::records::AttackSnapshot;
+use crate::settings::TelemetrySettings;
+use crate::talents::Talent;
+
+pub const TELEMETRY_KEY: &str = "telemetry";
+/// Reports sent in one request.
+const BATCH: usize = 20;
+/// Oldest reports are dropped beyond this, for a server that's gone for good.
+const MAX_QUEUED: usize = 200;
+/// Seconds to wait after a failed batch.
+const RETRY_SECONDS: f64 = 60.;
+
+/// The Excellency's build at the end of a run.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Build {
+    pub basic_attack: AttackSnapshot,
+    pub big_attack: AttackSnapshot,
+    pub talents: Vec<Talent>,
+    pub auras: Vec<Aura>,
+    pub upgrades: usize,
+}
+
+/// A finished run as sent to the server.
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+pub struct Report {
+    pub version: String,
+    pub seed: u64,
+    pub wave: u32,
+    /// Seconds.
+    pub duration: f32,
+    pub kills: u32,
+    pub victorious: bool,
+    pub mutators: Vec<Mutator>,
+    pub build: Build,
+}
+
+#[derive(Default)]
+pub struct Telemetry {
+    queue: Vec<Report>,
+    /// How many of the oldest queued reports are on their way.
+    sending: Option<(usize, Receiver<Result<(), String>>)>,
+    /// Frame time before which no new batch goes out.
+    retry_at: f64,
+    last_error: Option<String>,
+}
+
+impl Telemetry {
+    /// The queue left over from the last session.
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        Self {
+            queue: storage
+                .and_then(|storage| eframe::get_value(storage, TELEMETRY_KEY))
+                .unwrap_or_default(),
+            ..Self::default()
+        }
+    }
+
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, TELEMETRY_KEY, &self.queue);
+    }
+
+    /// Queues `report`, nothing happens unless the player opted in.
+    pub fn record(&mut self, settings: &TelemetrySettings, report: Report) {
+        if !settings.enabled {
+            return;
+        }
+        self.queue.push(report);
+        if self.queue.len() > MAX_QUEUED {
+            // The batch on its way is at the front, the oldest one after it goes instead
+            let sending = self.sending.as_ref().map_or(0, |(count, _)| *count);
+            self.queue.remove(sending);
+        }
+    }
+
+    /// Picks up the batch on its way and sends the next one when it's time, call once per
+    /// frame with the frame time.
+    pub fn poll(&mut self, settings: &TelemetrySettings, now: f64) {
+        let finished = self
+            .sending
+            .as_ref()
+            .and_then(|(count, receiver)| Some((*count, receiver.try_recv().ok()?)));
+        if let Some((count, result)) = finished {
+            self.sending = None;
+            match result {
+                Ok(()) => {
+                    self.queue.drain(..count.min(self.queue.len()));
+                    self.last_error = None;
+                }
+                Err(err) => {
+                    log::warn!("Failed to send telemetry: {err}");
+                    self.last_error = Some(err);
+                    self.retry_at = now + RETRY_SECONDS;
+                }
+            }
+        }
+        if !settings.enabled {
+            self.queue.clear();
+            return;
+        }
+        if self.sending.is_some()
+            || self.queue.is_empty()
+            || settings.url.is_empty()
+            || now < self.retry_at
+        {
+            return;
+        }
+        let count = self.queue.len().min(BATCH);
+        let batch = self.queue[..count].to_vec();
+        let url = format!("{}/reports", settings.url.trim_end_matches('/'));
+        self.sending = Some((count, http::spawn(move || http::post(&url, &batch))));
+    }
+
+    pub fn show_status(&self, ui: &mut egui::Ui) {
+        ui.label(trf(
+            "Reports waiting to be sent: {}",
+            &[self.queue.len().to_string()],
+        ));
+        if let Some(err) = &self.last_error {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                trf("Telemetry unavailable: {}", std::slice::from_ref(err)),
+            );
+        }
+    }
+}
+
+/// What the settings say is sent, so opting in is an informed choice.
+pub fn describe() -> &'static str {
+    tr("Sends the wave reached, the run's length and the final build of every finished run. Nothing identifies you.")
+}
@@ -0,0 +1,80 @@
+//! Look and feel: a dark or light base plus accent colors for the stat bars.
+
+use egui::Color32;
+
+use crate::i18n::tr;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum ThemePreset {
+    Dark,
+    Light,
+}
+
+impl ThemePreset {
+    pub const ALL: [ThemePreset; 2] = [ThemePreset::Dark, ThemePreset::Light];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            ThemePreset::Dark => "Dark",
+            ThemePreset::Light => "Light",
+        })
+    }
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Theme {
+    pub preset: ThemePreset,
+    pub hp_bar: Color32,
+    pub cooldown_bar: Color32,
+    /// Fill of the enemies' distance bars.
+    pub enemy_bar: Color32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            preset: ThemePreset::Dark,
+            hp_bar: Color32::DARK_RED,
+            cooldown_bar: Color32::DARK_BLUE,
+            enemy_bar: Color32::from_rgb(96, 96, 96),
+        }
+    }
+}
+
+impl Theme {
+    pub fn visuals(&self) -> egui::Visuals {
+        match self.preset {
+            ThemePreset::Dark => egui::Visuals::dark(),
+            ThemePreset::Light => egui::Visuals::light(),
+        }
+    }
+
+    /// Returns whether anything changed, so the caller knows to re-apply the settings.
+    pub fn show_editor(&mut self, ui: &mut egui::Ui) -> bool {
+        let before = self.clone();
+        ui.horizontal(|ui| {
+            for preset in ThemePreset::ALL {
+                ui.selectable_value(&mut self.preset, preset, preset.name());
+            }
+        });
+        egui::Grid::new("theme_colors").show(ui, |ui| {
+            for (name, color) in [
+                (tr("HP bars"), &mut self.hp_bar),
+                (tr("Cooldown bars"), &mut self.cooldown_bar),
+                (tr("Enemy bars"), &mut self.enemy_bar),
+            ] {
+                ui.label(name);
+                ui.color_edit_button_srgba(color);
+                ui.end_row();
+            }
+        });
+        if ui.button(tr("Reset colors")).clicked() {
+            *self = Self {
+                preset: self.preset,
+                ..Default::default()
+            };
+        }
+        *self != before
+    }
+}
@@ -0,0 +1,75 @@
+//! Countdowns driving cooldowns, spawns and waves.
+
+/// Counts down from `total` seconds, then starts over unless it's `one_shot`.
+#[derive(serde::Deserialize, serde::Serialize, Clone, PartialEq)]
+pub struct Timer {
+    pub total: f32,
+    /// Seconds until it finishes next.
+    pub remaining: f32,
+    /// Pauses itself after finishing once instead of repeating.
+    pub one_shot: bool,
+    /// A paused timer ignores ticks.
+    pub paused: bool,
+}
+
+impl Timer {
+    /// A repeating timer, running and full.
+    pub fn new(total: f32) -> Self {
+        Self {
+            total,
+            remaining: total,
+            one_shot: false,
+            paused: false,
+        }
+    }
+
+    /// From 1 right after a reset down to 0 when it finishes.
+    pub fn remaining_fraction(&self) -> f32 {
+        self.remaining / self.total
+    }
+
+    /// Advances the timer and returns how many times it finished within `delta`.
+    pub fn tick(&mut self, delta: f32) -> u32 {
+        if self.paused {
+            return 0;
+        }
+        let mut completions = 0;
+        self.remaining -= delta;
+        while self.remaining <= 0. {
+            completions += 1;
+            self.remaining += self.total; // Necessary because of the case when we aren't exactly 0
+            if self.one_shot {
+                self.paused = true;
+                self.remaining = self.remaining.max(0.);
+                break;
+            }
+            if self.total <= 0. {
+                self.remaining = 0.;
+                break;
+            }
+        }
+        completions
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false
+    }
+
+    /// Starts the countdown over from `total`.
+    pub fn reset(&mut self) {
+        self.remaining = self.total;
+    }
+
+    /// Makes the timer finish on its next (unpaused) tick.
+    pub fn finish_now(&mut self) {
+        self.remaining = 0.;
+    }
+}
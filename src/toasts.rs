@@ -0,0 +1,63 @@
+//! Short notifications stacked in the bottom right corner, fading out on their own.
+
+/// Seconds a toast stays up, errors linger longer so there's time to read them.
+const INFO_SECONDS: f64 = 4.;
+const ERROR_SECONDS: f64 = 10.;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Error,
+}
+
+struct Toast {
+    kind: ToastKind,
+    text: String,
+    /// `None` until first shown, toasts pushed outside of a frame don't know the time yet.
+    expires_at: Option<f64>,
+}
+
+#[derive(Default)]
+pub struct Toasts(Vec<Toast>);
+
+impl Toasts {
+    pub fn push(&mut self, kind: ToastKind, text: impl Into<String>) {
+        self.0.push(Toast {
+            kind,
+            text: text.into(),
+            expires_at: None,
+        });
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = ctx.input(|input| input.time);
+        for toast in &mut self.0 {
+            let lifetime = match toast.kind {
+                ToastKind::Info => INFO_SECONDS,
+                ToastKind::Error => ERROR_SECONDS,
+            };
+            toast.expires_at.get_or_insert(now + lifetime);
+        }
+        self.0.retain(|toast| toast.expires_at > Some(now));
+        if self.0.is_empty() {
+            return;
+        }
+
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-8., -8.])
+            .interactable(false)
+            .show(ctx, |ui| {
+                for toast in &self.0 {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(320.);
+                        match toast.kind {
+                            ToastKind::Info => ui.label(&toast.text),
+                            ToastKind::Error => {
+                                ui.colored_label(ui.visuals().error_fg_color, &toast.text)
+                            }
+                        };
+                    });
+                }
+            });
+    }
+}
@@ -0,0 +1,26 @@
+//! Treasure chests: now and then a wave starts with one part way down the lane. It never
+//! attacks, it flees back towards the spawn and is gone for good unless it's broken open in
+//! time, paying a big bounty and a relic. Like every relic drop, that one only comes in runs
+//! counting towards the records, so a Sandbox chest pays just the gold.
+
+use crate::rng::GameRng;
+
+/// Chance of a wave starting with a chest.
+pub const CHANCE: f32 = 0.25;
+/// Where a chest shows up.
+pub const START: f32 = 60.;
+/// Seconds it has to get away.
+pub const LIFETIME: f32 = 15.;
+/// Times the spawner's HP it has.
+pub const HP_FACTOR: f32 = 4.;
+/// Regular enemies its bounty is worth.
+pub const BOUNTY_FACTOR: u32 = 25;
+/// Offsets the seed of the chest rolls from the rest.
+const STREAM: u64 = 2 << 32;
+
+/// Whether `wave` starts with a chest, on a stream of its own so the rest of the seed plays
+/// out the same either way.
+pub fn rolls(seed: u64, wave: u32) -> bool {
+    let mut rng = GameRng::new(seed.wrapping_add(STREAM).wrapping_add(wave as u64));
+    rng.chance(CHANCE)
+}
@@ -0,0 +1,332 @@
+//! Everything drawn on screen. The simulation types know nothing about the panels; the
+//! panels read and edit the `GameState` directly.
+
+mod ability_bar;
+mod battlefield;
+mod boss_bar;
+pub mod enemies;
+mod player;
+mod spawner;
+mod windows;
+
+use egui::{Key, Modifiers};
+
+use crate::app::GameState;
+use crate::ascendancy::ASCENDANCY_KEY;
+use crate::audio::{SoundEvent, SoundQueue};
+#[cfg(feature = "discord")]
+use crate::discord::Presence;
+use crate::durations;
+use crate::enemy::EnemyKind;
+use crate::events::GameEvent;
+use crate::i18n::{self, tr, trf};
+use crate::profiles::{self, PROFILES_KEY};
+use crate::records::RECORDS_KEY;
+#[cfg(feature = "discord")]
+use crate::saves;
+use crate::settings::SETTINGS_KEY;
+use crate::toasts::ToastKind;
+#[cfg(feature = "discord")]
+use crate::waves::WavePhase;
+
+/// The panels of the touch layout, the desktop layout shows them side by side.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum Tab {
+    #[default]
+    Player,
+    Spawner,
+    Enemies,
+    Battlefield,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Player, Tab::Spawner, Tab::Enemies, Tab::Battlefield];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Tab::Player => "Player",
+            Tab::Spawner => "Spawner",
+            Tab::Enemies => "Enemies",
+            Tab::Battlefield => "Battlefield",
+        })
+    }
+}
+
+impl GameState {
+    #[cfg(feature = "discord")]
+    fn presence(&self) -> Presence {
+        let state = match (self.defeated, self.waves.phase) {
+            (true, _) => tr("Defeated"),
+            _ if self.victorious => tr("Victorious"),
+            (false, WavePhase::Active) => tr("Fighting"),
+            (false, WavePhase::Break) => tr("Between waves"),
+        };
+        Presence {
+            details: trf("Wave {}", &[self.waves.number.to_string()]),
+            state: state.to_owned(),
+        }
+    }
+
+    /// Routes this frame's key presses either into a pending rebinding or to their actions.
+    fn handle_keys(&mut self, ctx: &egui::Context) {
+        if let Some(action) = self.rebinding {
+            let pressed = ctx.input(|i| {
+                i.events.iter().find_map(|event| match event {
+                    egui::Event::Key {
+                        key, pressed: true, ..
+                    } => Some(*key),
+                    _ => None,
+                })
+            });
+            match pressed {
+                Some(egui::Key::Escape) => self.rebinding = None,
+                Some(key) => {
+                    self.settings.keybindings.bind(action, key);
+                    self.rebinding = None;
+                }
+                None => {}
+            }
+            return;
+        }
+
+        // Typing a save name or seed shouldn't pause the game, nor undo an edit while the
+        // text field undoes its own typing
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        for action in ctx.input(|i| self.settings.keybindings.pressed(i)) {
+            self.perform(action);
+        }
+        if !self.mode.free_sliders() {
+            return;
+        }
+        // Ctrl+Z also matches with Shift held, so redo goes first
+        if ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z)) {
+            self.redo();
+        } else if ctx.input_mut(|i| i.consume_key(Modifiers::COMMAND, Key::Z)) {
+            self.undo();
+        }
+    }
+
+    /// One panel at a time behind a row of tabs, for narrow touch screens.
+    fn show_touch_layout(&mut self, ctx: &egui::Context) {
+        self.show_boss_bars(ctx);
+        egui::TopBottomPanel::top("tabs").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for tab in Tab::ALL {
+                    ui.selectable_value(&mut self.tab, tab, tab.name());
+                }
+            });
+        });
+        egui::TopBottomPanel::bottom("ability_bar").show(ctx, |ui| {
+            egui::ScrollArea::horizontal().show(ui, |ui| self.show_ability_bar(ui));
+        });
+        egui::CentralPanel::default().show(ctx, |ui| match self.tab {
+            Tab::Player => {
+                egui::ScrollArea::vertical().show(ui, |ui| self.show_player_panel(ui));
+            }
+            Tab::Spawner => {
+                egui::ScrollArea::vertical().show(ui, |ui| self.show_spawner_panel(ui));
+            }
+            // Both scroll on their own
+            Tab::Enemies => self.show_enemies_panel(ui),
+            Tab::Battlefield => self.show_battlefield_panel(ui),
+        });
+    }
+
+    fn show_desktop_layout(&mut self, ctx: &egui::Context) {
+        self.show_boss_bars(ctx);
+        egui::SidePanel::right("right_panel").show(ctx, |ui| {
+            self.show_spawner_panel(ui);
+            ui.separator();
+            self.show_enemies_panel(ui);
+        });
+        egui::TopBottomPanel::bottom("ability_bar").show(ctx, |ui| self.show_ability_bar(ui));
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.show_player_panel(ui);
+            ui.separator();
+            self.show_battlefield_panel(ui);
+        });
+    }
+}
+
+impl eframe::App for GameState {
+    /// Called by the frame work to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        let key = |key| profiles::key_of(self.profile, key);
+        eframe::set_value(storage, &key(eframe::APP_KEY), self);
+        eframe::set_value(storage, &key(SETTINGS_KEY), &self.settings);
+        eframe::set_value(storage, &key(RECORDS_KEY), &self.records);
+        eframe::set_value(storage, &key(ASCENDANCY_KEY), &self.ascendancy);
+        eframe::set_value(storage, PROFILES_KEY, &self.profiles);
+        #[cfg(feature = "telemetry")]
+        self.telemetry.save(storage);
+    }
+
+    #[cfg(feature = "gamepad")]
+    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        for action in self.gamepad.poll(&self.settings.gamepad, raw_input) {
+            self.perform(action);
+        }
+    }
+
+    #[cfg(feature = "discord")]
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.discord.clear();
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per second.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        // Put your widgets into a `SidePanel`, `TopBottomPanel`, `CentralPanel`, `Window` or `Area`.
+        // For inspiration and more examples, go to https://emilk.github.io/egui
+
+        i18n::set_language(self.settings.language);
+        durations::set_format(self.settings.duration_format);
+        self.handle_keys(ctx);
+        let (focused, minimized) = ctx.input(|input| {
+            let minimized = input.viewport().minimized.unwrap_or(false);
+            (input.focused, minimized)
+        });
+        self.auto_pause(focused);
+        self.low_power = minimized && self.settings.low_power_background;
+        let milestone_was_cleared = self.milestone_cleared;
+        let tick_started = web_time::Instant::now();
+        self.tick(ctx);
+        let ui_started = web_time::Instant::now();
+        let frame_time = ctx.input(|input| input.unstable_dt);
+        let mut sounds = SoundQueue::default();
+        for event in self.events.drain(..) {
+            self.combat_log.record(self.elapsed, &event);
+            self.floating_texts.record(&event);
+            self.dying.record(&event);
+            self.particles.record(&event);
+            self.juice.record(&event, self.excellency.hp.maximum);
+            if let Some(sound) = SoundEvent::for_event(&event) {
+                sounds.push(sound);
+            }
+            match event {
+                GameEvent::WaveStarted { wave } => self
+                    .toasts
+                    .push(ToastKind::Info, trf("Wave {} started", &[wave.to_string()])),
+                GameEvent::IncidentStruck(incident) => {
+                    self.toasts.push(ToastKind::Info, incident.announcement())
+                }
+                GameEvent::EnemySpawned(enemy) if enemy.kind == EnemyKind::Chest => {
+                    self.toasts.push(
+                        ToastKind::Info,
+                        tr("A treasure chest appeared, break it before it flees!"),
+                    )
+                }
+                GameEvent::RelicFound(relic) => self.toasts.push(
+                    ToastKind::Info,
+                    trf("Found a relic: {}", &[relic.describe()]),
+                ),
+                GameEvent::BossPhaseEntered { boss, action, .. } => {
+                    self.toasts.push(ToastKind::Info, action.describe(&boss))
+                }
+                _ => {}
+            }
+        }
+        // Only the boss clearing the milestone announces it, not every one after
+        if self.milestone_cleared && !milestone_was_cleared {
+            self.toasts.push(
+                ToastKind::Info,
+                tr("The milestone boss fell, New Game+ is unlocked."),
+            );
+        }
+        self.dying.tick(frame_time);
+        self.particles.tick(frame_time);
+        self.juice.tick(frame_time);
+        let sound = &self.settings.sound;
+        self.audio.play(sounds.drain(), sound.effects(focused));
+        self.audio
+            .update_music(self.music_mood(), frame_time, sound.music(focused));
+
+        if self.defeated {
+            self.show_defeat_window(ctx);
+        } else if self.victorious {
+            self.show_victory_window(ctx);
+        } else {
+            self.show_talent_window(ctx);
+        }
+        if self.show_settings_window {
+            self.show_settings_window(ctx);
+        }
+        if self.show_history_window {
+            self.show_history_window(ctx);
+        }
+        if self.show_stat_sheet_window {
+            self.show_stat_sheet_window(ctx);
+        }
+        if self.show_character_sheet_window {
+            self.show_character_sheet_window(ctx);
+        }
+        if self.show_campaign_window {
+            self.show_campaign_window(ctx);
+        }
+        if self.show_automation_window {
+            self.show_automation_window(ctx);
+        }
+        if self.show_ascendancy_window {
+            self.show_ascendancy_window(ctx);
+        }
+        if self.show_relics_window {
+            self.show_relics_window(ctx);
+        }
+        self.show_inspection_window(ctx);
+        #[cfg(feature = "discord")]
+        {
+            let started_at = saves::now() as i64 * 1000 - (self.elapsed * 1000.) as i64;
+            self.discord.update(self.presence(), started_at);
+        }
+        #[cfg(feature = "telemetry")]
+        {
+            let now = ctx.input(|input| input.time);
+            self.telemetry.poll(&self.settings.telemetry, now);
+        }
+        #[cfg(feature = "leaderboard")]
+        {
+            self.leaderboard.poll();
+            if self.show_leaderboard_window {
+                self.show_leaderboard_window(ctx);
+            }
+        }
+        if self.show_save_window {
+            if let Some(storage) = frame.storage_mut() {
+                self.show_save_window(ctx, storage);
+            }
+        }
+        if self.show_profile_window {
+            if let Some(storage) = frame.storage_mut() {
+                self.show_profile_window(ctx, storage);
+            }
+        }
+        #[cfg(all(feature = "hot-reload", not(target_arch = "wasm32")))]
+        self.hot_reload();
+        self.toasts.show(ctx);
+
+        let touch_layout = self.settings.touch_layout(ctx);
+        if touch_layout != self.touch_layout {
+            self.touch_layout = touch_layout;
+            self.settings.apply(ctx, touch_layout);
+        }
+        if touch_layout {
+            self.show_touch_layout(ctx);
+        } else {
+            self.show_desktop_layout(ctx);
+        }
+        self.profiler.show(ctx);
+        self.profiler.record(
+            frame_time,
+            ui_started - tick_started,
+            ui_started.elapsed(),
+            self.enemies.len(),
+        );
+
+        let idle = self.paused || self.defeated || self.victorious;
+        match self.settings.repaint_after(idle, self.low_power) {
+            Some(after) => ctx.request_repaint_after(after),
+            None => ctx.request_repaint(),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use crate::app::GameState;
+use crate::combat::{AttackKind, AttackPhase, BasicAttack};
+use crate::durations;
+use crate::i18n::{tr, trf};
+use crate::settings::Action;
+use crate::waves::WavePhase;
+use crate::widgets::ability_slot;
+
+impl GameState {
+    /// Every ability in a row, the castable ones clickable and labelled with their hotkeys.
+    pub(crate) fn show_ability_bar(&mut self, ui: &mut egui::Ui) {
+        let mut cast = None;
+        ui.horizontal(|ui| {
+            for (kind, action) in [
+                (AttackKind::Basic, Action::CastBasicAttack),
+                (AttackKind::Big, Action::CastBigAttack),
+            ] {
+                let attack = self.excellency.attack(kind);
+                let slot = self
+                    .ability_slot(
+                        ui,
+                        kind.name(),
+                        action,
+                        cooldown_left(attack),
+                        attack.can_cast(),
+                    )
+                    .on_hover_text(trf(
+                        "Cooldown: {}",
+                        &[durations::format(attack.cooldown_timer.total)],
+                    ));
+                if slot.clicked() {
+                    cast = Some(action);
+                }
+            }
+            let ultimate = &self.excellency.ultimate;
+            let charge = &ultimate.charge;
+            let slot = self
+                .ability_slot(
+                    ui,
+                    tr("Ultimate"),
+                    Action::CastUltimate,
+                    1. - charge.fraction(),
+                    charge.is_full(),
+                )
+                .on_hover_text(trf(
+                    "Charge: {} / {}",
+                    &[
+                        format!("{:.0}", charge.current),
+                        format!("{:.0}", charge.required),
+                    ],
+                ));
+            if slot.clicked() {
+                cast = Some(Action::CastUltimate);
+            }
+            #[cfg(feature = "scripting")]
+            for (name, cooldown_left) in self.scripts.cooldowns() {
+                ability_slot(ui, name, None, cooldown_left, false)
+                    .on_hover_text(tr("Scripted, casts on its own."));
+            }
+            ui.separator();
+            ui.vertical(|ui| {
+                ui.label(trf(
+                    "Next wave in {}",
+                    &[durations::format(self.waves.countdown())],
+                ));
+                let gold = self.early_call_gold();
+                let mut text = trf("Call wave early (+{} gold)", &[gold.to_string()]);
+                if let Some(key) = self.settings.keybindings.key(Action::CallWave) {
+                    text = format!("{text} [{}]", key.name());
+                }
+                let button = ui
+                    .add_enabled(
+                        self.waves.phase == WavePhase::Break,
+                        egui::Button::new(text),
+                    )
+                    .on_hover_text(tr("Skips the rest of the break for gold."))
+                    .on_disabled_hover_text(tr("Only between waves."));
+                if button.clicked() {
+                    cast = Some(Action::CallWave);
+                }
+            });
+        });
+        if let Some(action) = cast {
+            self.perform(action);
+        }
+    }
+
+    fn ability_slot(
+        &self,
+        ui: &mut egui::Ui,
+        name: &str,
+        action: Action,
+        cooldown_left: f32,
+        castable: bool,
+    ) -> egui::Response {
+        let key = self.settings.keybindings.key(action).map(|key| key.name());
+        ability_slot(ui, name, key, cooldown_left, castable)
+    }
+}
+
+/// Only the cooldown sweeps, a winding up or held attack shows as ready.
+fn cooldown_left(attack: &BasicAttack) -> f32 {
+    match attack.phase {
+        AttackPhase::Cooldown => attack.cooldown_timer.remaining_fraction(),
+        AttackPhase::WindingUp { .. } | AttackPhase::Ready => 0.,
+    }
+}
@@ -0,0 +1,242 @@
+use egui::{Color32, Pos2, Sense};
+
+use crate::app::GameState;
+use crate::combat_log::FloatingTexts;
+use crate::crowd_control::CrowdControl;
+use crate::dying::DyingEnemies;
+use crate::enemy::{Distance, Enemy, EnemyKind};
+use crate::excellency::Excellency;
+use crate::export;
+use crate::graphs;
+use crate::i18n::{tr, trf};
+use crate::particles::Particles;
+use crate::toasts::ToastKind;
+use crate::world::{Components, Entity};
+
+use super::enemies::{show_enemy_menu, EnemyAction};
+
+/// Ring around the focused enemy.
+pub(super) const FOCUS_COLOR: Color32 = Color32::LIGHT_BLUE;
+/// Ring around the marked enemies.
+pub(super) const MARK_COLOR: Color32 = Color32::from_rgb(0, 220, 220);
+
+/// What's painted over the lane besides the Excellency and the enemies themselves.
+struct Decorations<'a> {
+    dying: &'a DyingEnemies,
+    particles: &'a Particles,
+    floating_texts: &'a mut FloatingTexts,
+    shake: egui::Vec2,
+    focused: Option<Entity>,
+    marked: &'a [Entity],
+    crowd_control: &'a Components<CrowdControl>,
+}
+
+/// Paints the lane from the Excellency (left) to the spawn point (right), with the attack
+/// ranges as arcs and every enemy as a dot that shrinks as it loses HP, along with the purely
+/// visual fades, particles and texts. Returns the lane's response and the enemy under the
+/// cursor.
+fn show_battlefield(
+    ui: &mut egui::Ui,
+    excellency: &Excellency,
+    enemies: &[Enemy],
+    decorations: Decorations<'_>,
+) -> (egui::Response, Option<Entity>) {
+    const HEIGHT: f32 = 160.;
+    const MARGIN: f32 = 24.;
+
+    let size = egui::vec2(ui.available_width(), HEIGHT);
+    let (response, painter) = ui.allocate_painter(size, Sense::click());
+    let rect = response.rect;
+    let painter = painter.with_clip_rect(rect);
+    painter.rect_stroke(rect, 0., ui.visuals().widgets.noninteractive.bg_stroke);
+
+    let scale = (rect.width() - 2. * MARGIN) / Distance::start().0;
+    let origin = Pos2::new(rect.left() + MARGIN, rect.center().y) + decorations.shake;
+    let position = |distance: &Distance| origin + egui::vec2(distance.0 * scale, 0.);
+
+    for &aura in &excellency.auras {
+        let radius = excellency.aura_radius(aura) * scale;
+        painter.circle_filled(origin, radius, aura.color().gamma_multiply(0.2));
+        painter.circle_stroke(origin, radius, egui::Stroke::new(1., aura.color()));
+    }
+    for (attack, color) in [
+        (&excellency.basic_attack, Color32::LIGHT_BLUE),
+        (&excellency.big_attack, Color32::LIGHT_RED),
+    ] {
+        painter.circle_stroke(origin, attack.range * scale, egui::Stroke::new(1.5, color));
+    }
+    painter.circle_filled(origin, 12., Color32::GOLD);
+    painter.circle_stroke(
+        origin,
+        12.,
+        egui::Stroke::new(2., ui.visuals().strong_text_color()),
+    );
+
+    // Dead ones swell a little as they fade, under the living
+    for (enemy, faded) in decorations.dying.iter() {
+        painter.circle_filled(
+            position(&enemy.distance),
+            enemy.radius() * (1. + faded),
+            enemy_color(enemy).gamma_multiply(1. - faded),
+        );
+    }
+
+    let hover = response.hover_pos();
+    let mut hovered = None;
+    for enemy in enemies {
+        let center = position(&enemy.distance);
+        let radius = enemy.radius();
+        painter.circle_filled(center, radius, enemy_color(enemy));
+        if decorations.focused == Some(enemy.id) {
+            paint_crosshair(&painter, center, radius + 4.);
+        }
+        if decorations.marked.contains(&enemy.id) {
+            painter.circle_stroke(center, radius + 6., egui::Stroke::new(1.5, MARK_COLOR));
+        }
+        let held = decorations.crowd_control.get(enemy.id);
+        if let Some(control) = held.and_then(CrowdControl::control) {
+            painter.circle_stroke(center, radius + 1., egui::Stroke::new(2., control.color()));
+        }
+        if hover.is_some_and(|hover| hover.distance(center) <= radius + 2.) {
+            hovered = Some(enemy.id);
+            painter.circle_stroke(center, radius + 2., egui::Stroke::new(1.5, Color32::WHITE));
+        }
+    }
+    let at = |distance| position(&Distance(distance));
+    decorations.particles.paint(&painter, at);
+    let now = ui.input(|input| input.time);
+    decorations.floating_texts.paint(&painter, now, at);
+    (response, hovered)
+}
+
+/// Marks the focused enemy: a ring with four ticks pointing in at it.
+fn paint_crosshair(painter: &egui::Painter, center: Pos2, radius: f32) {
+    let stroke = egui::Stroke::new(2., FOCUS_COLOR);
+    painter.circle_stroke(center, radius, stroke);
+    for direction in [egui::Vec2::X, -egui::Vec2::X, egui::Vec2::Y, -egui::Vec2::Y] {
+        painter.line_segment(
+            [
+                center + direction * (radius - 3.),
+                center + direction * (radius + 4.),
+            ],
+            stroke,
+        );
+    }
+}
+
+const CHEST_COLOR: Color32 = Color32::from_rgb(180, 120, 40);
+
+fn enemy_color(enemy: &Enemy) -> Color32 {
+    match (enemy.kind, enemy.is_elite()) {
+        (EnemyKind::Healer, _) => Color32::GREEN,
+        (EnemyKind::Chest, _) => CHEST_COLOR,
+        (EnemyKind::Grunt, true) => Color32::GOLD,
+        (EnemyKind::Grunt, false) => Color32::RED,
+    }
+}
+
+/// A thin strip with one tick per enemy along the distance axis, colored from red (almost
+/// dead) to green (full HP), over the shaded attack ranges.
+pub(super) fn show_minimap(ui: &mut egui::Ui, excellency: &Excellency, enemies: &[Enemy]) {
+    let size = egui::vec2(ui.available_width(), 20.);
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let rect = response.rect;
+    let x = |distance: f32| rect.left() + distance / Distance::start().0 * rect.width();
+
+    let attacks = [
+        (tr("Basic"), &excellency.basic_attack, Color32::LIGHT_BLUE),
+        (tr("Big"), &excellency.big_attack, Color32::LIGHT_RED),
+    ];
+    for (_, attack, color) in &attacks {
+        let range = egui::Rect::from_x_y_ranges(rect.left()..=x(attack.range), rect.y_range());
+        painter.rect_filled(range, 0., color.gamma_multiply(0.15));
+    }
+    painter.rect_stroke(rect, 0., ui.visuals().widgets.noninteractive.bg_stroke);
+
+    for enemy in enemies {
+        let fraction = (enemy.hp.current / enemy.hp.maximum).clamp(0., 1.);
+        let color = Color32::from_rgb((255. * (1. - fraction)) as u8, (255. * fraction) as u8, 0);
+        let x = x(enemy.distance.0);
+        painter.line_segment(
+            [Pos2::new(x, rect.top()), Pos2::new(x, rect.bottom())],
+            egui::Stroke::new(2., color),
+        );
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        for (name, attack, color) in attacks {
+            let in_range = enemies
+                .iter()
+                .filter(|enemy| enemy.distance.0 <= attack.range)
+                .count();
+            ui.colored_label(
+                color,
+                trf("{} in range: {}", &[name.to_owned(), in_range.to_string()]),
+            );
+        }
+    });
+}
+
+impl GameState {
+    /// Saves `export_csv` as a file named after the seed, or downloads it on the web.
+    fn export_csv_file(&mut self) {
+        let file_name = format!("your_excellency_{}.csv", self.rng.seed());
+        match export::save(&file_name, &self.export_csv()) {
+            Ok(path) => self
+                .toasts
+                .push(ToastKind::Info, trf("Exported to {}", &[path])),
+            Err(err) => self
+                .toasts
+                .push(ToastKind::Error, trf("Export failed: {}", &[err])),
+        }
+    }
+
+    pub(crate) fn show_battlefield_panel(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing(tr("Graphs"), |ui| {
+            self.hp_history.show(ui);
+            graphs::show_wave_damage(ui, &self.wave_damage);
+            ui.label(tr("Damage by source"));
+            self.damage_by_source.show(ui, &self.true_damage_by_source);
+        });
+        ui.collapsing(tr("Combat log"), |ui| {
+            self.combat_log.show(ui);
+            if ui.button(tr("Export CSV")).clicked() {
+                self.export_csv_file();
+            }
+        });
+        let shake = self
+            .juice
+            .shake(&self.settings.juice, self.settings.reduced_motion);
+        let decorations = Decorations {
+            dying: &self.dying,
+            particles: &self.particles,
+            floating_texts: &mut self.floating_texts,
+            shake,
+            focused: self.focused,
+            marked: &self.marked,
+            crowd_control: &self.crowd_control,
+        };
+        let (lane, hovered) = show_battlefield(ui, &self.excellency, &self.enemies, decorations);
+        self.hovered = hovered;
+        // The first click of a double click focuses, the second one takes it back
+        if let (true, Some(id)) = (lane.clicked(), hovered) {
+            self.apply_enemy_action(id, EnemyAction::ToggleFocus);
+        }
+        if let (true, Some(id)) = (lane.double_clicked(), hovered) {
+            self.apply_enemy_action(id, EnemyAction::Inspect);
+        }
+        if lane.secondary_clicked() {
+            self.menu_enemy = hovered;
+        }
+        if let Some(id) = self.menu_enemy {
+            let focused = self.focused == Some(id);
+            let marked = self.marked.contains(&id);
+            let sandbox = self.mode.free_sliders();
+            let mut picked = None;
+            lane.context_menu(|ui| picked = show_enemy_menu(ui, focused, marked, sandbox));
+            if let Some(action) = picked {
+                self.apply_enemy_action(id, action);
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use egui::Color32;
+
+use crate::app::GameState;
+use crate::boss_phases::THRESHOLDS;
+use crate::i18n::trf;
+
+const BAR_COLOR: Color32 = Color32::from_rgb(170, 30, 30);
+
+impl GameState {
+    /// A bar across the top for every boss on the field, marked where its phases start.
+    pub(crate) fn show_boss_bars(&self, ctx: &egui::Context) {
+        if !self.enemies.iter().any(|enemy| enemy.is_boss) {
+            return;
+        }
+        egui::TopBottomPanel::top("boss_bars").show(ctx, |ui| {
+            for boss in self.enemies.iter().filter(|enemy| enemy.is_boss) {
+                let phase = self.boss_phases.get(boss.id).map_or(0, |phase| phase.phase);
+                ui.horizontal(|ui| {
+                    ui.strong(boss.display_name());
+                    ui.weak(trf(
+                        "Phase {} of {}",
+                        &[(phase + 1).to_string(), (THRESHOLDS.len() + 1).to_string()],
+                    ));
+                });
+                let hp = &boss.hp;
+                let bar = ui.add(
+                    egui::ProgressBar::new(hp.current / hp.maximum)
+                        .text(format!("{:.0} / {:.0}", hp.current, hp.maximum))
+                        .fill(BAR_COLOR),
+                );
+                let stroke = egui::Stroke::new(2., ui.visuals().strong_text_color());
+                for threshold in THRESHOLDS {
+                    let x = bar.rect.left() + bar.rect.width() * threshold;
+                    ui.painter().vline(x, bar.rect.y_range(), stroke);
+                }
+            }
+        });
+    }
+}
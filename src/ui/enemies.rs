@@ -0,0 +1,422 @@
+use egui::Color32;
+
+use crate::app::GameState;
+use crate::combat::AttackKind;
+use crate::crowd_control::CrowdControl;
+use crate::durations;
+use crate::effects::{Effects, Stat};
+use crate::enemy::{Enemy, EnemyKind};
+use crate::excellency::Excellency;
+use crate::i18n::{tr, trf};
+use crate::widgets::{info_popup, stat_tooltip};
+use crate::world::Entity;
+
+use super::battlefield::{show_minimap, FOCUS_COLOR, MARK_COLOR};
+
+/// Rows rendered in the enemies panel, the rest are only counted.
+const MAX_LISTED_ENEMIES: usize = 500;
+
+/// Order of the enemies panel. Distance and HP list the weakest position first, damage and
+/// speed the most dangerous enemy first.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum EnemySort {
+    #[default]
+    Distance,
+    Hp,
+    Damage,
+    Speed,
+}
+
+impl EnemySort {
+    const ALL: [EnemySort; 4] = [
+        EnemySort::Distance,
+        EnemySort::Hp,
+        EnemySort::Damage,
+        EnemySort::Speed,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            EnemySort::Distance => "Distance",
+            EnemySort::Hp => "HP",
+            EnemySort::Damage => "Damage",
+            EnemySort::Speed => "Speed",
+        })
+    }
+
+    pub fn sort(&self, indices: &mut [usize], enemies: &[Enemy]) {
+        let key = |index: usize| {
+            let enemy = &enemies[index];
+            match self {
+                EnemySort::Distance => enemy.distance.0,
+                EnemySort::Hp => enemy.hp.current,
+                EnemySort::Damage => -enemy.damage,
+                EnemySort::Speed => -enemy.speed,
+            }
+        };
+        indices.sort_by(|&a, &b| key(a).partial_cmp(&key(b)).expect("Compared two f32's"));
+    }
+}
+
+/// Which enemies the enemies panel lists.
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum EnemyFilter {
+    #[default]
+    All,
+    InBasicRange,
+    Elites,
+}
+
+impl EnemyFilter {
+    const ALL: [EnemyFilter; 3] = [
+        EnemyFilter::All,
+        EnemyFilter::InBasicRange,
+        EnemyFilter::Elites,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            EnemyFilter::All => "All",
+            EnemyFilter::InBasicRange => "In basic attack range",
+            EnemyFilter::Elites => "Elites",
+        })
+    }
+
+    pub fn matches(&self, enemy: &Enemy, excellency: &Excellency) -> bool {
+        match self {
+            EnemyFilter::All => true,
+            EnemyFilter::InBasicRange => enemy.distance.0 <= excellency.basic_attack.range,
+            EnemyFilter::Elites => enemy.is_elite(),
+        }
+    }
+}
+
+/// The full breakdown of an enemy, too long for its row in the enemies panel.
+fn show_enemy_details(
+    ui: &mut egui::Ui,
+    enemy: &Enemy,
+    debuffs: Option<&Effects>,
+    crowd_control: Option<&CrowdControl>,
+) {
+    if !enemy.name.is_empty() {
+        ui.strong(&enemy.name);
+    }
+    if !enemy.archetype.is_empty() {
+        ui.weak(&enemy.archetype);
+    }
+    if enemy.is_elite() {
+        let modifiers: Vec<_> = enemy.modifiers.iter().map(|m| m.name()).collect();
+        ui.colored_label(Color32::GOLD, trf("ELITE: {}", &[modifiers.join(", ")]));
+    }
+    ui.label(trf("Distance: {}", &[format!("{:.1}", enemy.distance.0)]));
+    ui.label(trf(
+        "HP: {}",
+        &[format!("{:.1}/{:.1}", enemy.hp.current, enemy.hp.maximum)],
+    ));
+    if enemy.armor > 0. {
+        ui.label(trf("Armor: {}", &[enemy.armor.to_string()]));
+    }
+    if enemy.shield > 0. {
+        ui.label(trf("Shield: {}", &[format!("{:.1}", enemy.shield)]));
+    }
+    if let Some(crowd_control) = crowd_control {
+        crowd_control.show(ui);
+    }
+    if let Some(debuffs) = debuffs {
+        debuffs.show(ui);
+    }
+}
+
+fn show_reach(ui: &mut egui::Ui, reaches: bool, status: String) {
+    let color = match reaches {
+        true => Color32::GREEN,
+        false => ui.visuals().weak_text_color(),
+    };
+    ui.colored_label(color, status);
+}
+
+/// Something done to one enemy from its context menu.
+#[derive(Clone, Copy)]
+pub(crate) enum EnemyAction {
+    ToggleFocus,
+    Inspect,
+    ToggleMark,
+    /// Only in sandbox.
+    Kill,
+    /// Only in sandbox.
+    Heal,
+}
+
+/// The context menu of an enemy row or dot, returns the action picked.
+pub(super) fn show_enemy_menu(
+    ui: &mut egui::Ui,
+    focused: bool,
+    marked: bool,
+    sandbox: bool,
+) -> Option<EnemyAction> {
+    let mut actions = vec![
+        (
+            match focused {
+                true => tr("Stop focusing"),
+                false => tr("Focus fire"),
+            },
+            EnemyAction::ToggleFocus,
+        ),
+        (tr("Inspect"), EnemyAction::Inspect),
+        (
+            match marked {
+                true => tr("Unmark"),
+                false => tr("Mark"),
+            },
+            EnemyAction::ToggleMark,
+        ),
+    ];
+    if sandbox {
+        actions.push((tr("Kill"), EnemyAction::Kill));
+        actions.push((tr("Heal to full"), EnemyAction::Heal));
+    }
+    let mut picked = None;
+    for (name, action) in actions {
+        if ui.button(name).clicked() {
+            picked = Some(action);
+            ui.close_menu();
+        }
+    }
+    picked
+}
+
+impl GameState {
+    pub(crate) fn apply_enemy_action(&mut self, id: Entity, action: EnemyAction) {
+        match action {
+            EnemyAction::ToggleFocus => {
+                self.focused = (self.focused != Some(id)).then_some(id);
+            }
+            EnemyAction::Inspect => self.inspected = Some(id),
+            EnemyAction::ToggleMark => match self.marked.iter().position(|&marked| marked == id) {
+                Some(index) => {
+                    self.marked.remove(index);
+                }
+                None => self.marked.push(id),
+            },
+            EnemyAction::Kill | EnemyAction::Heal if !self.mode.free_sliders() => {}
+            EnemyAction::Kill => {
+                if let Some(enemy) = self.enemies.iter_mut().find(|enemy| enemy.id == id) {
+                    // Removed with its bounty paid by the next step like any other kill
+                    enemy.shield = 0.;
+                    enemy.hp.current = 0.;
+                }
+            }
+            EnemyAction::Heal => {
+                if let Some(enemy) = self.enemies.iter_mut().find(|enemy| enemy.id == id) {
+                    enemy.hp.current = enemy.hp.maximum;
+                }
+            }
+        }
+    }
+
+    /// Details of the inspected enemy, closed once it's gone.
+    pub(crate) fn show_inspection_window(&mut self, ctx: &egui::Context) {
+        let Some(id) = self.inspected else {
+            return;
+        };
+        let Some(enemy) = self.enemies.iter().find(|enemy| enemy.id == id) else {
+            self.inspected = None;
+            return;
+        };
+        let mut open = true;
+        egui::Window::new(tr("Enemy"))
+            .id(egui::Id::new("inspection_window"))
+            .open(&mut open)
+            .show(ctx, |ui| self.show_inspection(ui, enemy));
+        if !open {
+            self.inspected = None;
+        }
+    }
+
+    /// Everything about one enemy: its stats, how it's been faring and what can reach it.
+    fn show_inspection(&self, ui: &mut egui::Ui, enemy: &Enemy) {
+        let debuffs = self.debuffs.get(enemy.id);
+        show_enemy_details(ui, enemy, debuffs, self.crowd_control.get(enemy.id));
+        ui.separator();
+        let speed = debuffs.map_or(enemy.speed, |debuffs| {
+            debuffs.scale(Stat::Speed, enemy.speed)
+        });
+        let arrival = match speed > 0. {
+            true => durations::format(enemy.distance.0 / speed),
+            false => tr("never").to_owned(),
+        };
+        egui::Grid::new("inspection_stats").show(ui, |ui| {
+            for (name, value) in [
+                (tr("Damage"), format!("{:.1}", enemy.damage)),
+                (tr("Speed"), format!("{speed:.1}")),
+                (tr("Regeneration"), format!("{:.1}", enemy.regeneration)),
+                (tr("Damage taken"), format!("{:.1}", enemy.damage_taken)),
+                (tr("Reaches the Excellency in"), arrival),
+            ] {
+                ui.label(name);
+                ui.strong(value);
+                ui.end_row();
+            }
+        });
+        ui.separator();
+        ui.strong(tr("What can hit it"));
+        egui::Grid::new("inspection_attacks").show(ui, |ui| {
+            for kind in AttackKind::ALL {
+                let attack = self.excellency.attack(kind);
+                let damage = self.excellency.buffs.scale(Stat::Damage, attack.damage);
+                let (reaches, status) = if self.mutators.iter().any(|m| m.disables(kind)) {
+                    (false, tr("Disabled by a mutator").to_owned())
+                } else if enemy.distance.0 > attack.range {
+                    let gap = format!("{:.1}", enemy.distance.0 - attack.range);
+                    (false, trf("Out of range by {}", &[gap]))
+                } else if damage <= enemy.armor {
+                    (false, tr("Can't get through its armor").to_owned())
+                } else {
+                    let hits = enemy.hits_to_kill(damage);
+                    (true, trf("In range, {} hits to kill", &[hits]))
+                };
+                ui.label(kind.name());
+                show_reach(ui, reaches, status);
+                ui.end_row();
+            }
+            for &aura in &self.excellency.auras {
+                ui.label(aura.name());
+                let reaches = enemy.distance.0 <= self.excellency.aura_radius(aura);
+                let status = match reaches {
+                    true => tr("Within reach"),
+                    false => tr("Out of reach"),
+                };
+                show_reach(ui, reaches, status.to_owned());
+                ui.end_row();
+            }
+        });
+    }
+
+    pub(crate) fn show_enemies_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr("Enemies"));
+        show_minimap(ui, &self.excellency, &self.enemies);
+        ui.horizontal(|ui| {
+            ui.label(tr("Sort by:"));
+            egui::ComboBox::from_id_source("enemy_sort")
+                .selected_text(self.enemy_sort.name())
+                .show_ui(ui, |ui| {
+                    for sort in EnemySort::ALL {
+                        ui.selectable_value(&mut self.enemy_sort, sort, sort.name());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Show:"));
+            egui::ComboBox::from_id_source("enemy_filter")
+                .selected_text(self.enemy_filter.name())
+                .show_ui(ui, |ui| {
+                    for filter in EnemyFilter::ALL {
+                        ui.selectable_value(&mut self.enemy_filter, filter, filter.name());
+                    }
+                });
+        });
+        ui.separator();
+
+        let mut listed: Vec<usize> = (0..self.enemies.len())
+            .filter(|&index| {
+                self.enemy_filter
+                    .matches(&self.enemies[index], &self.excellency)
+            })
+            .collect();
+        self.enemy_sort.sort(&mut listed, &self.enemies);
+
+        let hidden = listed.len().saturating_sub(MAX_LISTED_ENEMIES);
+        listed.truncate(MAX_LISTED_ENEMIES);
+        let theme = &self.settings.theme;
+        let sandbox = self.mode.free_sliders();
+        let touch_layout = self.touch_layout;
+        let mut picked = None;
+        let line_height = ui.spacing().interact_size.y;
+        let row_height = 2. * line_height + 2. * ui.spacing().item_spacing.y;
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, true])
+            .max_height(ui.available_height() - line_height)
+            .show_rows(ui, row_height, listed.len(), |ui, rows| {
+                for &index in &listed[rows] {
+                    let enemy = &self.enemies[index];
+                    ui.vertical(|ui| {
+                        ui.set_height(row_height - ui.spacing().item_spacing.y);
+                        let row = ui.horizontal(|ui| {
+                            ui.set_height(line_height);
+                            if self.hovered == Some(enemy.id) {
+                                ui.label(tr("HOVERED"));
+                            }
+                            if self.focused == Some(enemy.id) {
+                                ui.colored_label(FOCUS_COLOR, tr("FOCUSED"));
+                            }
+                            if self.marked.contains(&enemy.id) {
+                                ui.colored_label(MARK_COLOR, tr("MARKED"));
+                            }
+                            ui.strong(enemy.display_name());
+                            if enemy.is_boss {
+                                ui.colored_label(Color32::RED, tr("BOSS"));
+                            }
+                            if enemy.kind == EnemyKind::Healer {
+                                ui.colored_label(Color32::GREEN, tr("HEALER"));
+                            }
+                            if enemy.is_elite() {
+                                ui.colored_label(Color32::GOLD, tr("ELITE"));
+                            }
+                            ui.label(trf("Damage: {}", &[enemy.damage.to_string()]));
+                            ui.label(trf("Speed: {}", &[enemy.speed.to_string()]));
+                        });
+                        let row = info_popup(row.response, |ui| {
+                            let crowd_control = self.crowd_control.get(enemy.id);
+                            show_enemy_details(ui, enemy, self.debuffs.get(enemy.id), crowd_control)
+                        })
+                        .interact(egui::Sense::click());
+                        // Taps in the touch layout already open the details popup
+                        if row.clicked() && !touch_layout {
+                            picked = Some((enemy.id, EnemyAction::Inspect));
+                        }
+                        row.context_menu(|ui| {
+                            let focused = self.focused == Some(enemy.id);
+                            let marked = self.marked.contains(&enemy.id);
+                            if let Some(action) = show_enemy_menu(ui, focused, marked, sandbox) {
+                                picked = Some((enemy.id, action));
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            let width = (ui.available_width() - ui.spacing().item_spacing.x) / 2.;
+                            let bar = ui.add(
+                                enemy
+                                    .distance
+                                    .as_progress_bar(theme.enemy_bar)
+                                    .desired_width(width),
+                            );
+                            stat_tooltip(
+                                bar,
+                                tr("How far the enemy still has to walk."),
+                                &[(
+                                    tr("Time to arrive"),
+                                    durations::format(enemy.distance.0 / enemy.speed),
+                                )],
+                            );
+                            let bar =
+                                ui.add(enemy.hp.as_progress_bar(theme.hp_bar).desired_width(width));
+                            stat_tooltip(
+                                bar,
+                                tr("The enemy dies when its HP reaches zero."),
+                                &[(
+                                    tr("Basic attack hits to kill"),
+                                    enemy.hits_to_kill(self.excellency.basic_attack.damage),
+                                )],
+                            );
+                        });
+                    });
+                    ui.separator();
+                }
+            });
+        if hidden > 0 {
+            ui.label(trf("{} more enemies", &[hidden.to_string()]));
+        }
+        if let Some((id, action)) = picked {
+            self.apply_enemy_action(id, action);
+        }
+    }
+}
@@ -0,0 +1,417 @@
+use egui::Color32;
+
+use crate::app::GameState;
+use crate::auras::Aura;
+use crate::combat::AttackKind;
+use crate::daily;
+use crate::durations;
+use crate::i18n::{tr, trf};
+use crate::modes::GameMode;
+use crate::mutators::Mutator;
+use crate::settings::Action;
+use crate::upgrades::Upgrade;
+use crate::victory;
+use crate::widgets::{stat_slider, stat_tooltip};
+
+impl GameState {
+    pub(crate) fn show_player_panel(&mut self, ui: &mut egui::Ui) {
+        if !self.content_errors.is_empty() {
+            ui.colored_label(
+                Color32::RED,
+                trf(
+                    "Some content couldn't be loaded, the built-in version is used instead: {}",
+                    &[self.content_errors.join("; ")],
+                ),
+            );
+            if ui.button(tr("Dismiss")).clicked() {
+                self.content_errors.clear();
+            }
+            ui.separator();
+        }
+        if let Some(error) = &self.load_error {
+            ui.colored_label(Color32::RED, error);
+            if ui.button(tr("Dismiss")).clicked() {
+                self.load_error = None;
+            }
+            ui.separator();
+        }
+
+        ui.horizontal(|ui| {
+            ui.heading(tr("Run summary"));
+            ui.label(
+                egui::RichText::new(self.mode.name())
+                    .heading()
+                    .strong()
+                    .color(self.mode.color()),
+            )
+            .on_hover_text(self.mode.description());
+        });
+        ui.label(trf("Seed: {}", &[self.rng.seed().to_string()]));
+        ui.label(trf("Elapsed: {}", &[durations::format(self.elapsed)]));
+        ui.label(self.waves.status());
+        if self.new_game_plus > 0 {
+            ui.label(trf("New Game+ {}", &[self.new_game_plus.to_string()]));
+        }
+        if let Some(day) = self.daily {
+            ui.label(trf("Daily challenge of {}", &[daily::date(day)]));
+        }
+        if let Some(stage) = &self.stage {
+            ui.label(trf("Campaign stage: {}", std::slice::from_ref(stage)));
+        }
+        if let Some(victory) = self.victory {
+            ui.label(trf("Goal: {}", &[victory.describe()]));
+        }
+        for mutator in &self.mutators {
+            ui.label(mutator.name());
+        }
+        if self.incidents.frenzy > 0. {
+            ui.colored_label(
+                Color32::RED,
+                trf(
+                    "Frenzy: {} left",
+                    &[durations::format(self.incidents.frenzy)],
+                ),
+            );
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("Next seed:"));
+            ui.add(egui::DragValue::new(&mut self.next_seed));
+            if ui.button(tr("Randomize")).clicked() {
+                self.next_seed = (ui.input(|i| i.time) * 1e6) as u64;
+            }
+            if ui.button(tr("Restart")).clicked() {
+                self.restart(self.next_seed, self.next_mutators.clone());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Next mode:"));
+            for mode in GameMode::ALL {
+                ui.selectable_value(&mut self.next_mode, mode, mode.name())
+                    .on_hover_text(mode.description());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Win condition:"));
+            victory::show_picker(ui, &mut self.next_victory);
+        });
+        ui.collapsing(tr("Mutators"), |ui| self.show_mutator_picker(ui));
+        if self.milestone_cleared {
+            let button = ui.button(trf(
+                "Start New Game+ {}",
+                &[(self.new_game_plus + 1).to_string()],
+            ));
+            let tooltip = tr("Enemies get tougher, the Excellency keeps its auras and talents.");
+            if button.on_hover_text(tooltip).clicked() {
+                self.start_next_cycle();
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.toggle_value(&mut self.paused, tr("Pause"));
+            ui.label(trf("Speed: x{}", &[self.game_speed.to_string()]));
+            if ui.button(tr("-")).clicked() {
+                self.perform(Action::SpeedDown);
+            }
+            if ui.button(tr("+")).clicked() {
+                self.perform(Action::SpeedUp);
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(tr("Save / Load")).clicked() {
+                self.show_save_window = true;
+            }
+            if ui.button(tr("Settings")).clicked() {
+                self.show_settings_window = true;
+            }
+            if ui.button(tr("Run history")).clicked() {
+                self.show_history_window = true;
+            }
+            if ui.button(tr("Stat sheet")).clicked() {
+                self.show_stat_sheet_window = true;
+            }
+            if ui.button(tr("Character sheet")).clicked() {
+                self.show_character_sheet_window = true;
+            }
+            if ui.button(tr("Campaign")).clicked() {
+                self.show_campaign_window = true;
+            }
+            if ui.button(tr("Automation")).clicked() {
+                self.show_automation_window = true;
+            }
+            if ui.button(tr("Ascendancy")).clicked() {
+                self.show_ascendancy_window = true;
+            }
+            if ui.button(tr("Relics")).clicked() {
+                self.show_relics_window = true;
+            }
+            let profile = self.profiles.name(self.profile);
+            if ui
+                .button(trf("Profile: {}", &[profile.to_owned()]))
+                .clicked()
+            {
+                self.show_profile_window = true;
+            }
+            #[cfg(feature = "leaderboard")]
+            if ui.button(tr("Leaderboard")).clicked() {
+                self.show_leaderboard_window = true;
+            }
+        });
+        ui.collapsing(tr("Records"), |ui| self.records.show(ui));
+        ui.collapsing(tr("Daily challenge"), |ui| self.show_daily(ui));
+        ui.separator();
+
+        ui.heading(tr("Player stuff"));
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(tr("HP:"));
+            let bar = ui.add(
+                self.excellency.hp.as_progress_bar(
+                    self.juice
+                        .flash(&self.settings.juice, self.settings.theme.hp_bar),
+                ),
+            );
+            let incoming: f32 = self.enemies.iter().map(|enemy| enemy.damage).sum();
+            stat_tooltip(
+                bar,
+                tr("Enemies that reach the Excellency deal their damage to it."),
+                &[(tr("Damage on the field"), format!("{incoming:.1}"))],
+            );
+        });
+        if ui.button(tr("Reset HP")).clicked() {
+            self.excellency.hp.reset()
+        }
+        let dps = self.excellency.basic_attack.effective_dps()
+            + self.excellency.big_attack.effective_dps();
+        let healing = dps * self.excellency.life_steal;
+        stat_slider(
+            ui,
+            tr("Life steal:"),
+            &mut self.excellency.life_steal,
+            0. ..=1.,
+            tr("Fraction of the damage dealt to enemies that heals the Excellency."),
+            &[(tr("Healing per second"), format!("{healing:.1}"))],
+        );
+        stat_slider(
+            ui,
+            tr("Thorns:"),
+            &mut self.excellency.thorns,
+            0. ..=2.,
+            tr("Fraction of the damage taken that's dealt back to the enemy that reached the Excellency, before its armor and shield. Killing it pays its bounty."),
+            &[],
+        );
+        let effective_hp = self.excellency.hp.maximum / (1. - self.excellency.dodge_chance);
+        stat_slider(
+            ui,
+            tr("Dodge chance:"),
+            &mut self.excellency.dodge_chance,
+            0. ..=0.75,
+            tr("Chance an enemy reaching the Excellency misses and deals no damage."),
+            &[(tr("Effective HP"), format!("{effective_hp:.0}"))],
+        );
+        stat_slider(
+            ui,
+            tr("Block:"),
+            &mut self.excellency.block,
+            0. ..=50.,
+            tr("Taken off the damage of every enemy that reaches the Excellency."),
+            &[],
+        );
+        ui.label(trf("Kills: {}", &[self.kills.to_string()]));
+        ui.label(trf("Gold: {}", &[self.excellency.gold.to_string()]));
+        ui.label(trf(
+            "Experience: {}",
+            &[self.excellency.experience.to_string()],
+        ));
+        ui.label(trf("Level: {}", &[self.excellency.level().to_string()]));
+        if !self.excellency.talents.is_empty() {
+            ui.collapsing(tr("Talents"), |ui| {
+                for talent in &self.excellency.talents {
+                    ui.label(talent.name()).on_hover_text(talent.description());
+                }
+            });
+        }
+        if !self.excellency.buffs.is_empty() {
+            ui.collapsing(tr("Effects"), |ui| self.excellency.buffs.show(ui));
+        }
+        ui.collapsing(tr("Auras"), |ui| {
+            for aura in Aura::ALL {
+                ui.horizontal(|ui| {
+                    ui.label(aura.name()).on_hover_text(aura.description());
+                    if self.excellency.auras.contains(&aura) {
+                        ui.weak(tr("Unlocked"));
+                        let Some(range) = aura.radius_range() else {
+                            return;
+                        };
+                        let mut radius = self.excellency.aura_radius(aura);
+                        let slider = egui::Slider::new(&mut radius, range).text(tr("Radius"));
+                        if ui.add(slider).changed() {
+                            self.excellency.set_aura_radius(aura, radius);
+                        }
+                    } else if ui
+                        .add_enabled(
+                            self.excellency.gold >= aura.cost(),
+                            egui::Button::new(trf("Unlock ({} gold)", &[aura.cost().to_string()])),
+                        )
+                        .on_hover_text(trf(
+                            "Spends {} gold of this run, the aura stays on until it ends.",
+                            &[aura.cost().to_string()],
+                        ))
+                        .on_disabled_hover_text(trf(
+                            "Needs {} gold, kills pay it.",
+                            &[aura.cost().to_string()],
+                        ))
+                        .clicked()
+                    {
+                        self.excellency.unlock(aura);
+                    }
+                });
+            }
+        });
+        let cooldown_color = self.settings.theme.cooldown_bar;
+        let free_sliders = self.mode.free_sliders();
+        self.recording_edits(ui, |state, ui| {
+            for (kind, id_source) in [(AttackKind::Basic, "basic"), (AttackKind::Big, "big")] {
+                ui.separator();
+                ui.heading(kind.name());
+                state.excellency.attack_mut(kind).show_editor(
+                    ui,
+                    id_source,
+                    cooldown_color,
+                    free_sliders,
+                );
+                if !free_sliders {
+                    state.show_upgrades(ui, kind);
+                }
+            }
+
+            ui.collapsing(tr("Loadouts"), |ui| state.show_loadouts(ui));
+        });
+
+        ui.separator();
+        ui.heading(tr("Ultimate"));
+        self.excellency.ultimate.show(ui);
+
+        #[cfg(feature = "scripting")]
+        {
+            ui.separator();
+            ui.heading(tr("Scripted abilities"));
+            self.scripts.show(ui, cooldown_color);
+        }
+
+        for plugin in &mut self.plugins {
+            if plugin.has_ui_panel() {
+                ui.collapsing(plugin.name(), |ui| plugin.ui_panel(ui));
+            }
+        }
+    }
+    fn show_mutator_picker(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("Used by the next restart."));
+        for mutator in Mutator::ALL {
+            let mut picked = self.next_mutators.contains(&mutator);
+            let label = match mutator.reward() > 0. {
+                true => trf(
+                    "{} (+{}% gold)",
+                    &[
+                        mutator.name().to_owned(),
+                        format!("{:.0}", mutator.reward() * 100.),
+                    ],
+                ),
+                false => mutator.name().to_owned(),
+            };
+            if ui.checkbox(&mut picked, label).changed() {
+                match picked {
+                    true => self.next_mutators.push(mutator),
+                    false => self.next_mutators.retain(|&other| other != mutator),
+                }
+            }
+        }
+    }
+
+    fn show_daily(&mut self, ui: &mut egui::Ui) {
+        let today = daily::today();
+        ui.label(daily::date(today));
+        for mutator in daily::mutators(today) {
+            ui.label(mutator.name());
+        }
+        match self.records.dailies.get(&today) {
+            Some(best) => ui.label(trf(
+                "Completed, best: wave {}, {} kills",
+                &[best.wave.to_string(), best.kills.to_string()],
+            )),
+            None => ui.label(tr("Not completed yet")),
+        };
+        if ui.button(tr("Play today's daily")).clicked() {
+            self.replace_run(GameState::daily(today));
+        }
+        let next = daily::seconds_until_next();
+        ui.label(trf(
+            "Next daily in {}h {}m",
+            &[(next / 3600).to_string(), (next % 3600 / 60).to_string()],
+        ));
+    }
+
+    /// Saves the attacks under a name and swaps between saved ones.
+    fn show_loadouts(&mut self, ui: &mut egui::Ui) {
+        let mut swapped = None;
+        ui.horizontal_wrapped(|ui| {
+            for name in self.settings.loadouts.keys() {
+                let active = self.loadout.as_ref() == Some(name);
+                let label = ui.add_enabled(
+                    self.mode.free_sliders(),
+                    egui::SelectableLabel::new(active, name),
+                );
+                if label.clicked() {
+                    swapped = Some(name.clone());
+                }
+            }
+        });
+        if let Some(name) = swapped {
+            self.swap_loadout(&name);
+            self.loadout_name = name;
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("Name:"));
+            ui.text_edit_singleline(&mut self.loadout_name);
+            let name = self.loadout_name.trim().to_owned();
+            let save = ui
+                .add_enabled(!name.is_empty(), egui::Button::new(tr("Save")))
+                .on_hover_text(tr(
+                    "Saves both attacks under this name, replacing a loadout of the same name.",
+                ));
+            if save.clicked() {
+                self.save_loadout(&name);
+            }
+            let saved = self.settings.loadouts.contains_key(&name);
+            if ui
+                .add_enabled(saved, egui::Button::new(tr("Delete")))
+                .clicked()
+            {
+                self.settings.loadouts.remove(&name);
+                if self.loadout.as_ref() == Some(&name) {
+                    self.loadout = None;
+                }
+            }
+        });
+    }
+
+    /// Gold spent on making `kind` better, the only way to in survival.
+    fn show_upgrades(&mut self, ui: &mut egui::Ui, kind: AttackKind) {
+        ui.horizontal_wrapped(|ui| {
+            for upgrade in Upgrade::ALL {
+                let excellency = &self.excellency;
+                let cost = upgrade.cost(excellency.upgrades_bought(kind, upgrade));
+                let available =
+                    excellency.gold >= cost && !upgrade.is_maxed(excellency.attack(kind));
+                let label = trf(
+                    "{} ({} gold)",
+                    &[upgrade.name().to_owned(), cost.to_string()],
+                );
+                if ui
+                    .add_enabled(available, egui::Button::new(label))
+                    .clicked()
+                {
+                    self.excellency.buy_upgrade(kind, upgrade);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,196 @@
+use egui::{Pos2, Sense};
+
+use crate::app::GameState;
+use crate::content::Content;
+use crate::durations;
+use crate::i18n::{tr, trf};
+use crate::spawner::{EnemySpawner, SpawnerPreset, SpawnerScaling};
+
+/// Plots the multipliers of all scaled stats over the next `PROJECTION_MINUTES`.
+fn show_scaling_projection(ui: &mut egui::Ui, scaling: &SpawnerScaling, elapsed: f32) {
+    const PROJECTION_MINUTES: f32 = 30.;
+    const SAMPLES: usize = 60;
+
+    let curves = scaling.curves();
+    let end = elapsed + PROJECTION_MINUTES * 60.;
+    let maximum = curves
+        .iter()
+        .map(|(_, curve, _)| curve.multiplier(end))
+        .fold(1., f32::max);
+
+    let size = egui::vec2(ui.available_width(), 120.);
+    let (response, painter) = ui.allocate_painter(size, Sense::hover());
+    let rect = response.rect;
+    painter.rect_stroke(rect, 0., ui.visuals().widgets.noninteractive.bg_stroke);
+
+    for (_, curve, color) in curves {
+        let points = (0..=SAMPLES)
+            .map(|sample| {
+                let fraction = sample as f32 / SAMPLES as f32;
+                let multiplier = curve.multiplier(elapsed + fraction * PROJECTION_MINUTES * 60.);
+                Pos2::new(
+                    rect.left() + fraction * rect.width(),
+                    rect.bottom()
+                        - (multiplier - 1.) / (maximum - 1.).max(f32::EPSILON) * rect.height(),
+                )
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        for (name, _, color) in curves {
+            ui.colored_label(color, name);
+        }
+        ui.label(trf(
+            "(max x{} in {} min)",
+            &[format!("{maximum:.1}"), format!("{PROJECTION_MINUTES:.0}")],
+        ));
+    });
+}
+
+/// Picks the one archetype `spawner` sticks to, or none to follow the wave composition.
+fn show_archetype_picker(ui: &mut egui::Ui, spawner: &mut EnemySpawner, content: &Content) {
+    ui.horizontal(|ui| {
+        ui.label(tr("Enemy kind:"));
+        egui::ComboBox::from_id_source("spawner_archetype")
+            .selected_text(spawner.archetype.as_deref().unwrap_or(tr("By wave")))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut spawner.archetype, None, tr("By wave"));
+                for name in content.archetypes.keys() {
+                    ui.selectable_value(&mut spawner.archetype, Some(name.clone()), name);
+                }
+            });
+    });
+}
+
+impl GameState {
+    /// Saves the spawners under a name and switches back to saved ones.
+    fn show_spawner_presets(&mut self, ui: &mut egui::Ui) {
+        let presets = &mut self.settings.spawner_presets;
+        ui.horizontal(|ui| {
+            ui.label(tr("Preset:"));
+            egui::ComboBox::from_id_source("spawner_preset")
+                .selected_text(tr("Load"))
+                .show_ui(ui, |ui| {
+                    for (name, preset) in presets.iter() {
+                        if ui.selectable_label(false, name).clicked() {
+                            self.enemy_spawner = preset.main.clone();
+                            self.extra_spawners.clone_from(&preset.extras);
+                            self.preset_name.clone_from(name);
+                        }
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Name:"));
+            ui.text_edit_singleline(&mut self.preset_name);
+            let name = self.preset_name.trim();
+            let save = ui
+                .add_enabled(!name.is_empty(), egui::Button::new(tr("Save")))
+                .on_hover_text(tr(
+                    "Saves all spawners under this name, replacing a preset of the same name.",
+                ));
+            if save.clicked() {
+                let preset = SpawnerPreset::capture(&self.enemy_spawner, &self.extra_spawners);
+                presets.insert(name.to_owned(), preset);
+            }
+            if ui
+                .add_enabled(presets.contains_key(name), egui::Button::new(tr("Delete")))
+                .clicked()
+            {
+                presets.remove(name);
+            }
+        });
+    }
+
+    pub(crate) fn show_spawner_panel(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(tr("Enemy spawner"));
+            if !self.mode.free_sliders() {
+                return;
+            }
+            let undo = ui
+                .add_enabled(self.edits.can_undo(), egui::Button::new(tr("Undo")))
+                .on_hover_text(tr(
+                    "Reverts the last change to the attacks or spawners (Ctrl+Z).",
+                ));
+            if undo.clicked() {
+                self.undo();
+            }
+            let redo = ui
+                .add_enabled(self.edits.can_redo(), egui::Button::new(tr("Redo")))
+                .on_hover_text(tr("Brings back the last undone change (Ctrl+Shift+Z)."));
+            if redo.clicked() {
+                self.redo();
+            }
+        });
+        if !self.mode.free_sliders() {
+            ui.label(tr("The spawners can only be changed in sandbox."));
+        }
+        ui.add_enabled_ui(self.mode.free_sliders(), |ui| {
+            self.recording_edits(ui, Self::show_spawners)
+        });
+    }
+
+    fn show_spawners(&mut self, ui: &mut egui::Ui) {
+        show_archetype_picker(ui, &mut self.enemy_spawner, &self.content);
+        self.enemy_spawner.show_editor(ui, self.elapsed);
+
+        let mut removed = None;
+        for (index, spawner) in self.extra_spawners.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.heading(trf("Spawner {}", &[(index + 2).to_string()]));
+                    if ui.button(tr("Remove")).clicked() {
+                        removed = Some(index);
+                    }
+                });
+                show_archetype_picker(ui, spawner, &self.content);
+                spawner.show_editor(ui, self.elapsed);
+            });
+        }
+        if let Some(index) = removed {
+            self.extra_spawners.remove(index);
+        }
+        if ui
+            .button(tr("Add spawner"))
+            .on_hover_text(tr(
+                "Adds a spawner with the main one's stats, on its own timer.",
+            ))
+            .clicked()
+        {
+            let SpawnerPreset { main, .. } = SpawnerPreset::capture(&self.enemy_spawner, &[]);
+            self.extra_spawners.push(main);
+        }
+
+        ui.separator();
+        self.show_spawner_presets(ui);
+
+        ui.separator();
+        ui.heading(tr("Scaling"));
+        ui.label(trf("Elapsed: {}", &[durations::format(self.elapsed)]));
+        let scaling = &mut self.enemy_spawner.scaling;
+        for (name, curve) in [
+            ("HP", &mut scaling.hp),
+            ("Speed", &mut scaling.speed),
+            ("Damage", &mut scaling.damage),
+            ("Spawn rate", &mut scaling.spawn_rate),
+        ] {
+            ui.horizontal(|ui| {
+                ui.label(trf(
+                    "{}: x{}",
+                    &[
+                        tr(name).to_owned(),
+                        format!("{:.2}", curve.multiplier(self.elapsed)),
+                    ],
+                ));
+                curve.show_editor(ui, name);
+            });
+        }
+        ui.collapsing(tr("Projection"), |ui| {
+            show_scaling_projection(ui, &self.enemy_spawner.scaling, self.elapsed)
+        });
+    }
+}
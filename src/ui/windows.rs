@@ -0,0 +1,674 @@
+use crate::app::GameState;
+use crate::ascendancy::{self, Perk};
+use crate::automation::Automation;
+use crate::durations::{self, DurationFormat};
+use crate::i18n::{tr, trf, Language};
+use crate::mutators::Mutator;
+use crate::records::{HistorySort, NewBests};
+use crate::relics;
+use crate::saves::{self, SlotSummary};
+use crate::settings::Action;
+#[cfg(feature = "telemetry")]
+use crate::telemetry;
+use crate::toasts::ToastKind;
+use crate::widgets::info_popup;
+
+enum SaveAction {
+    Save(usize),
+    Load(usize),
+    Delete(usize),
+    Export,
+    Import,
+}
+
+enum ProfileAction {
+    Switch(u32),
+    Add,
+    Remove(u32),
+}
+
+enum HistoryAction {
+    Replay(u64, Vec<Mutator>),
+    Delete(usize),
+}
+
+impl GameState {
+    #[cfg(feature = "leaderboard")]
+    pub(crate) fn show_leaderboard_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("Leaderboard"))
+            .id(egui::Id::new("leaderboard_window"))
+            .open(&mut self.show_leaderboard_window)
+            .show(ctx, |ui| {
+                self.leaderboard.show(ui, &self.settings.leaderboard.url)
+            });
+    }
+
+    pub(crate) fn show_stat_sheet_window(&mut self, ctx: &egui::Context) {
+        let sheet = self.stat_sheet();
+        egui::Window::new(tr("Stat sheet"))
+            .id(egui::Id::new("stat_sheet_window"))
+            .open(&mut self.show_stat_sheet_window)
+            .show(ctx, |ui| sheet.show(ui));
+    }
+
+    pub(crate) fn show_character_sheet_window(&mut self, ctx: &egui::Context) {
+        let sheet = self.character_sheet();
+        egui::Window::new(tr("Character sheet"))
+            .id(egui::Id::new("character_sheet_window"))
+            .open(&mut self.show_character_sheet_window)
+            .show(ctx, |ui| sheet.show(ui));
+    }
+
+    pub(crate) fn show_history_window(&mut self, ctx: &egui::Context) {
+        let mut action = None;
+        egui::Window::new(tr("Run history"))
+            .id(egui::Id::new("history_window"))
+            .open(&mut self.show_history_window)
+            .show(ctx, |ui| {
+                if self.records.history.is_empty() {
+                    ui.label(tr("No finished runs yet."));
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("history").striped(true).show(ui, |ui| {
+                        for sort in [HistorySort::Date, HistorySort::Wave] {
+                            ui.selectable_value(&mut self.history_sort, sort, sort.name());
+                        }
+                        ui.strong(tr("Seed"));
+                        ui.strong(tr("Mutators"));
+                        for sort in [HistorySort::Duration, HistorySort::Kills] {
+                            ui.selectable_value(&mut self.history_sort, sort, sort.name());
+                        }
+                        ui.end_row();
+
+                        let history = &self.records.history;
+                        for index in self.history_sort.order(history) {
+                            let run = &history[index];
+                            ui.label(saves::format_age(run.finished_at));
+                            ui.label(run.wave.to_string());
+                            ui.label(run.seed.to_string());
+                            match run.mutators.is_empty() {
+                                true => ui.label(tr("-")),
+                                false => ui.label(
+                                    run.mutators
+                                        .iter()
+                                        .map(Mutator::name)
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                ),
+                            };
+                            ui.label(durations::format(run.duration));
+                            ui.label(run.kills.to_string());
+                            info_popup(ui.label(tr("Build")), |ui| run.show_build(ui));
+                            if ui.button(tr("Replay seed")).clicked() {
+                                action =
+                                    Some(HistoryAction::Replay(run.seed, run.mutators.clone()));
+                            }
+                            if ui.button(tr("Delete")).clicked() {
+                                action = Some(HistoryAction::Delete(index));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        match action {
+            Some(HistoryAction::Replay(seed, mutators)) => {
+                self.next_seed = seed;
+                self.next_mutators.clone_from(&mutators);
+                self.restart(seed, mutators);
+            }
+            Some(HistoryAction::Delete(index)) => {
+                self.records.history.remove(index);
+            }
+            None => {}
+        }
+    }
+
+    pub(crate) fn show_defeat_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("Defeat"))
+            .id(egui::Id::new("defeat_window"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(tr("The Excellency has fallen."));
+                ui.separator();
+                let no_bests = NewBests::default();
+                self.run_result()
+                    .show(ui, self.new_bests.as_ref().unwrap_or(&no_bests));
+                ui.separator();
+                if ui.button(tr("New run")).clicked() {
+                    self.restart(self.next_seed, self.next_mutators.clone());
+                }
+            });
+    }
+
+    pub(crate) fn show_victory_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("Victory"))
+            .id(egui::Id::new("victory_window"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                if let Some(victory) = self.victory {
+                    ui.label(trf("Goal reached: {}", &[victory.describe()]));
+                }
+                if let Some(stars) = self.stars() {
+                    ui.heading(stars_text(stars));
+                }
+                if let (Some(victory), true) = (self.victory, self.mode.tracks_records()) {
+                    ui.colored_label(
+                        egui::Color32::GOLD,
+                        trf("+{} laurels", &[victory.laurels().to_string()]),
+                    );
+                }
+                ui.separator();
+                let no_bests = NewBests::default();
+                self.run_result()
+                    .show(ui, self.new_bests.as_ref().unwrap_or(&no_bests));
+                ui.separator();
+                if self.auto_continue_stopped {
+                    ui.label(tr("Auto-continue stopped, HP fell too low."));
+                }
+                let next = self.next_stage();
+                ui.horizontal(|ui| {
+                    if let Some(next) = next {
+                        if ui.button(tr("Next stage")).clicked() {
+                            self.start_stage(&next);
+                        }
+                    }
+                    if ui.button(tr("Campaign")).clicked() {
+                        self.show_campaign_window = true;
+                    }
+                    if ui.button(tr("New run")).clicked() {
+                        self.restart(self.next_seed, self.next_mutators.clone());
+                    }
+                });
+            });
+    }
+
+    pub(crate) fn show_campaign_window(&mut self, ctx: &egui::Context) {
+        let mut play = None;
+        egui::Window::new(tr("Campaign"))
+            .id(egui::Id::new("campaign_window"))
+            .open(&mut self.show_campaign_window)
+            .show(ctx, |ui| {
+                let stages = &self.content.stages;
+                if stages.is_empty() {
+                    ui.label(tr("The campaign has no stages."));
+                    return;
+                }
+                let auto_continue = &mut self.settings.auto_continue;
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut auto_continue.enabled, tr("Auto-continue"))
+                        .on_hover_text(tr("Starts the next stage on its own after a win."));
+                    ui.add_enabled(
+                        auto_continue.enabled,
+                        egui::Slider::new(&mut auto_continue.min_hp, 0.0..=0.9)
+                            .custom_formatter(|fraction, _| format!("{:.0}%", fraction * 100.))
+                            .text(tr("Stop below HP")),
+                    )
+                    .on_hover_text(tr("Pauses the run instead once HP falls below this."));
+                });
+                ui.separator();
+                egui::Grid::new("campaign").striped(true).show(ui, |ui| {
+                    ui.strong(tr("Stage"));
+                    ui.strong(tr("Goal"));
+                    ui.strong(tr("Stars"));
+                    ui.strong(tr("Fastest"));
+                    ui.end_row();
+                    for (index, stage) in stages.iter().enumerate() {
+                        ui.label(&stage.name).on_hover_text(&stage.description);
+                        ui.label(stage.victory.describe());
+                        match self.records.stages.get(&stage.name) {
+                            Some(best) => {
+                                ui.label(stars_text(best.stars));
+                                ui.label(durations::format(best.fastest));
+                            }
+                            None => {
+                                ui.label(tr("-"));
+                                ui.label(tr("-"));
+                            }
+                        }
+                        let unlocked = self.records.is_unlocked(stages, index);
+                        let button = ui
+                            .add_enabled(unlocked, egui::Button::new(tr("Play")))
+                            .on_disabled_hover_text(tr("Win the stage before to unlock it."));
+                        if button.clicked() {
+                            play = Some(stage.name.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+        if let Some(name) = play {
+            self.show_campaign_window = false;
+            self.start_stage(&name);
+        }
+    }
+
+    pub(crate) fn show_automation_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("Automation"))
+            .id(egui::Id::new("automation_window"))
+            .open(&mut self.show_automation_window)
+            .show(ctx, |ui| {
+                let records = &mut self.records;
+                ui.label(trf("Laurels: {}", &[records.laurels.to_string()]));
+                ui.separator();
+                egui::Grid::new("automation").striped(true).show(ui, |ui| {
+                    for automation in Automation::ALL {
+                        ui.label(automation.name())
+                            .on_hover_text(automation.description());
+                        let automations = &mut records.automations;
+                        if automations.is_unlocked(automation) {
+                            let mut on = automations.is_on(automation);
+                            if ui.checkbox(&mut on, tr("On")).changed() {
+                                automations.set(automation, on);
+                            }
+                        } else {
+                            let cost = automation.cost();
+                            let label = trf("Unlock ({} laurels)", &[cost.to_string()]);
+                            if ui
+                                .add_enabled(records.laurels >= cost, egui::Button::new(label))
+                                .on_disabled_hover_text(tr("Victories earn laurels."))
+                                .clicked()
+                            {
+                                automations.unlock(automation, &mut records.laurels);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    /// Every perk in a row with a cell per rank: bought, the next one for sale, or to come.
+    pub(crate) fn show_ascendancy_window(&mut self, ctx: &egui::Context) {
+        let mut buy = None;
+        egui::Window::new(tr("Ascendancy"))
+            .id(egui::Id::new("ascendancy_window"))
+            .open(&mut self.show_ascendancy_window)
+            .show(ctx, |ui| {
+                let ascendancy = &self.ascendancy;
+                ui.label(trf(
+                    "Ascendancy shards: {}",
+                    &[ascendancy.shards.to_string()],
+                ));
+                ui.label(trf(
+                    "Runs reaching wave {} or deeper pay shards.",
+                    &[ascendancy::MIN_WAVE.to_string()],
+                ));
+                ui.separator();
+                egui::Grid::new("ascendancy").striped(true).show(ui, |ui| {
+                    for perk in Perk::ALL {
+                        ui.label(perk.name()).on_hover_text(perk.description());
+                        let rank = ascendancy.rank(perk);
+                        for next in 0..perk.max_rank() {
+                            if next < rank {
+                                ui.colored_label(egui::Color32::GOLD, "✔");
+                            } else if next == rank {
+                                let cost = perk.cost(rank);
+                                let button = ui.add_enabled(
+                                    ascendancy.shards >= cost,
+                                    egui::Button::new(cost.to_string()),
+                                );
+                                if button.on_hover_text(tr("Shards the rank costs")).clicked() {
+                                    buy = Some(perk);
+                                }
+                            } else {
+                                ui.weak(tr("-"));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.weak(tr("Perks apply from the next run on."));
+            });
+        if let Some(perk) = buy {
+            self.ascendancy.buy(perk);
+        }
+    }
+
+    pub(crate) fn show_relics_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("Relics"))
+            .id(egui::Id::new("relics_window"))
+            .open(&mut self.show_relics_window)
+            .show(ctx, |ui| {
+                let relics = &mut self.records.relics;
+                let equipped = relics.equipped().count();
+                ui.label(trf(
+                    "Equipped: {} of {}",
+                    &[equipped.to_string(), relics::SLOTS.to_string()],
+                ));
+                if relics.owned().is_empty() {
+                    ui.weak(tr("Elites and bosses sometimes drop relics."));
+                    return;
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for index in 0..relics.owned().len() {
+                        let mut on = relics.is_equipped(index);
+                        let description = relics.owned()[index].describe();
+                        let enabled = on || equipped < relics::SLOTS;
+                        let checkbox = ui
+                            .add_enabled(enabled, egui::Checkbox::new(&mut on, description))
+                            .on_disabled_hover_text(tr("Every slot is taken."));
+                        if checkbox.changed() {
+                            relics.toggle(index);
+                        }
+                    }
+                });
+            });
+    }
+
+    pub(crate) fn show_talent_window(&mut self, ctx: &egui::Context) {
+        let Some(offered) = self.talent_choice.clone() else {
+            return;
+        };
+        egui::Window::new(tr("Choose a talent"))
+            .id(egui::Id::new("talent_window"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(trf(
+                    "Level {} reached, pick one to keep for the rest of the run.",
+                    &[self.excellency.level().to_string()],
+                ));
+                ui.separator();
+                for talent in offered {
+                    let button =
+                        egui::Button::new(format!("{}\n{}", talent.name(), talent.description()));
+                    if ui.add_sized([ui.available_width(), 40.], button).clicked() {
+                        self.choose_talent(talent);
+                    }
+                }
+            });
+    }
+
+    fn slot_summary(&self) -> SlotSummary {
+        SlotSummary {
+            name: self.save_name.clone(),
+            saved_at: saves::now(),
+            playtime: self.elapsed,
+            gold: self.excellency.gold,
+            wave: self.waves.number,
+        }
+    }
+
+    pub(crate) fn show_save_window(
+        &mut self,
+        ctx: &egui::Context,
+        storage: &mut dyn eframe::Storage,
+    ) {
+        let mut action = None;
+        egui::Window::new(tr("Save / Load"))
+            .id(egui::Id::new("save_window"))
+            .open(&mut self.show_save_window)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.text_edit_singleline(&mut self.save_name);
+                });
+                ui.separator();
+                for slot in 0..saves::SLOT_COUNT {
+                    let summary = saves::summary(storage, self.profile, slot);
+                    ui.horizontal(|ui| {
+                        match &summary {
+                            Some(summary) => ui.label(trf(
+                                "{}. {} - wave {}, {} played, {} gold, saved {}",
+                                &[
+                                    (slot + 1).to_string(),
+                                    summary.name.clone(),
+                                    summary.wave.to_string(),
+                                    durations::format(summary.playtime),
+                                    summary.gold.to_string(),
+                                    saves::format_age(summary.saved_at),
+                                ],
+                            )),
+                            None => ui.label(trf("{}. <empty>", &[(slot + 1).to_string()])),
+                        };
+                        if ui.button(tr("Save")).clicked() {
+                            action = Some(SaveAction::Save(slot));
+                        }
+                        if summary.is_some() {
+                            if ui.button(tr("Load")).clicked() {
+                                action = Some(SaveAction::Load(slot));
+                            }
+                            if ui.button(tr("Delete")).clicked() {
+                                action = Some(SaveAction::Delete(slot));
+                            }
+                        }
+                    });
+                }
+
+                ui.separator();
+                ui.label(tr("Backup or share the whole run as JSON:"));
+                if ui.button(tr("Copy save to clipboard")).clicked() {
+                    action = Some(SaveAction::Export);
+                }
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.import_text)
+                        .hint_text(tr("Paste an exported save here"))
+                        .desired_rows(3),
+                );
+                if ui.button(tr("Import")).clicked() {
+                    action = Some(SaveAction::Import);
+                }
+            });
+
+        match action {
+            Some(SaveAction::Save(slot)) => {
+                saves::save(storage, self.profile, slot, &self.slot_summary(), self);
+                self.toasts.push(
+                    ToastKind::Info,
+                    trf("Saved to slot {}", &[(slot + 1).to_string()]),
+                );
+            }
+            Some(SaveAction::Load(slot)) => match saves::load(storage, self.profile, slot) {
+                Some(Ok(loaded)) => self.replace_run(loaded),
+                Some(Err(err)) => {
+                    self.load_error = Some(trf(
+                        "Slot {}: {}",
+                        &[(slot + 1).to_string(), err.to_string()],
+                    ))
+                }
+                None => {}
+            },
+            Some(SaveAction::Delete(slot)) => saves::clear(storage, self.profile, slot),
+            Some(SaveAction::Export) => match self.to_json() {
+                Ok(json) => {
+                    ctx.output_mut(|output| output.copied_text = json);
+                    self.toasts
+                        .push(ToastKind::Info, tr("Save copied to the clipboard"));
+                }
+                Err(err) => self.toasts.push(
+                    ToastKind::Error,
+                    trf("Export failed: {}", &[err.to_string()]),
+                ),
+            },
+            Some(SaveAction::Import) => match Self::from_json(&self.import_text) {
+                Ok(imported) => self.replace_run(imported),
+                Err(err) => self.load_error = Some(trf("Import failed: {}", &[err.to_string()])),
+            },
+            None => {}
+        }
+    }
+
+    pub(crate) fn show_settings_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("Settings"))
+            .id(egui::Id::new("settings_window"))
+            .open(&mut self.show_settings_window)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Language"));
+                    egui::ComboBox::from_id_source("language")
+                        .selected_text(self.settings.language.name())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                ui.selectable_value(
+                                    &mut self.settings.language,
+                                    language,
+                                    language.name(),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Durations"));
+                    egui::ComboBox::from_id_source("duration_format")
+                        .selected_text(self.settings.duration_format.name())
+                        .show_ui(ui, |ui| {
+                            for format in DurationFormat::ALL {
+                                ui.selectable_value(
+                                    &mut self.settings.duration_format,
+                                    format,
+                                    format.name(),
+                                );
+                            }
+                        });
+                });
+                ui.separator();
+                ui.heading(tr("Theme"));
+                if self.settings.theme.show_editor(ui) {
+                    self.settings.apply(ctx, self.touch_layout);
+                }
+                ui.separator();
+                ui.heading(tr("Accessibility"));
+                if self.settings.show_accessibility_editor(ui) {
+                    self.settings.apply(ctx, self.touch_layout);
+                }
+                ui.separator();
+                ui.heading(tr("Sound"));
+                self.settings.sound.show_editor(ui);
+                ui.separator();
+                ui.heading(tr("Performance"));
+                self.settings.show_performance_editor(ui);
+                ui.separator();
+                ui.heading(tr("Hit feedback"));
+                self.settings.juice.show_editor(ui);
+                #[cfg(feature = "leaderboard")]
+                {
+                    ui.separator();
+                    ui.heading(tr("Leaderboard"));
+                    egui::Grid::new("leaderboard_settings").show(ui, |ui| {
+                        ui.label(tr("Server:"));
+                        ui.text_edit_singleline(&mut self.settings.leaderboard.url);
+                        ui.end_row();
+                        ui.label(tr("Player name:"));
+                        ui.text_edit_singleline(&mut self.settings.leaderboard.player_name);
+                        ui.end_row();
+                    });
+                }
+                #[cfg(feature = "telemetry")]
+                {
+                    ui.separator();
+                    ui.heading(tr("Telemetry"));
+                    ui.checkbox(
+                        &mut self.settings.telemetry.enabled,
+                        tr("Send anonymous statistics"),
+                    );
+                    ui.weak(telemetry::describe());
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Server:"));
+                        ui.text_edit_singleline(&mut self.settings.telemetry.url);
+                    });
+                    self.telemetry.show_status(ui);
+                }
+                #[cfg(feature = "gamepad")]
+                {
+                    ui.separator();
+                    ui.heading(tr("Controller"));
+                    self.settings.gamepad.show_editor(ui);
+                }
+                ui.separator();
+                ui.heading(tr("Hotkeys"));
+                egui::Grid::new("keybindings").show(ui, |ui| {
+                    for action in Action::ALL {
+                        ui.label(action.name());
+                        let text = match (self.rebinding, self.settings.keybindings.key(action)) {
+                            (Some(rebinding), _) if rebinding == action => {
+                                tr("Press a key...").to_owned()
+                            }
+                            (_, Some(key)) => key.name().to_owned(),
+                            (_, None) => tr("Unbound").to_owned(),
+                        };
+                        if ui.button(text).clicked() {
+                            self.rebinding = Some(action);
+                        }
+                        if ui.button(tr("Clear")).clicked() {
+                            self.settings.keybindings.unbind(action);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if self.rebinding.is_some() {
+                    ui.label(tr("Press Escape to cancel."));
+                }
+            });
+    }
+    pub(crate) fn show_profile_window(
+        &mut self,
+        ctx: &egui::Context,
+        storage: &mut dyn eframe::Storage,
+    ) {
+        let mut action = None;
+        egui::Window::new(tr("Profiles"))
+            .id(egui::Id::new("profile_window"))
+            .open(&mut self.show_profile_window)
+            .show(ctx, |ui| {
+                ui.label(tr("Every profile has its own saves, settings and records."));
+                ui.separator();
+                egui::Grid::new("profiles").show(ui, |ui| {
+                    for profile in &self.profiles.list {
+                        ui.label(&profile.name);
+                        if profile.id == self.profile {
+                            ui.label(tr("Playing"));
+                        } else {
+                            if ui.button(tr("Play")).clicked() {
+                                action = Some(ProfileAction::Switch(profile.id));
+                            }
+                            if ui.button(tr("Delete")).clicked() {
+                                action = Some(ProfileAction::Remove(profile.id));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.text_edit_singleline(&mut self.profile_name);
+                    let name_given = !self.profile_name.trim().is_empty();
+                    if ui
+                        .add_enabled(name_given, egui::Button::new(tr("Add")))
+                        .clicked()
+                    {
+                        action = Some(ProfileAction::Add);
+                    }
+                });
+            });
+
+        match action {
+            Some(ProfileAction::Switch(profile)) => {
+                self.switch_profile(ctx, storage, profile);
+                self.show_profile_window = false;
+            }
+            Some(ProfileAction::Add) => {
+                let name = std::mem::take(&mut self.profile_name);
+                self.profiles.add(name.trim().to_owned());
+            }
+            Some(ProfileAction::Remove(profile)) => self.profiles.remove(storage, profile),
+            None => {}
+        }
+    }
+}
+
+/// Filled and empty stars out of three.
+fn stars_text(stars: u32) -> String {
+    (0..3)
+        .map(|star| if star < stars { '★' } else { '☆' })
+        .collect()
+}
@@ -0,0 +1,79 @@
+//! The Excellency's ultimate: charged by kills and damage, cast by hand, and clearing the
+//! field when it goes off.
+
+use egui::Color32;
+
+use crate::charge::Charge;
+use crate::combat::{AttackOutcome, DamageSpec};
+use crate::enemy::{remove_dead, Enemy};
+use crate::i18n::{tr, trf};
+
+/// Fraction of their maximum HP bosses lose to it, everything else dies outright.
+const BOSS_DAMAGE: f32 = 0.25;
+/// Seconds the bosses that survive it are stunned.
+pub const BOSS_STUN: f32 = 2.;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone)]
+#[serde(default)]
+pub struct Ultimate {
+    pub charge: Charge,
+    /// Charge gained per kill.
+    pub per_kill: f32,
+    /// Charge gained per point of damage dealt.
+    pub per_damage: f32,
+    /// Cast by the player, goes off on the next step if it's full.
+    pub queued: bool,
+}
+
+impl Default for Ultimate {
+    fn default() -> Self {
+        Self {
+            charge: Charge::new(100.),
+            per_kill: 4.,
+            per_damage: 0.1,
+            queued: false,
+        }
+    }
+}
+
+impl Ultimate {
+    /// Hits every enemy on the field and removes the ones that died.
+    pub fn fire(&self, enemies: &mut Vec<Enemy>) -> AttackOutcome {
+        let mut damage_dealt = 0.;
+        let mut hits = vec![];
+        for enemy in enemies.iter_mut() {
+            let damage = match enemy.is_boss {
+                true => DamageSpec::MaximumHp(BOSS_DAMAGE),
+                false => DamageSpec::Flat(enemy.hp.current + enemy.shield + enemy.armor),
+            };
+            damage_dealt += enemy.take_damage(damage);
+            hits.push(enemy.distance.0);
+        }
+        let killed = remove_dead(enemies);
+        AttackOutcome {
+            killed,
+            damage_dealt,
+            true_damage: 0.,
+            hits,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Charge:"));
+            let charge = &self.charge;
+            ui.add(
+                egui::ProgressBar::new(charge.fraction())
+                    .text(format!("{:.0} / {:.0}", charge.current, charge.required))
+                    .fill(Color32::from_rgb(170, 90, 255)),
+            )
+            .on_hover_text(trf(
+                "Charged by kills ({} each) and by damage dealt ({} per point).",
+                &[self.per_kill.to_string(), self.per_damage.to_string()],
+            ));
+        });
+        ui.label(tr(
+            "Kills every enemy on the field, bosses lose a quarter of their maximum HP and are stunned.",
+        ));
+    }
+}
@@ -0,0 +1,154 @@
+//! Undo and redo for the editors configuring the attacks and spawners. Whatever they change
+//! is recorded as an edit holding the configuration before and after it, a whole slider
+//! drag counting as a single edit.
+
+use crate::app::GameState;
+use crate::loadouts::Loadout;
+use crate::spawner::{EnemySpawner, SpawnerPreset};
+
+/// Edits kept to undo, the oldest ones are forgotten past it.
+const MAX_EDITS: usize = 100;
+
+/// Everything the editors configure, without the progress of the run.
+#[derive(Clone, PartialEq)]
+pub struct Configuration {
+    loadout: Loadout,
+    spawners: SpawnerPreset,
+}
+
+impl Configuration {
+    pub fn capture(state: &GameState) -> Self {
+        Self {
+            loadout: Loadout::capture(&state.excellency),
+            spawners: SpawnerPreset::capture(&state.enemy_spawner, &state.extra_spawners),
+        }
+    }
+
+    /// Puts the configuration back, leaving cooldowns, spawn timers and queues running.
+    fn restore(&self, state: &mut GameState) {
+        self.loadout.apply(&mut state.excellency);
+        restore_spawner(&mut state.enemy_spawner, &self.spawners.main);
+        let mut current = std::mem::take(&mut state.extra_spawners).into_iter();
+        state.extra_spawners = self
+            .spawners
+            .extras
+            .iter()
+            .map(|configuration| match current.next() {
+                Some(mut spawner) => {
+                    restore_spawner(&mut spawner, configuration);
+                    spawner
+                }
+                None => configuration.clone(),
+            })
+            .collect();
+    }
+}
+
+fn restore_spawner(spawner: &mut EnemySpawner, configuration: &EnemySpawner) {
+    let progress = std::mem::replace(spawner, configuration.clone());
+    spawner.timer = progress.timer;
+    spawner.queue = progress.queue;
+    spawner.streak = progress.streak;
+}
+
+struct Edit {
+    before: Configuration,
+    after: Configuration,
+}
+
+#[derive(Default)]
+pub struct EditHistory {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    /// The configuration from before the drag still going on.
+    started: Option<Configuration>,
+}
+
+impl EditHistory {
+    /// Records what the editors changed from `before` to `after`. While `dragging`, changes
+    /// pile up into one edit, recorded once the pointer lets go.
+    pub fn record(&mut self, before: Configuration, after: &Configuration, dragging: bool) {
+        if self.started.is_none() && before != *after {
+            self.started = Some(before);
+        }
+        if dragging {
+            return;
+        }
+        let Some(before) = self.started.take() else {
+            return;
+        };
+        if before == *after {
+            return;
+        }
+        if self.undo.len() == MAX_EDITS {
+            self.undo.remove(0);
+        }
+        self.undo.push(Edit {
+            before,
+            after: after.clone(),
+        });
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Steps back over the last edit, returning the configuration from before it.
+    pub fn undo(&mut self) -> Option<Configuration> {
+        let edit = self.undo.pop()?;
+        let before = edit.before.clone();
+        self.redo.push(edit);
+        Some(before)
+    }
+
+    /// Steps forward over the last undone edit, returning the configuration after it.
+    pub fn redo(&mut self) -> Option<Configuration> {
+        let edit = self.redo.pop()?;
+        let after = edit.after.clone();
+        self.undo.push(edit);
+        Some(after)
+    }
+}
+
+impl GameState {
+    /// Reverts the last edit, false if there's none.
+    pub fn undo(&mut self) -> bool {
+        let Some(before) = self.edits.undo() else {
+            return false;
+        };
+        before.restore(self);
+        true
+    }
+
+    /// Applies the last undone edit again, false if there's none.
+    pub fn redo(&mut self) -> bool {
+        let Some(after) = self.edits.redo() else {
+            return false;
+        };
+        after.restore(self);
+        true
+    }
+
+    /// Runs the editors in `show` and records what they changed, only in sandbox where
+    /// they're free to change.
+    pub(crate) fn recording_edits(
+        &mut self,
+        ui: &mut egui::Ui,
+        show: impl FnOnce(&mut Self, &mut egui::Ui),
+    ) {
+        if !self.mode.free_sliders() {
+            show(self, ui);
+            return;
+        }
+        let before = Configuration::capture(self);
+        show(self, ui);
+        let after = Configuration::capture(self);
+        let dragging = ui.input(|i| i.pointer.any_down());
+        self.edits.record(before, &after, dragging);
+    }
+}
@@ -0,0 +1,95 @@
+//! What gold buys for the attacks in survival, where their sliders are locked. Every
+//! purchase of the same upgrade on the same attack costs more than the one before.
+
+use crate::character_sheet::{CharacterStat, Modifier};
+use crate::combat::{AttackKind, BasicAttack};
+use crate::i18n::tr;
+
+/// Multiplier on the cost of every further purchase.
+pub const COST_GROWTH: f32 = 1.5;
+/// Caps matching the ends of the sliders.
+const MAX_RANGE: f32 = 50.;
+const MAX_TARGETS: usize = 10;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Upgrade {
+    Damage,
+    Range,
+    Cooldown,
+    MaxTargets,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 4] = [
+        Upgrade::Damage,
+        Upgrade::Range,
+        Upgrade::Cooldown,
+        Upgrade::MaxTargets,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        tr(match self {
+            Upgrade::Damage => "+15% damage",
+            Upgrade::Range => "+3 range",
+            Upgrade::Cooldown => "-10% cooldown",
+            Upgrade::MaxTargets => "+1 target",
+        })
+    }
+
+    fn base_cost(&self) -> u32 {
+        match self {
+            Upgrade::Damage => 20,
+            Upgrade::Range => 15,
+            Upgrade::Cooldown => 25,
+            Upgrade::MaxTargets => 40,
+        }
+    }
+
+    /// Gold the next one costs after `bought` of them.
+    pub fn cost(&self, bought: usize) -> u32 {
+        (self.base_cost() as f32 * COST_GROWTH.powi(bought as i32)).round() as u32
+    }
+
+    /// Whether `attack` can't get any better at it.
+    pub fn is_maxed(&self, attack: &BasicAttack) -> bool {
+        match self {
+            Upgrade::Range => attack.range >= MAX_RANGE,
+            Upgrade::MaxTargets => attack.max_targets >= MAX_TARGETS,
+            Upgrade::Damage | Upgrade::Cooldown => false,
+        }
+    }
+
+    /// The stat of `kind` it changes.
+    pub fn stat(&self, kind: AttackKind) -> CharacterStat {
+        match self {
+            Upgrade::Damage => CharacterStat::Damage(kind),
+            Upgrade::Range => CharacterStat::Range(kind),
+            Upgrade::Cooldown => CharacterStat::Cooldown(kind),
+            Upgrade::MaxTargets => CharacterStat::Targets(kind),
+        }
+    }
+
+    pub fn modifier(&self) -> Modifier {
+        match self {
+            Upgrade::Damage => Modifier::Multiply(1.15),
+            Upgrade::Range => Modifier::Add(3.),
+            Upgrade::Cooldown => Modifier::Multiply(0.9),
+            Upgrade::MaxTargets => Modifier::Add(1.),
+        }
+    }
+
+    pub fn apply(&self, attack: &mut BasicAttack) {
+        let modifier = self.modifier();
+        match self {
+            Upgrade::Damage => attack.damage = modifier.apply(attack.damage),
+            Upgrade::Range => attack.range = modifier.apply(attack.range).min(MAX_RANGE),
+            Upgrade::Cooldown => {
+                attack.cooldown_timer.total = modifier.apply(attack.cooldown_timer.total)
+            }
+            Upgrade::MaxTargets => {
+                let targets = modifier.apply(attack.max_targets as f32) as usize;
+                attack.max_targets = targets.min(MAX_TARGETS);
+            }
+        }
+    }
+}
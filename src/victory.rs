@@ -0,0 +1,103 @@
+//! What ends a run in victory. Without a condition a run goes on until the Excellency falls.
+
+use crate::durations;
+use crate::i18n::{tr, trf};
+use crate::waves::{WavePhase, Waves};
+
+/// Laurels paid for every minute survived.
+const LAURELS_PER_MINUTE: f32 = 10.;
+/// Laurels paid for every wave cleared.
+const LAURELS_PER_WAVE: u32 = 10;
+const LAURELS_FOR_FINAL_BOSS: u32 = 100;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Victory {
+    /// Wave `n` stopped spawning and its enemies are all gone.
+    ClearWave(u32),
+    /// The Excellency is still standing after this many seconds.
+    Survive(f32),
+    /// The boss of the last wave with one has fallen, see `new_game_plus::milestone_wave`.
+    KillFinalBoss,
+}
+
+impl Victory {
+    pub fn describe(&self) -> String {
+        match *self {
+            Victory::ClearWave(wave) => trf("Clear wave {}", &[wave.to_string()]),
+            Victory::Survive(seconds) => trf("Survive for {}", &[durations::format(seconds)]),
+            Victory::KillFinalBoss => tr("Kill the final boss").to_owned(),
+        }
+    }
+
+    /// Laurels a victory earns, the longer the way to it the more.
+    pub fn laurels(&self) -> u32 {
+        match *self {
+            Victory::ClearWave(wave) => wave * LAURELS_PER_WAVE,
+            Victory::Survive(seconds) => (seconds / 60. * LAURELS_PER_MINUTE) as u32,
+            Victory::KillFinalBoss => LAURELS_FOR_FINAL_BOSS,
+        }
+    }
+
+    pub fn is_met(
+        &self,
+        waves: &Waves,
+        elapsed: f32,
+        enemies_left: usize,
+        final_boss_killed: bool,
+    ) -> bool {
+        match *self {
+            Victory::ClearWave(wave) => {
+                let spawning_done = waves.number > wave
+                    || (waves.number == wave && waves.phase == WavePhase::Break);
+                spawning_done && enemies_left == 0
+            }
+            Victory::Survive(seconds) => elapsed >= seconds,
+            Victory::KillFinalBoss => final_boss_killed,
+        }
+    }
+}
+
+/// Picks how the next regular run is won, or that it's endless.
+pub(crate) fn show_picker(ui: &mut egui::Ui, victory: &mut Option<Victory>) {
+    let kinds = [
+        None,
+        Some(Victory::Survive(600.)),
+        Some(Victory::ClearWave(10)),
+        Some(Victory::KillFinalBoss),
+    ];
+    egui::ComboBox::from_id_source("victory_picker")
+        .selected_text(kind_name(victory))
+        .show_ui(ui, |ui| {
+            for kind in kinds {
+                let selected = kind_name(&kind) == kind_name(victory);
+                if ui.selectable_label(selected, kind_name(&kind)).clicked() && !selected {
+                    *victory = kind;
+                }
+            }
+        });
+    match victory {
+        Some(Victory::Survive(seconds)) => {
+            let mut minutes = *seconds / 60.;
+            let picker = egui::DragValue::new(&mut minutes)
+                .clamp_range(1.0..=120.0)
+                .speed(0.1)
+                .suffix(tr(" min"));
+            if ui.add(picker).changed() {
+                *seconds = minutes.round() * 60.;
+            }
+        }
+        Some(Victory::ClearWave(wave)) => {
+            ui.add(egui::DragValue::new(wave).clamp_range(1..=100));
+        }
+        Some(Victory::KillFinalBoss) | None => {}
+    }
+}
+
+fn kind_name(victory: &Option<Victory>) -> &'static str {
+    match victory {
+        None => tr("Endless"),
+        Some(Victory::Survive(_)) => tr("Survive"),
+        Some(Victory::ClearWave(_)) => tr("Clear wave"),
+        Some(Victory::KillFinalBoss) => tr("Kill the final boss"),
+    }
+}
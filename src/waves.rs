@@ -0,0 +1,102 @@
+//! The rhythm of a run: waves of enemies with breaks in between.
+
+use crate::durations;
+use crate::i18n::trf;
+use crate::timer::Timer;
+
+/// Gold for every second of a break skipped by calling the next wave early.
+pub const EARLY_GOLD_PER_SECOND: f32 = 2.;
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, PartialEq)]
+pub enum WavePhase {
+    /// Enemies are spawning.
+    Active,
+    /// A breather before the next wave.
+    Break,
+}
+
+/// Waves alternate between spawning enemies for `active_duration` and a quiet break.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Waves {
+    /// The running wave, or the last finished one during a break.
+    pub number: u32,
+    pub phase: WavePhase,
+    /// Counts down the current phase.
+    pub timer: Timer,
+    pub active_duration: f32,
+    pub break_duration: f32,
+}
+
+impl Default for Waves {
+    fn default() -> Self {
+        let active_duration = 30.;
+        Self {
+            number: 1,
+            phase: WavePhase::Active,
+            timer: Timer::new(active_duration),
+            active_duration,
+            break_duration: 10.,
+        }
+    }
+}
+
+impl Waves {
+    /// Returns whether a new wave started.
+    pub fn tick(&mut self, delta: f32) -> bool {
+        if self.timer.tick(delta) == 0 {
+            return false;
+        }
+        match self.phase {
+            WavePhase::Active => {
+                self.phase = WavePhase::Break;
+                self.timer = Timer::new(self.break_duration);
+                false
+            }
+            WavePhase::Break => {
+                self.number += 1;
+                self.phase = WavePhase::Active;
+                self.timer = Timer::new(self.active_duration);
+                true
+            }
+        }
+    }
+
+    /// Seconds until the next wave starts, counting the break after a running one.
+    pub fn countdown(&self) -> f32 {
+        match self.phase {
+            WavePhase::Active => self.timer.remaining + self.break_duration,
+            WavePhase::Break => self.timer.remaining,
+        }
+    }
+
+    /// Cuts a break short so the next wave starts on the following tick. Returns the
+    /// seconds skipped, nothing while a wave is running.
+    pub fn call_early(&mut self) -> Option<f32> {
+        if self.phase != WavePhase::Break {
+            return None;
+        }
+        let skipped = self.timer.remaining;
+        self.timer.remaining = 0.;
+        Some(skipped)
+    }
+
+    /// What's going on, for the run summary.
+    pub fn status(&self) -> String {
+        match self.phase {
+            WavePhase::Active => trf(
+                "Wave {} - {} left",
+                &[
+                    self.number.to_string(),
+                    durations::format(self.timer.remaining),
+                ],
+            ),
+            WavePhase::Break => trf(
+                "Wave {} in {}",
+                &[
+                    (self.number + 1).to_string(),
+                    durations::format(self.timer.remaining),
+                ],
+            ),
+        }
+    }
+}
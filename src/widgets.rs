@@ -0,0 +1,130 @@
+//! Small widgets shared by the panels.
+
+use std::ops::RangeInclusive;
+
+fn touch_id() -> egui::Id {
+    egui::Id::new("touch_layout")
+}
+
+/// Switches `info_popup` between hover and tap, set whenever the layout changes.
+pub fn set_touch(ctx: &egui::Context, touch: bool) {
+    ctx.data_mut(|data| data.insert_temp(touch_id(), touch));
+}
+
+/// Shows `add_contents` on hover, or in the touch layout, where nothing hovers, while the
+/// widget is tapped open. Only one popup is open at a time, tapping it again closes it.
+pub fn info_popup(
+    response: egui::Response,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) -> egui::Response {
+    let ctx = response.ctx.clone();
+    if !ctx.data(|data| data.get_temp(touch_id())).unwrap_or(false) {
+        return response.on_hover_ui(add_contents);
+    }
+    let response = response.interact(egui::Sense::click());
+    let open_id = egui::Id::new("open_info_popup");
+    let mut open = ctx.data(|data| data.get_temp::<egui::Id>(open_id));
+    if response.clicked() {
+        open = (open != Some(response.id)).then_some(response.id);
+        ctx.data_mut(|data| match open {
+            Some(id) => data.insert_temp(open_id, id),
+            None => data.remove::<egui::Id>(open_id),
+        });
+    }
+    if open == Some(response.id) {
+        egui::show_tooltip_for(&ctx, response.id.with("info"), &response.rect, add_contents);
+    }
+    response
+}
+
+/// Explains a stat on hover: what the mechanic does, then the values derived from it.
+pub fn stat_tooltip(
+    response: egui::Response,
+    explanation: &str,
+    derived: &[(&str, String)],
+) -> egui::Response {
+    info_popup(response, |ui| {
+        ui.set_max_width(280.);
+        ui.label(explanation);
+        if !derived.is_empty() {
+            ui.separator();
+            egui::Grid::new("stat_tooltip").show(ui, |ui| {
+                for (name, value) in derived {
+                    ui.label(*name);
+                    ui.strong(value);
+                    ui.end_row();
+                }
+            });
+        }
+    })
+}
+
+/// A labelled slider for a stat, hovering either the label or the slider shows its tooltip.
+pub fn stat_slider<Num: egui::emath::Numeric>(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Num,
+    range: RangeInclusive<Num>,
+    explanation: &str,
+    derived: &[(&str, String)],
+) -> egui::Response {
+    ui.horizontal(|ui| {
+        stat_tooltip(ui.label(label), explanation, derived);
+        stat_tooltip(
+            ui.add(egui::Slider::new(value, range)),
+            explanation,
+            derived,
+        )
+    })
+    .inner
+}
+
+/// A square slot of the ability bar, darkened from the top by the part of the cooldown left
+/// and marked with the key casting it.
+pub fn ability_slot(
+    ui: &mut egui::Ui,
+    name: &str,
+    key: Option<&str>,
+    cooldown_left: f32,
+    castable: bool,
+) -> egui::Response {
+    let sense = match castable {
+        true => egui::Sense::click(),
+        false => egui::Sense::hover(),
+    };
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(88., 52.), sense);
+    if !ui.is_rect_visible(rect) {
+        return response;
+    }
+    let visuals = ui.style().interact(&response);
+    let painter = ui.painter();
+    painter.rect(rect, visuals.rounding, visuals.bg_fill, visuals.bg_stroke);
+    let mut sweep = rect;
+    sweep.set_height(rect.height() * cooldown_left.clamp(0., 1.));
+    painter.rect_filled(
+        sweep,
+        visuals.rounding,
+        egui::Color32::from_black_alpha(150),
+    );
+    let text_color = match castable {
+        true => visuals.text_color(),
+        false => ui.visuals().weak_text_color(),
+    };
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        name,
+        egui::FontId::proportional(13.),
+        text_color,
+    );
+    if let Some(key) = key {
+        painter.text(
+            rect.left_top() + egui::vec2(4., 2.),
+            egui::Align2::LEFT_TOP,
+            key,
+            egui::FontId::monospace(11.),
+            visuals.text_color(),
+        );
+    }
+    response
+}
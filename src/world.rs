@@ -0,0 +1,137 @@
+//! A small entity store: stable handles for the things on the field, and columns of
+//! optional data keyed by them.
+//!
+//! Enemies stay a plain list sorted by distance, which is the order every system walks
+//! them in. What only some of them carry, or what a feature wants to attach without adding
+//! a field to `Enemy`, goes into a `Components` column instead, which `retain_alive` prunes of
+//! the entities that left the field.
+
+/// Handle to something on the field, never reused within a run.
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+    Default,
+)]
+pub struct Entity(pub u64);
+
+/// Hands out entity handles.
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+pub struct Entities {
+    next: u64,
+}
+
+impl Entities {
+    pub fn spawn(&mut self) -> Entity {
+        let entity = Entity(self.next);
+        self.next += 1;
+        entity
+    }
+
+    /// Gives every entity in `entities` a fresh handle unless they're already distinct and
+    /// were handed out by this allocator. Saves from before handles have them all at 0.
+    pub fn adopt<'a>(&mut self, entities: impl IntoIterator<Item = &'a mut Entity>) {
+        let mut entities: Vec<&mut Entity> = entities.into_iter().collect();
+        let mut seen: Vec<Entity> = entities.iter().map(|entity| **entity).collect();
+        seen.sort();
+        seen.dedup();
+        let valid =
+            seen.len() == entities.len() && seen.last().map_or(true, |last| last.0 < self.next);
+        if !valid {
+            for entity in &mut entities {
+                **entity = self.spawn();
+            }
+        }
+    }
+}
+
+/// One kind of data for any number of entities, sorted by entity.
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Components<T>(Vec<(Entity, T)>);
+
+impl<T> Default for Components<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> Components<T> {
+    fn position(&self, entity: Entity) -> Result<usize, usize> {
+        self.0.binary_search_by_key(&entity, |(other, _)| *other)
+    }
+
+    /// Returns the value `entity` had before, if any.
+    pub fn insert(&mut self, entity: Entity, value: T) -> Option<T> {
+        match self.position(entity) {
+            Ok(index) => Some(std::mem::replace(&mut self.0[index].1, value)),
+            Err(index) => {
+                self.0.insert(index, (entity, value));
+                None
+            }
+        }
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&T> {
+        let index = self.position(entity).ok()?;
+        Some(&self.0[index].1)
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
+        let index = self.position(entity).ok()?;
+        Some(&mut self.0[index].1)
+    }
+
+    /// The value for `entity`, inserting `T::default()` first if it has none.
+    pub fn entry(&mut self, entity: Entity) -> &mut T
+    where
+        T: Default,
+    {
+        let index = match self.position(entity) {
+            Ok(index) => index,
+            Err(index) => {
+                self.0.insert(index, (entity, T::default()));
+                index
+            }
+        };
+        &mut self.0[index].1
+    }
+
+    pub fn remove(&mut self, entity: Entity) -> Option<T> {
+        let index = self.position(entity).ok()?;
+        Some(self.0.remove(index).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &T)> {
+        self.0.iter().map(|(entity, value)| (*entity, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut T)> {
+        self.0.iter_mut().map(|(entity, value)| (*entity, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Keeps only the entries `keep` returns true for, it may change them along the way.
+    pub fn retain(&mut self, mut keep: impl FnMut(Entity, &mut T) -> bool) {
+        self.0.retain_mut(|(entity, value)| keep(*entity, value));
+    }
+
+    /// Drops the values of entities that are gone, `alive` must be sorted.
+    pub fn retain_alive(&mut self, alive: &[Entity]) {
+        self.0
+            .retain(|(entity, _)| alive.binary_search(entity).is_ok());
+    }
+}
@@ -0,0 +1,56 @@
+use your_excellency::ascendancy::{shards, Ascendancy, Perk, MIN_WAVE};
+use your_excellency::character_sheet::{CharacterSheet, CharacterStat};
+use your_excellency::combat::AttackKind;
+use your_excellency::excellency::Excellency;
+use your_excellency::GameState;
+
+#[test]
+fn deeper_runs_pay_more_shards() {
+    assert_eq!(shards(MIN_WAVE - 1, 3), 0);
+    assert_eq!(shards(MIN_WAVE, 0), 1);
+    assert_eq!(shards(MIN_WAVE + 5, 0), 2);
+    assert_eq!(shards(MIN_WAVE + 5, 1), 4);
+}
+
+#[test]
+fn perks_are_bought_rank_by_rank_up_to_their_max() {
+    let mut ascendancy = Ascendancy::default();
+    ascendancy.shards = 100;
+    assert!(ascendancy.buy(Perk::ExtraTalentSlot));
+    assert!(!ascendancy.buy(Perk::ExtraTalentSlot));
+    assert_eq!(ascendancy.rank(Perk::ExtraTalentSlot), 1);
+    let spent: u32 = (0..2).map(|rank| Perk::BaseDamage.cost(rank)).sum();
+    let left = ascendancy.shards;
+    assert!(ascendancy.buy(Perk::BaseDamage));
+    assert!(ascendancy.buy(Perk::BaseDamage));
+    assert_eq!(ascendancy.shards, left - spent);
+    assert_eq!(ascendancy.rank(Perk::BaseDamage), 2);
+}
+
+#[test]
+fn perks_need_the_shards() {
+    let mut ascendancy = Ascendancy::default();
+    assert!(!ascendancy.buy(Perk::StartingGold));
+    assert_eq!(ascendancy.rank(Perk::StartingGold), 0);
+}
+
+#[test]
+fn damage_perk_shows_on_the_character_sheet() {
+    let state = GameState::with_seed(1);
+    let json = serde_json::to_string(state.excellency()).unwrap();
+    let mut excellency: Excellency = serde_json::from_str(&json).unwrap();
+    let base = excellency.basic_attack.damage;
+    let mut ascendancy = Ascendancy::default();
+    ascendancy.shards = 100;
+    assert!(ascendancy.buy(Perk::BaseDamage));
+    ascendancy.apply(&mut excellency);
+    let sheet = CharacterSheet::compute(&excellency);
+    let breakdown = sheet
+        .breakdown(CharacterStat::Damage(AttackKind::Basic))
+        .unwrap();
+    assert_eq!(breakdown.contributions.len(), 1);
+    assert_eq!(breakdown.contributions[0].source, Perk::BaseDamage.name());
+    assert!((breakdown.base - base).abs() < 1e-4);
+    assert!((breakdown.total - excellency.basic_attack.damage).abs() < 1e-4);
+    assert!(excellency.basic_attack.damage > base);
+}
@@ -0,0 +1,77 @@
+use your_excellency::auras::{Aura, BURN_DAMAGE};
+use your_excellency::enemy::Enemy;
+use your_excellency::events::{DamageSource, GameEvent};
+use your_excellency::excellency::Excellency;
+use your_excellency::GameState;
+
+const TOUGH: f32 = 10_000.;
+
+/// A fresh run with `auras` already unlocked.
+fn with_auras(auras: &[Aura]) -> GameState {
+    let mut save: serde_json::Value =
+        serde_json::from_str(&GameState::with_seed(1).to_json().unwrap()).unwrap();
+    save["excellency"]["auras"] = serde_json::to_value(auras).unwrap();
+    GameState::from_json(&save.to_string()).unwrap()
+}
+
+/// How far the tough enemy put on the field got.
+fn tough_distance(state: &GameState) -> f32 {
+    let tough = state
+        .enemies()
+        .iter()
+        .find(|enemy| enemy.hp.maximum == TOUGH);
+    tough.expect("It can't die").distance.0
+}
+
+#[test]
+fn unlocking_spends_gold_once() {
+    let json = serde_json::to_string(GameState::with_seed(1).excellency()).unwrap();
+    let mut excellency: Excellency = serde_json::from_str(&json).unwrap();
+    excellency.gold = Aura::Frost.cost() + Aura::Burn.cost() - 1;
+    assert!(excellency.unlock(Aura::Frost));
+    assert!(!excellency.unlock(Aura::Frost));
+    assert_eq!(excellency.gold, Aura::Burn.cost() - 1);
+    assert!(!excellency.unlock(Aura::Burn));
+    assert_eq!(excellency.auras, vec![Aura::Frost]);
+}
+
+#[test]
+fn burning_ticks_only_inside_the_radius() {
+    let mut state = with_auras(&[Aura::Burn]);
+    let radius = Aura::Burn.radius();
+    state.add_enemies([
+        Enemy::new(TOUGH, radius / 2.),
+        Enemy::new(TOUGH, radius * 5.),
+    ]);
+    state.take_events();
+    for _ in 0..10 {
+        state.step(0.1);
+    }
+    let ticks: Vec<_> = state
+        .take_events()
+        .into_iter()
+        .filter_map(|event| match event {
+            GameEvent::DamageTicked { amount, distance } => Some((amount, distance)),
+            _ => None,
+        })
+        .collect();
+    assert!(!ticks.is_empty());
+    for (amount, distance) in ticks {
+        assert_eq!(amount, BURN_DAMAGE);
+        assert!(distance <= radius);
+    }
+    assert!(state.damage_by_source().get(DamageSource::Aura) > 0.);
+}
+
+#[test]
+fn frost_holds_enemies_back() {
+    let mut frosted = with_auras(&[Aura::Frost]);
+    let mut plain = with_auras(&[]);
+    for state in [&mut frosted, &mut plain] {
+        state.add_enemies([Enemy::new(TOUGH, Aura::Frost.radius() - 1.)]);
+        for _ in 0..20 {
+            state.step(0.1);
+        }
+    }
+    assert!(tough_distance(&frosted) > tough_distance(&plain));
+}
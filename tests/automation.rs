@@ -0,0 +1,97 @@
+use your_excellency::automation::{cheapest_upgrade, Automation, Automations};
+use your_excellency::combat::AttackKind;
+use your_excellency::events::GameEvent;
+use your_excellency::excellency::Excellency;
+use your_excellency::upgrades::Upgrade;
+use your_excellency::GameState;
+
+#[test]
+fn unlocking_spends_laurels_once() {
+    let mut automations = Automations::default();
+    let mut laurels = Automation::BuyUpgrades.cost() - 1;
+    assert!(!automations.unlock(Automation::BuyUpgrades, &mut laurels));
+    laurels += 1;
+    assert!(automations.unlock(Automation::BuyUpgrades, &mut laurels));
+    assert_eq!(laurels, 0);
+    assert!(automations.is_on(Automation::BuyUpgrades));
+    assert!(!automations.unlock(Automation::BuyUpgrades, &mut laurels));
+}
+
+#[test]
+fn only_unlocked_automations_switch_on() {
+    let mut automations = Automations::default();
+    automations.set(Automation::CallWaves, true);
+    assert!(!automations.is_on(Automation::CallWaves));
+    let mut laurels = 100;
+    automations.unlock(Automation::CallWaves, &mut laurels);
+    automations.set(Automation::CallWaves, false);
+    assert!(!automations.is_on(Automation::CallWaves));
+    automations.set(Automation::CallWaves, true);
+    automations.set(Automation::CallWaves, true);
+    assert!(automations.is_on(Automation::CallWaves));
+    automations.set(Automation::CallWaves, false);
+    assert!(!automations.is_on(Automation::CallWaves));
+}
+
+/// A copy of a fresh run's Excellency with `gold`.
+fn excellency(gold: u32) -> Excellency {
+    let json = serde_json::to_string(GameState::with_seed(1).excellency()).unwrap();
+    let mut excellency: Excellency = serde_json::from_str(&json).unwrap();
+    excellency.gold = gold;
+    excellency
+}
+
+#[test]
+fn buys_the_cheapest_affordable_upgrade() {
+    let cheapest = Upgrade::ALL
+        .into_iter()
+        .min_by_key(|upgrade| upgrade.cost(0))
+        .unwrap();
+    let affordable = excellency(cheapest.cost(0));
+    assert_eq!(
+        cheapest_upgrade(&affordable),
+        Some((AttackKind::Basic, cheapest))
+    );
+    assert_eq!(cheapest_upgrade(&excellency(cheapest.cost(0) - 1)), None);
+    // Once the basic attack's is pricier, the big attack's first one is the cheapest
+    let mut bought = excellency(1000);
+    assert!(bought.buy_upgrade(AttackKind::Basic, cheapest));
+    assert_eq!(cheapest_upgrade(&bought), Some((AttackKind::Big, cheapest)));
+}
+
+/// Whether stepping a run with `automation` on for a while emits an event `matches`.
+fn acts(automation: Automation, matches: fn(&GameEvent) -> bool) -> bool {
+    let mut state = GameState::with_seed(4);
+    let mut laurels = automation.cost();
+    assert!(state.automations_mut().unlock(automation, &mut laurels));
+    let mut seen = false;
+    for _ in 0..3000 {
+        state.step(0.1);
+        seen |= state.take_events().iter().any(matches);
+    }
+    seen
+}
+
+#[test]
+fn switched_on_automations_act_every_step() {
+    assert!(acts(Automation::CallWaves, |event| matches!(
+        event,
+        GameEvent::WaveCalledEarly { .. }
+    )));
+    assert!(acts(Automation::CastUltimate, |event| matches!(
+        event,
+        GameEvent::UltimateFired { .. }
+    )));
+}
+
+#[test]
+fn nothing_acts_without_automations() {
+    let mut state = GameState::with_seed(4);
+    for _ in 0..3000 {
+        state.step(0.1);
+        assert!(!state.take_events().iter().any(|event| matches!(
+            event,
+            GameEvent::WaveCalledEarly { .. } | GameEvent::UltimateFired { .. }
+        )));
+    }
+}
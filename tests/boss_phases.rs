@@ -0,0 +1,40 @@
+use your_excellency::boss_phases::{actions, BossPhase, PhaseAction};
+use your_excellency::combat::HitPoints;
+
+fn at(current: f32) -> HitPoints {
+    HitPoints {
+        current,
+        ..HitPoints::new_full(100.)
+    }
+}
+
+#[test]
+fn phases_start_once_below_their_thresholds() {
+    let mut phase = BossPhase::default();
+    assert!(phase.update(&at(80.)).is_empty());
+    assert_eq!(phase.update(&at(74.)), 1..2);
+    assert!(phase.update(&at(60.)).is_empty());
+    // Healing back up doesn't enter a phase twice
+    assert!(phase.update(&at(90.)).is_empty());
+    assert!(phase.update(&at(70.)).is_empty());
+    assert_eq!(phase.phase, 1);
+}
+
+#[test]
+fn a_big_hit_skips_through_phases_without_losing_them() {
+    let mut phase = BossPhase::default();
+    let entered: Vec<PhaseAction> = phase
+        .update(&at(10.))
+        .flat_map(|phase| actions(phase).iter().copied())
+        .collect();
+    assert_eq!(
+        entered,
+        [
+            PhaseAction::Enrage,
+            PhaseAction::Summon,
+            PhaseAction::Enrage,
+            PhaseAction::Summon
+        ]
+    );
+    assert_eq!(phase.phase, 3);
+}
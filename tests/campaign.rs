@@ -0,0 +1,117 @@
+use your_excellency::content::Content;
+use your_excellency::victory::Victory;
+use your_excellency::GameState;
+
+#[test]
+fn stars_follow_the_hp_left() {
+    let content = Content::default();
+    let stage = content.stage("Heavy Infantry").unwrap();
+    assert_eq!(stage.stars(0.1), 1);
+    assert_eq!(stage.stars(0.6), 2);
+    assert_eq!(stage.stars(0.95), 3);
+}
+
+#[test]
+fn stage_content_plays_the_authored_waves() {
+    let content = Content::default();
+    let stage = content.stage("Heavy Infantry").unwrap();
+    let stage_content = content.for_stage(stage);
+    assert_eq!(stage_content.compositions.len(), 4);
+    assert_eq!(stage_content.compositions[2].from_wave, 3);
+    assert!(!stage_content.compositions[2].weights.contains_key("grunt"));
+    assert_eq!(stage_content.bosses(3).count(), 0);
+    let bosses: Vec<_> = stage_content.bosses(4).map(|(name, _)| name).collect();
+    assert_eq!(bosses, ["warlord"]);
+}
+
+#[test]
+fn surviving_the_stage_wins_it() {
+    let mut state = GameState::with_stage(7, "Ambush").unwrap();
+    for _ in 0..950 {
+        state.step(0.1);
+    }
+    assert!(!state.is_defeated());
+    assert!(state.is_victorious());
+    assert!(state.stars().is_some());
+    // A won run stands still
+    let elapsed = state.elapsed();
+    state.step(0.1);
+    assert_eq!(state.elapsed(), elapsed);
+}
+
+#[test]
+fn clearing_wave_needs_every_enemy_gone() {
+    let mut state = GameState::with_seed(1);
+    let victory = Victory::ClearWave(1);
+    assert!(!victory.is_met(state.waves(), 0., 0, false));
+    while state.waves().number == 1 {
+        state.step(0.5);
+    }
+    assert!(victory.is_met(state.waves(), 0., 0, false));
+    assert!(!victory.is_met(state.waves(), 0., 3, false));
+    assert!(GameState::with_stage(1, "Nowhere").is_none());
+}
+
+#[test]
+fn final_boss_victory_waits_for_the_kill() {
+    let victory = Victory::KillFinalBoss;
+    let state = GameState::with_seed(1);
+    assert!(!victory.is_met(state.waves(), 1e6, 0, false));
+    assert!(victory.is_met(state.waves(), 0., 5, true));
+}
+
+#[test]
+fn regular_runs_can_be_won() {
+    let mut state = GameState::with_victory(3, Victory::Survive(30.));
+    for _ in 0..310 {
+        state.step(0.1);
+    }
+    assert!(state.is_victorious());
+    assert!(state.stars().is_none());
+    assert!(!GameState::with_seed(3).is_victorious());
+}
+
+#[test]
+fn longer_victories_earn_more_laurels() {
+    assert!(Victory::Survive(600.).laurels() > Victory::Survive(60.).laurels());
+    assert!(Victory::ClearWave(10).laurels() > Victory::ClearWave(2).laurels());
+    assert!(Victory::KillFinalBoss.laurels() > 0);
+}
+
+#[test]
+fn stages_lead_on_to_the_next_one() {
+    let content = Content::default();
+    let first = &content.stages[0].name;
+    let second = content.stages[1].name.clone();
+    let state = GameState::with_stage(1, first).unwrap();
+    assert_eq!(state.next_stage(), Some(second));
+    let last = &content.stages.last().unwrap().name;
+    assert_eq!(GameState::with_stage(1, last).unwrap().next_stage(), None);
+    assert_eq!(GameState::with_seed(1).next_stage(), None);
+}
+
+#[test]
+fn auto_continue_moves_on_to_the_next_stage() {
+    let mut state = GameState::with_stage(7, "Ambush").unwrap();
+    let next = state.next_stage().unwrap();
+    state.set_auto_continue(true, 0.);
+    for _ in 0..950 {
+        state.step(0.1);
+    }
+    assert_eq!(state.stage(), Some(next.as_str()));
+    assert!(!state.is_victorious());
+}
+
+#[test]
+fn auto_continue_stops_once_hp_runs_low() {
+    let mut state = GameState::with_stage(7, "Ambush").unwrap();
+    // Any damage at all is too much
+    state.set_auto_continue(true, 1.);
+    for _ in 0..950 {
+        state.step(0.1);
+    }
+    let hp = &state.excellency().hp;
+    assert!(hp.current < hp.maximum);
+    assert!(state.is_victorious());
+    assert_eq!(state.stage(), Some("Ambush"));
+}
@@ -0,0 +1,66 @@
+use your_excellency::character_sheet::{CharacterSheet, CharacterStat, Modifier};
+use your_excellency::combat::AttackKind;
+use your_excellency::excellency::Excellency;
+use your_excellency::talents::Talent;
+use your_excellency::upgrades::Upgrade;
+use your_excellency::GameState;
+
+#[test]
+fn fresh_run_is_all_base() {
+    let state = GameState::with_seed(1);
+    let sheet = state.character_sheet();
+    assert_eq!(sheet.breakdowns.len(), CharacterStat::all().len());
+    for breakdown in &sheet.breakdowns {
+        assert!(breakdown.contributions.is_empty());
+        assert_eq!(breakdown.base, breakdown.total);
+        assert_eq!(breakdown.total, breakdown.stat.value(state.excellency()));
+    }
+}
+
+#[test]
+fn undo_reverts_apply() {
+    for modifier in [Modifier::Add(3.), Modifier::Multiply(1.15)] {
+        assert!((modifier.undo(modifier.apply(7.)) - 7.).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn talents_and_upgrades_touch_the_stat_they_name() {
+    assert_eq!(
+        Talent::SharpEdge.modifier().0,
+        CharacterStat::Damage(AttackKind::Basic)
+    );
+    assert_eq!(
+        Upgrade::Cooldown.stat(AttackKind::Big),
+        CharacterStat::Cooldown(AttackKind::Big)
+    );
+    for talent in Talent::ALL {
+        assert_ne!(talent.modifier().1.apply(1.), 1.);
+    }
+}
+
+#[test]
+fn capped_upgrades_keep_the_true_base() {
+    let state = GameState::with_seed(1);
+    let json = serde_json::to_string(state.excellency()).unwrap();
+    let mut excellency: Excellency = serde_json::from_str(&json).unwrap();
+    // Less than an upgrade short of the cap, so the upgrade overshoots it
+    let range = 49.;
+    excellency.basic_attack.range = range;
+    excellency.gold = u32::MAX / 2;
+    while excellency.buy_upgrade(AttackKind::Basic, Upgrade::Range) {}
+    let sheet = CharacterSheet::compute(&excellency);
+    let breakdown = sheet
+        .breakdown(CharacterStat::Range(AttackKind::Basic))
+        .unwrap();
+    assert_eq!(breakdown.base, range);
+    assert_eq!(breakdown.total, excellency.basic_attack.range);
+    // The last one was cut short by the cap
+    let uncapped = breakdown
+        .contributions
+        .iter()
+        .fold(breakdown.base, |value, contribution| {
+            contribution.modifier.apply(value)
+        });
+    assert!(uncapped > breakdown.total);
+}
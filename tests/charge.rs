@@ -0,0 +1,23 @@
+use your_excellency::charge::Charge;
+
+#[test]
+fn fills_up_to_required() {
+    let mut charge = Charge::new(10.);
+    charge.add(4.);
+    assert_eq!(charge.fraction(), 0.4);
+    assert!(!charge.is_full());
+    charge.add(20.);
+    assert_eq!(charge.current, 10.);
+    assert!(charge.is_full());
+}
+
+#[test]
+fn empty_starts_over() {
+    let mut charge = Charge::new(10.);
+    charge.add(10.);
+    charge.empty();
+    assert_eq!(charge.current, 0.);
+    // Negative amounts never drain it
+    charge.add(-5.);
+    assert_eq!(charge.current, 0.);
+}
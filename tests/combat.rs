@@ -0,0 +1,157 @@
+use your_excellency::combat::{
+    AttackPhase, BasicAttack, CastMode, DamageSpec, DamageType, TargetingStrategy,
+};
+use your_excellency::effects::Effects;
+use your_excellency::enemy::{around, count_in_range, Enemy};
+use your_excellency::timer::Timer;
+use your_excellency::world::Entity;
+
+fn attack(damage: f32, max_targets: usize, overkill_carry: bool) -> BasicAttack {
+    BasicAttack {
+        cooldown_timer: Timer::new(1.),
+        damage,
+        range: 50.,
+        max_targets,
+        targeting: TargetingStrategy::Closest,
+        overkill_carry,
+        wind_up: 0.,
+        phase: AttackPhase::Cooldown,
+        cast_mode: CastMode::Auto,
+        damage_type: DamageType::Normal,
+    }
+}
+
+#[test]
+fn overkill_is_lost_without_carry() {
+    let mut enemies = vec![Enemy::new(5., 1.), Enemy::new(20., 2.)];
+    let outcome = attack(30., 1, false).fire(&mut enemies, &Effects::default(), None);
+    assert_eq!(outcome.killed.len(), 1);
+    assert_eq!(outcome.damage_dealt, 5.);
+    assert_eq!(enemies[0].hp.current, 20.);
+}
+
+#[test]
+fn overkill_carries_past_max_targets() {
+    let mut enemies = vec![Enemy::new(5., 1.), Enemy::new(10., 2.), Enemy::new(20., 3.)];
+    let outcome = attack(30., 1, true).fire(&mut enemies, &Effects::default(), None);
+    assert_eq!(outcome.killed.len(), 2);
+    assert_eq!(outcome.damage_dealt, 30.);
+    assert_eq!(enemies[0].hp.current, 5.);
+}
+
+#[test]
+fn carried_damage_adds_to_the_next_hit() {
+    let mut enemies = vec![Enemy::new(5., 1.), Enemy::new(12., 2.)];
+    let outcome = attack(10., 2, true).fire(&mut enemies, &Effects::default(), None);
+    assert_eq!(outcome.killed.len(), 2);
+    assert!(enemies.is_empty());
+}
+
+#[test]
+fn wind_up_delays_the_shot() {
+    let mut attack = attack(1., 1, false);
+    attack.wind_up = 0.5;
+    assert_eq!(attack.tick(1.), 0);
+    assert_eq!(attack.phase, AttackPhase::WindingUp { remaining: 0.5 });
+    assert_eq!(attack.tick(0.25), 0);
+    assert_eq!(attack.tick(0.25), 1);
+    assert_eq!(attack.phase, AttackPhase::Cooldown);
+}
+
+#[test]
+fn getting_hit_interrupts_the_wind_up() {
+    let mut attack = attack(1., 1, false);
+    assert!(!attack.interrupt());
+    attack.wind_up = 0.5;
+    attack.tick(1.);
+    assert!(attack.interrupt());
+    assert_eq!(attack.phase, AttackPhase::Cooldown);
+    assert_eq!(attack.tick(0.9), 0);
+    assert_eq!(attack.cooldown_timer.remaining, 1. - 0.9);
+}
+
+#[test]
+fn held_attack_waits_for_a_cast() {
+    let mut attack = attack(1., 1, false);
+    attack.cast_mode = CastMode::Hold;
+    assert_eq!(attack.tick(1.), 0);
+    assert_eq!(attack.phase, AttackPhase::Ready);
+    assert_eq!(attack.tick(5.), 0);
+    attack.cast();
+    assert_eq!(attack.tick(0.1), 1);
+    assert_eq!(attack.phase, AttackPhase::Cooldown);
+}
+
+#[test]
+fn range_queries_search_the_sorted_field() {
+    let enemies: Vec<Enemy> = [1., 3., 3., 8., 12.]
+        .into_iter()
+        .map(|distance| Enemy::new(1., distance))
+        .collect();
+    assert_eq!(count_in_range(&enemies, 0.5), 0);
+    assert_eq!(count_in_range(&enemies, 3.), 3);
+    assert_eq!(count_in_range(&enemies, 50.), 5);
+    assert_eq!(around(&enemies, 5., 2.), 1..3);
+    assert_eq!(around(&enemies, 10., 2.), 3..5);
+}
+
+#[test]
+fn focused_targeting_hits_the_focused_enemy_first() {
+    let mut focusing = attack(10., 1, false);
+    focusing.targeting = TargetingStrategy::Focused;
+    let mut enemies = vec![
+        Enemy::new(50., 1.),
+        Enemy::new(50., 2.),
+        Enemy::new(50., 60.),
+    ];
+    for (id, enemy) in enemies.iter_mut().enumerate() {
+        enemy.id = Entity(id as u64 + 1);
+    }
+    focusing.fire(&mut enemies, &Effects::default(), Some(Entity(2)));
+    assert_eq!(enemies[1].hp.current, 40.);
+    assert_eq!(enemies[0].hp.current, 50.);
+    // Out of range, so the closest one takes the hit
+    focusing.fire(&mut enemies, &Effects::default(), Some(Entity(3)));
+    assert_eq!(enemies[0].hp.current, 40.);
+    assert_eq!(enemies[2].hp.current, 50.);
+}
+
+#[test]
+fn enemies_remember_the_damage_that_landed() {
+    let mut armored = Enemy::new(20., 1.);
+    armored.armor = 2.;
+    armored.shield = 5.;
+    armored.take_damage(10.);
+    armored.take_damage(30.);
+    // 8 and then the 17 left, shield included and overkill not
+    assert_eq!(armored.damage_taken, 25.);
+}
+
+#[test]
+fn percentage_damage_scales_with_the_target() {
+    let mut boss = Enemy::new(1000., 10.);
+    assert_eq!(boss.take_damage(DamageSpec::MaximumHp(0.1)), 100.);
+    assert_eq!(boss.take_damage(DamageSpec::CurrentHp(0.5)), 450.);
+    assert_eq!(boss.hp.current, 450.);
+}
+
+#[test]
+fn true_damage_ignores_armor_and_shields() {
+    let mut armored = Enemy::new(50., 1.);
+    armored.armor = 5.;
+    armored.shield = 10.;
+    assert_eq!(
+        armored.take_hit(DamageSpec::Flat(20.), DamageType::True),
+        20.
+    );
+    assert_eq!(armored.hp.current, 30.);
+    assert_eq!(armored.shield, 10.);
+
+    let mut piercing = attack(20., 1, false);
+    piercing.damage_type = DamageType::True;
+    let mut enemies = vec![armored];
+    let outcome = piercing.fire(&mut enemies, &Effects::default(), None);
+    assert_eq!(outcome.damage_dealt, 20.);
+    assert_eq!(outcome.true_damage, 20.);
+    assert_eq!(enemies[0].hp.current, 10.);
+}
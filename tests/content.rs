@@ -0,0 +1,87 @@
+use your_excellency::content::{Content, BOSSES_FILE, CAMPAIGN_FILE, ENEMIES_FILE, WAVES_FILE};
+
+/// Loads the content with only `file` on disk, reading `text`.
+fn load(file: &'static str, text: &'static str) -> (Content, Vec<String>) {
+    Content::load_with(move |read| (read == file).then(|| Ok(text.to_owned())))
+}
+
+fn assert_rejected(file: &str, errors: &[String], reason: &str) {
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].starts_with(file), "{errors:?}");
+    assert!(errors[0].contains(reason), "{errors:?}");
+}
+
+#[test]
+fn missing_files_fall_back_to_the_embedded_ones_quietly() {
+    let (content, errors) = Content::load_with(|_| None);
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(content.bosses.len(), Content::default().bosses.len());
+}
+
+#[test]
+fn archetypes_need_positive_stats() {
+    let (content, errors) = load(ENEMIES_FILE, r#"{"ghost": (hp: 0.0)}"#);
+    assert_rejected(ENEMIES_FILE, &errors, "needs positive hp");
+    assert!(!content.archetypes.contains_key("ghost"));
+    assert!(content.archetypes.contains_key("grunt"));
+}
+
+#[test]
+fn waves_cant_spawn_unknown_archetypes() {
+    let (content, errors) = load(WAVES_FILE, r#"[(from_wave: 1, weights: {"ghost": 1.0})]"#);
+    assert_rejected(WAVES_FILE, &errors, "unknown archetype \"ghost\"");
+    assert!(content.compositions[0].weights.contains_key("grunt"));
+}
+
+#[test]
+fn bosses_cant_be_unknown_archetypes() {
+    let (content, errors) = load(BOSSES_FILE, r#"[(wave: 5, archetype: "ghost")]"#);
+    assert_rejected(BOSSES_FILE, &errors, "unknown boss archetype \"ghost\"");
+    assert!(content
+        .bosses
+        .iter()
+        .all(|boss| boss.archetype == "warlord"));
+}
+
+#[test]
+fn bosses_cant_join_the_first_wave() {
+    let (content, errors) = load(BOSSES_FILE, r#"[(wave: 1, archetype: "warlord")]"#);
+    assert_rejected(BOSSES_FILE, &errors, "can't join before wave 2");
+    assert!(content.bosses.iter().all(|boss| boss.wave > 1));
+}
+
+#[test]
+fn stages_cant_use_unknown_archetypes() {
+    let (content, errors) = load(
+        CAMPAIGN_FILE,
+        r#"[(
+            name: "Haunted",
+            description: "",
+            waves: [(weights: {"grunt": 1.0}), (weights: {"grunt": 1.0}, bosses: ["ghost"])],
+            victory: ClearWave(2),
+        )]"#,
+    );
+    assert_rejected(CAMPAIGN_FILE, &errors, "unknown archetype \"ghost\"");
+    assert!(content.stage("Haunted").is_none());
+    assert!(content.stage("Outskirts").is_some());
+}
+
+#[test]
+fn unparsable_files_are_reported() {
+    let (content, errors) = load(WAVES_FILE, "[(from_wave: ");
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].starts_with(WAVES_FILE));
+    assert!(!content.compositions.is_empty());
+}
+
+#[test]
+fn waves_needing_archetypes_from_a_broken_enemies_file_fall_back_to_everything_built_in() {
+    let (content, errors) = Content::load_with(|file| match file {
+        ENEMIES_FILE => Some(Ok(r#"{"grunt": (), "ghost": ()}"#.to_owned())),
+        _ => None,
+    });
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert!(errors[0].contains(ENEMIES_FILE), "{errors:?}");
+    assert!(content.archetypes.contains_key("warlord"));
+    assert!(!content.archetypes.contains_key("ghost"));
+}
@@ -0,0 +1,78 @@
+use your_excellency::crowd_control::{Control, CrowdControl, DIMINISHING_RESET};
+use your_excellency::effects::Effects;
+use your_excellency::enemy::{advance, Enemy};
+use your_excellency::world::{Components, Entity};
+
+fn enemy(id: u64, distance: f32) -> Enemy {
+    Enemy {
+        id: Entity(id),
+        speed: 2.,
+        ..Enemy::new(10., distance)
+    }
+}
+
+#[test]
+fn repeated_holds_diminish_down_to_nothing() {
+    let mut crowd_control = CrowdControl::default();
+    let mut lasted = vec![];
+    for _ in 0..4 {
+        lasted.push(crowd_control.apply(Control::Stun, 2.));
+        crowd_control.tick(2.);
+        assert!(!crowd_control.is_held());
+    }
+    assert_eq!(lasted, [2., 1., 0.5, 0.]);
+}
+
+#[test]
+fn diminishing_returns_reset_after_a_quiet_spell() {
+    let mut crowd_control = CrowdControl::default();
+    crowd_control.apply(Control::Freeze, 1.);
+    crowd_control.tick(1. + DIMINISHING_RESET / 2.);
+    // Stuns and freezes count against each other
+    assert_eq!(crowd_control.apply(Control::Stun, 1.), 0.5);
+    crowd_control.tick(0.5 + DIMINISHING_RESET);
+    assert_eq!(crowd_control.apply(Control::Stun, 1.), 1.);
+}
+
+#[test]
+fn a_shorter_hold_does_not_cut_a_longer_one() {
+    let mut crowd_control = CrowdControl::default();
+    crowd_control.apply(Control::Freeze, 4.);
+    crowd_control.apply(Control::Stun, 2.);
+    crowd_control.tick(3.);
+    assert_eq!(crowd_control.control(), Some(Control::Freeze));
+}
+
+#[test]
+fn held_enemies_stay_where_they_are() {
+    let mut enemies = vec![enemy(1, 1.), enemy(2, 1.)];
+    let mut crowd_control = Components::<CrowdControl>::default();
+    crowd_control.entry(Entity(1)).apply(Control::Stun, 1.);
+    let arrived = advance(
+        &mut enemies,
+        0.5,
+        &Components::<Effects>::default(),
+        &crowd_control,
+    );
+    assert_eq!(arrived, [1]);
+    assert_eq!(enemies[0].distance.0, 1.);
+}
+
+#[test]
+fn held_enemies_do_not_regenerate() {
+    let mut enemies = vec![enemy(1, 50.), enemy(2, 50.)];
+    for enemy in &mut enemies {
+        enemy.regeneration = 2.;
+        enemy.hp.current = 5.;
+    }
+    let mut crowd_control = Components::<CrowdControl>::default();
+    crowd_control.entry(Entity(1)).apply(Control::Freeze, 1.);
+    advance(
+        &mut enemies,
+        0.5,
+        &Components::<Effects>::default(),
+        &crowd_control,
+    );
+    assert_eq!(enemies[0].hp.current, 5.);
+    assert_eq!(enemies[1].hp.current, 6.);
+}
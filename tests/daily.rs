@@ -0,0 +1,27 @@
+use your_excellency::daily;
+use your_excellency::GameState;
+
+#[test]
+fn same_day_plays_the_same_run() {
+    assert_eq!(daily::seed(20_000), daily::seed(20_000));
+    assert_ne!(daily::seed(20_000), daily::seed(20_001));
+    assert_eq!(daily::mutators(20_000), daily::mutators(20_000));
+    let mutators = daily::mutators(20_000);
+    assert_eq!(mutators.len(), 2);
+    assert_ne!(mutators[0], mutators[1]);
+    let mut first = GameState::daily(20_000);
+    let mut second = GameState::daily(20_000);
+    for _ in 0..600 {
+        first.step(0.02);
+        second.step(0.02);
+    }
+    assert_eq!(first.kills(), second.kills());
+    assert_eq!(first.enemies().len(), second.enemies().len());
+}
+
+#[test]
+fn days_format_as_dates() {
+    assert_eq!(daily::date(0), "1970-01-01");
+    assert_eq!(daily::date(11_016), "2000-02-29");
+    assert_eq!(daily::date(20_742), "2026-10-16");
+}
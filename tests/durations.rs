@@ -0,0 +1,28 @@
+use your_excellency::durations::DurationFormat;
+
+#[test]
+fn seconds_keep_a_decimal() {
+    assert_eq!(DurationFormat::Seconds.format(1.5), "1.5s");
+    assert_eq!(DurationFormat::Seconds.format(125.), "125.0s");
+}
+
+#[test]
+fn clock_shows_minutes_and_tenths_under_one() {
+    assert_eq!(DurationFormat::Clock.format(1.5), "0:01.5");
+    assert_eq!(DurationFormat::Clock.format(59.9), "0:59.9");
+    assert_eq!(DurationFormat::Clock.format(59.97), "1:00");
+    assert_eq!(DurationFormat::Clock.format(125.2), "2:05");
+    assert_eq!(DurationFormat::Clock.format(3600.), "60:00");
+}
+
+#[test]
+fn frames_count_at_sixty_per_second() {
+    assert_eq!(DurationFormat::Frames.format(1.5), "90f");
+    assert_eq!(DurationFormat::Frames.format(0.), "0f");
+}
+
+#[test]
+fn negative_durations_show_as_zero() {
+    assert_eq!(DurationFormat::Seconds.format(-0.2), "0.0s");
+    assert_eq!(DurationFormat::Clock.format(-3.), "0:00.0");
+}
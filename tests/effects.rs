@@ -0,0 +1,138 @@
+use your_excellency::effects::{DamageOverTime, Effect, Effects, Stacking, Stat};
+
+fn slow(amount: f32, duration: f32, stacking: Stacking) -> Effect {
+    Effect {
+        name: "Slow".to_owned(),
+        stat: Stat::Speed,
+        amount,
+        duration,
+        stacking,
+        damage_over_time: None,
+    }
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+#[test]
+fn refresh_replaces_and_restarts() {
+    let mut effects = Effects::default();
+    effects.apply(slow(-0.5, 2., Stacking::Refresh));
+    effects.tick(1.5);
+    effects.apply(slow(-0.2, 2., Stacking::Refresh));
+    assert!(close(effects.total(Stat::Speed), -0.2));
+    effects.tick(1.5);
+    assert!(!effects.is_empty());
+    effects.tick(1.);
+    assert!(effects.is_empty());
+}
+
+#[test]
+fn intensity_stacks_up_to_the_cap() {
+    let mut effects = Effects::default();
+    for _ in 0..5 {
+        effects.apply(slow(-0.1, 3., Stacking::Intensity { max_stacks: 3 }));
+    }
+    assert!(close(effects.total(Stat::Speed), -0.3));
+    assert!(close(effects.scale(Stat::Speed, 10.), 7.));
+}
+
+#[test]
+fn strongest_wins_ignores_weaker_applications() {
+    let mut effects = Effects::default();
+    effects.apply(slow(-0.4, 1., Stacking::StrongestWins));
+    effects.apply(slow(-0.1, 10., Stacking::StrongestWins));
+    assert!(close(effects.total(Stat::Speed), -0.4));
+    effects.tick(1.);
+    assert!(effects.is_empty());
+}
+
+#[test]
+fn effects_only_touch_their_stat() {
+    let mut effects = Effects::default();
+    effects.apply(Effect {
+        name: "Frenzy".to_owned(),
+        stat: Stat::AttackSpeed,
+        amount: 0.5,
+        duration: 5.,
+        stacking: Stacking::Refresh,
+        damage_over_time: None,
+    });
+    effects.apply(slow(-2., 5., Stacking::Refresh));
+    assert!(close(effects.scale(Stat::AttackSpeed, 1.), 1.5));
+    assert!(close(effects.scale(Stat::Damage, 4.), 4.));
+    // Slowed past a standstill never walks backwards
+    assert_eq!(effects.scale(Stat::Speed, 5.), 0.);
+}
+
+fn burn(duration: f32) -> Effect {
+    Effect {
+        name: "Burn".to_owned(),
+        stat: Stat::Speed,
+        amount: 0.,
+        duration,
+        stacking: Stacking::Refresh,
+        damage_over_time: Some(DamageOverTime {
+            damage: 2.,
+            interval: 0.5,
+        }),
+    }
+}
+
+#[test]
+fn damage_over_time_ticks_the_same_at_any_frame_rate() {
+    for frames_per_second in [10, 30, 60, 144] {
+        let mut effects = Effects::default();
+        effects.apply(burn(2.2));
+        let delta = 1. / frames_per_second as f32;
+        let dealt: f32 = (0..frames_per_second * 3)
+            .map(|_| effects.tick(delta))
+            .sum();
+        // Four ticks before it runs out, none after
+        assert!(close(dealt, 8.), "{dealt} at {frames_per_second} fps");
+        assert!(effects.is_empty());
+    }
+}
+
+#[test]
+fn refreshing_keeps_the_tick_timer_running() {
+    let mut effects = Effects::default();
+    effects.apply(burn(0.2));
+    let mut dealt = 0.;
+    for _ in 0..12 {
+        effects.apply(burn(0.2));
+        dealt += effects.tick(0.1);
+    }
+    assert!(close(dealt, 4.));
+}
+
+#[test]
+fn building_adds_a_stack_for_every_interval_it_stays() {
+    let mut effects = Effects::default();
+    let chill = || {
+        slow(
+            -0.1,
+            0.5,
+            Stacking::Building {
+                max_stacks: 3,
+                interval: 1.,
+            },
+        )
+    };
+    let mut totals = vec![];
+    for _ in 0..8 {
+        effects.apply(chill());
+        effects.tick(0.3);
+        totals.push(effects.total(Stat::Speed));
+    }
+    let expected = [-0.1, -0.1, -0.1, -0.2, -0.2, -0.2, -0.3, -0.3];
+    assert!(
+        totals.iter().zip(expected).all(|(&a, b)| close(a, b)),
+        "{totals:?}"
+    );
+    // Left alone it runs out and builds up from scratch
+    effects.tick(1.);
+    effects.apply(chill());
+    assert!(close(effects.total(Stat::Speed), -0.1));
+}
@@ -0,0 +1,67 @@
+use your_excellency::export::{csv_field, to_csv, StatSample, StatSamples, MAX_SAMPLES};
+use your_excellency::GameState;
+
+#[test]
+fn one_sample_per_simulated_second() {
+    let mut state = GameState::with_seed(2);
+    for _ in 0..100 {
+        state.step(0.1);
+    }
+    let csv = state.export_csv();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().starts_with("elapsed,"));
+    assert_eq!(lines.count(), 10);
+}
+
+#[test]
+fn log_lines_follow_the_sample_of_their_second() {
+    let sample = |elapsed| StatSample {
+        elapsed,
+        wave: 1,
+        hp: 100.,
+        gold: 0,
+        kills: 0,
+        enemies: 0,
+        enemy_hp: 0.,
+        damage_dealt: 0.,
+    };
+    let csv = to_csv(
+        &[sample(1.), sample(2.)],
+        [(1., "Took 3.0 damage, blocked 1.0")].into_iter(),
+    );
+    let rows: Vec<_> = csv.lines().skip(1).collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1], "1.0,,,,,,,,\"Took 3.0 damage, blocked 1.0\"");
+    assert!(rows[2].starts_with("2.0,"));
+}
+
+#[test]
+fn quotes_fields_that_would_break_the_row() {
+    assert_eq!(csv_field("plain"), "plain");
+    assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+}
+
+#[test]
+fn long_runs_are_sampled_sparser_but_whole() {
+    let mut samples = StatSamples::default();
+    for second in 1..=20_000 {
+        samples.record(StatSample {
+            elapsed: second as f32 + 0.01,
+            wave: 1,
+            hp: 100.,
+            gold: 0,
+            kills: 0,
+            enemies: 0,
+            enemy_hp: 0.,
+            damage_dealt: 0.,
+        });
+    }
+    let samples = samples.samples();
+    assert!(samples.len() < MAX_SAMPLES);
+    assert!(samples.len() > MAX_SAMPLES / 4);
+    assert!(samples[0].elapsed < 2.);
+    assert!(samples.last().unwrap().elapsed > 19_000.);
+    assert!(samples
+        .windows(2)
+        .all(|pair| pair[0].elapsed < pair[1].elapsed));
+}
@@ -0,0 +1,96 @@
+use proptest::prelude::*;
+use your_excellency::combat::{HitPoints, Overheal};
+
+#[derive(Debug, Clone)]
+enum Change {
+    Damage(f32),
+    Heal(f32),
+    Tick(f32),
+}
+
+fn change() -> impl Strategy<Value = Change> {
+    prop_oneof![
+        (0f32..200.).prop_map(Change::Damage),
+        (-50f32..200.).prop_map(Change::Heal),
+        (0f32..5.).prop_map(Change::Tick),
+    ]
+}
+
+fn overheal() -> impl Strategy<Value = Option<Overheal>> {
+    prop::option::of((0f32..1., 0f32..0.5).prop_map(|(cap, decay)| Overheal { cap, decay }))
+}
+
+fn apply(hp: &mut HitPoints, change: &Change) {
+    match *change {
+        Change::Damage(damage) => hp.take_damage(damage),
+        Change::Heal(amount) => hp.heal(amount),
+        Change::Tick(delta) => hp.tick(delta),
+    }
+}
+
+proptest! {
+    #[test]
+    fn stays_within_zero_and_the_limit(
+        maximum in 1f32..1000.,
+        overheal in overheal(),
+        changes in prop::collection::vec(change(), 0..50),
+    ) {
+        let mut hp = HitPoints::new_full(maximum);
+        hp.overheal = overheal;
+        for change in &changes {
+            apply(&mut hp, change);
+            prop_assert!(hp.current >= 0.);
+            prop_assert!(hp.current <= hp.limit());
+            prop_assert_eq!(hp.is_dead(), hp.current == 0.);
+        }
+    }
+
+    #[test]
+    fn never_above_the_maximum_without_overheal(
+        maximum in 1f32..1000.,
+        changes in prop::collection::vec(change(), 0..50),
+    ) {
+        let mut hp = HitPoints::new_full(maximum);
+        for change in &changes {
+            apply(&mut hp, change);
+            prop_assert!(hp.current <= hp.maximum);
+        }
+    }
+
+    #[test]
+    fn healing_never_lowers_hp(
+        maximum in 1f32..1000.,
+        overheal in overheal(),
+        damage in 0f32..1000.,
+        amount in -100f32..1000.,
+    ) {
+        let mut hp = HitPoints::new_full(maximum);
+        hp.overheal = overheal;
+        hp.take_damage(damage);
+        let before = hp.current;
+        hp.heal(amount);
+        prop_assert!(hp.current >= before);
+    }
+}
+
+#[test]
+fn overheal_decays_back_to_the_maximum() {
+    let mut hp = HitPoints::new_full(100.).with_overheal(Overheal {
+        cap: 0.5,
+        decay: 0.1,
+    });
+    hp.heal(80.);
+    assert_eq!(hp.current, 150.);
+    hp.tick(1.);
+    assert_eq!(hp.current, 140.);
+    hp.tick(10.);
+    assert_eq!(hp.current, 100.);
+}
+
+#[test]
+fn killing_blow_stops_at_zero() {
+    let mut hp = HitPoints::new_full(10.);
+    hp.take_damage(25.);
+    assert_eq!(hp.current, 0.);
+    assert!(hp.is_dead());
+}
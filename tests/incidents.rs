@@ -0,0 +1,28 @@
+use your_excellency::incidents::{Incident, Incidents, FRENZY_SPAWN_RATE};
+use your_excellency::rng::GameRng;
+
+fn sequence(seed: u64) -> Vec<Incident> {
+    let mut incidents = Incidents::default();
+    let mut rng = GameRng::new(seed);
+    (0..10_000)
+        .filter_map(|_| incidents.tick(0.1, &mut rng))
+        .collect()
+}
+
+#[test]
+fn same_seed_same_incidents() {
+    let incidents = sequence(11);
+    assert!(incidents.len() >= 10);
+    assert_eq!(incidents, sequence(11));
+}
+
+#[test]
+fn frenzy_speeds_up_spawns_for_a_while() {
+    let mut incidents = Incidents::default();
+    let mut rng = GameRng::new(0);
+    while incidents.tick(1., &mut rng) != Some(Incident::Frenzy) {}
+    assert_eq!(incidents.spawn_rate(), FRENZY_SPAWN_RATE);
+    incidents.frenzy = 0.5;
+    incidents.tick(1., &mut rng);
+    assert_eq!(incidents.spawn_rate(), 1.);
+}
@@ -0,0 +1,51 @@
+use your_excellency::combat::{AttackPhase, BasicAttack, CastMode, DamageType, TargetingStrategy};
+use your_excellency::loadouts::AttackSetup;
+use your_excellency::timer::Timer;
+use your_excellency::GameState;
+
+fn attack(damage: f32, cooldown: f32, targeting: TargetingStrategy) -> BasicAttack {
+    BasicAttack {
+        cooldown_timer: Timer::new(cooldown),
+        damage,
+        range: 50.,
+        max_targets: 1,
+        targeting,
+        overkill_carry: false,
+        wind_up: 0.,
+        phase: AttackPhase::Cooldown,
+        cast_mode: CastMode::Auto,
+        damage_type: DamageType::Normal,
+    }
+}
+
+#[test]
+fn applied_setup_keeps_the_running_cooldown() {
+    let setup = AttackSetup::capture(&attack(30., 2., TargetingStrategy::LowestHp));
+    let mut current = attack(5., 10., TargetingStrategy::Closest);
+    current.cooldown_timer.remaining = 6.;
+    setup.apply(&mut current);
+    assert_eq!(current.damage, 30.);
+    assert!(current.targeting == TargetingStrategy::LowestHp);
+    assert_eq!(current.cooldown_timer.total, 2.);
+    assert_eq!(current.cooldown_timer.remaining, 2.);
+
+    current.cooldown_timer.remaining = 1.;
+    setup.apply(&mut current);
+    assert_eq!(current.cooldown_timer.remaining, 1.);
+    assert!(AttackSetup::capture(&current) == setup);
+}
+
+#[test]
+fn swapped_loadout_shows_in_the_stat_sheet() {
+    let mut state = GameState::with_seed(1);
+    assert_eq!(state.stat_sheet().loadout, None);
+    state.save_loadout("starter");
+    for _ in 0..100 {
+        state.step(0.05);
+    }
+    assert!(!state.swap_loadout("missing"));
+    assert!(state.swap_loadout("starter"));
+    let sheet = state.stat_sheet();
+    assert_eq!(sheet.loadout.as_deref(), Some("starter"));
+    assert!(!sheet.loadout_modified);
+}
@@ -0,0 +1,23 @@
+use your_excellency::mutators::Mutator;
+use your_excellency::GameState;
+
+#[test]
+fn mutators_change_the_fresh_run() {
+    let plain = GameState::with_seed(7);
+    let mutated = GameState::with_mutators(7, vec![Mutator::HalfRange, Mutator::Fragile]);
+    let (plain, mutated) = (plain.excellency(), mutated.excellency());
+    assert_eq!(mutated.basic_attack.range, plain.basic_attack.range / 2.);
+    assert_eq!(mutated.hp.current, plain.hp.maximum / 2.);
+    assert_eq!(mutated.gold_bonus, 1.);
+}
+
+#[test]
+fn no_big_attack_never_fires() {
+    let mut state = GameState::with_mutators(7, vec![Mutator::NoBigAttack]);
+    let cooldown = state.excellency().big_attack.cooldown_timer.total;
+    for _ in 0..(cooldown / 0.02) as usize + 10 {
+        state.step(0.02);
+    }
+    let big_attack = &state.excellency().big_attack;
+    assert_eq!(big_attack.cooldown_timer.remaining, cooldown);
+}
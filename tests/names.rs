@@ -0,0 +1,26 @@
+use your_excellency::GameState;
+
+fn names_after(seed: u64, steps: usize) -> Vec<String> {
+    let mut state = GameState::with_seed(seed);
+    for _ in 0..steps {
+        state.step(0.02);
+    }
+    state
+        .enemies()
+        .iter()
+        .map(|enemy| enemy.name.clone())
+        .collect()
+}
+
+#[test]
+fn every_enemy_on_the_field_has_a_name() {
+    let names = names_after(3, 500);
+    assert!(!names.is_empty());
+    assert!(names.iter().all(|name| name.split(' ').count() >= 2));
+    assert!(names.windows(2).any(|pair| pair[0] != pair[1]));
+}
+
+#[test]
+fn names_replay_with_the_seed() {
+    assert_eq!(names_after(9, 500), names_after(9, 500));
+}
@@ -0,0 +1,23 @@
+use your_excellency::new_game_plus::{DAMAGE_SCALING, HP_SCALING};
+use your_excellency::GameState;
+
+fn first_spawn(mut state: GameState) -> (f32, f32) {
+    while state.enemies().is_empty() {
+        state.step(0.02);
+    }
+    let enemy = &state.enemies()[0];
+    (enemy.hp.maximum, enemy.damage)
+}
+
+#[test]
+fn next_cycle_spawns_tougher_enemies() {
+    let regular = GameState::with_seed(3);
+    let next = regular.next_cycle();
+    assert_eq!(next.new_game_plus(), 1);
+    assert_eq!(next.next_cycle().new_game_plus(), 2);
+
+    let (hp, damage) = first_spawn(GameState::with_seed(3));
+    let (scaled_hp, scaled_damage) = first_spawn(next);
+    assert!((scaled_hp - hp * HP_SCALING).abs() < 1e-3);
+    assert!((scaled_damage - damage * DAMAGE_SCALING).abs() < 1e-3);
+}
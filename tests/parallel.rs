@@ -0,0 +1,70 @@
+//! Only built with `--features parallel`, which `advance` needs to split crowded fields.
+#![cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+
+use your_excellency::crowd_control::CrowdControl;
+use your_excellency::effects::Effects;
+use your_excellency::enemy::{advance, Enemy};
+use your_excellency::rng::GameRng;
+use your_excellency::world::Components;
+use your_excellency::GameState;
+
+/// Well past the size `advance` goes parallel at.
+const CROWD: usize = 5000;
+/// Small enough for `advance` to stay on one thread.
+const SERIAL_CHUNK: usize = 100;
+
+/// Hurt, regenerating enemies at random distances, some close enough to arrive.
+fn crowd(seed: u64) -> Vec<Enemy> {
+    let mut rng = GameRng::new(seed);
+    (0..CROWD)
+        .map(|_| {
+            let mut enemy = Enemy {
+                speed: 0.5 + 2. * rng.next_f32(),
+                regeneration: rng.next_f32(),
+                ..Enemy::new(100., 30. * rng.next_f32())
+            };
+            enemy.hp.current = 100. * rng.next_f32();
+            enemy
+        })
+        .collect()
+}
+
+#[test]
+fn crowded_fields_advance_like_serial_ones() {
+    let debuffs = Components::<Effects>::default();
+    let crowd_control = Components::<CrowdControl>::default();
+    let mut parallel = crowd(3);
+    let mut serial = parallel.clone();
+    for _ in 0..50 {
+        let arrived = advance(&mut parallel, 0.1, &debuffs, &crowd_control);
+        let arrived_serially: Vec<usize> = serial
+            .chunks_mut(SERIAL_CHUNK)
+            .enumerate()
+            .flat_map(|(chunk, enemies)| {
+                advance(enemies, 0.1, &debuffs, &crowd_control)
+                    .into_iter()
+                    .map(move |index| chunk * SERIAL_CHUNK + index)
+            })
+            .collect();
+        assert_eq!(arrived, arrived_serially);
+        assert!(parallel == serial);
+    }
+}
+
+#[test]
+fn same_seed_plays_out_the_same_on_a_crowded_field() {
+    let mut first = GameState::with_seed(7);
+    let mut second = GameState::with_seed(7);
+    for state in [&mut first, &mut second] {
+        state.add_enemies(crowd(5));
+        for _ in 0..300 {
+            state.step(0.05);
+        }
+    }
+    assert_eq!(first.kills(), second.kills());
+    assert_eq!(
+        first.excellency().hp.current,
+        second.excellency().hp.current
+    );
+    assert!(first.enemies() == second.enemies());
+}
@@ -0,0 +1,42 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use your_excellency::enemy::Enemy;
+use your_excellency::events::GameEvent;
+use your_excellency::{GamePlugin, GameState};
+
+/// Counts what it's told into cells the test keeps hold of.
+#[derive(Default)]
+struct Counter {
+    ticks: Rc<Cell<u32>>,
+    spawns: Rc<Cell<u32>>,
+}
+
+impl GamePlugin for Counter {
+    fn name(&self) -> &'static str {
+        "Counter"
+    }
+
+    fn on_tick(&mut self, _delta: f32, _elapsed: f32, _enemies: &[Enemy]) {
+        self.ticks.set(self.ticks.get() + 1);
+    }
+
+    fn on_event(&mut self, event: &GameEvent) {
+        if let GameEvent::EnemySpawned(_) = event {
+            self.spawns.set(self.spawns.get() + 1);
+        }
+    }
+}
+
+#[test]
+fn registered_plugins_see_every_step_and_event() {
+    let counter = Counter::default();
+    let (ticks, spawns) = (counter.ticks.clone(), counter.spawns.clone());
+    let mut state = GameState::with_seed(3);
+    state.register_plugin(Box::new(counter));
+    for _ in 0..500 {
+        state.step(0.02);
+    }
+    assert_eq!(ticks.get(), 500);
+    assert!(spawns.get() > 0);
+}
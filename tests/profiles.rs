@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use your_excellency::profiles::{self, Profiles, KEYS, PROFILES_KEY};
+use your_excellency::saves::{self, SlotSummary, SLOT_COUNT};
+use your_excellency::GameState;
+
+/// Shared by every profile instead of kept apart.
+const SHARED_KEYS: [&str; 2] = [PROFILES_KEY, "telemetry"];
+
+#[derive(Default)]
+struct Memory(BTreeMap<String, String>);
+
+impl eframe::Storage for Memory {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.0.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_owned(), value);
+    }
+
+    fn flush(&mut self) {}
+}
+
+impl Memory {
+    fn is_set(&self, key: &str) -> bool {
+        self.0.get(key).is_some_and(|value| !value.is_empty())
+    }
+}
+
+/// Stores something under every key of `profile`, save slots included.
+fn fill(storage: &mut Memory, profile: u32) {
+    for key in KEYS {
+        eframe::Storage::set_string(storage, &profiles::key_of(profile, key), "{}".to_owned());
+    }
+    let summary = SlotSummary {
+        name: "Run".to_owned(),
+        saved_at: 0,
+        playtime: 0.,
+        gold: 0,
+        wave: 1,
+    };
+    for slot in 0..SLOT_COUNT {
+        saves::save(storage, profile, slot, &summary, &GameState::with_seed(1));
+    }
+}
+
+#[test]
+fn the_first_profile_keeps_the_bare_keys() {
+    assert_eq!(profiles::key_of(0, "records"), "records");
+    assert_eq!(profiles::key_of(3, "records"), "profile_3_records");
+    assert_ne!(
+        profiles::key_of(1, "records"),
+        profiles::key_of(11, "records")
+    );
+}
+
+#[test]
+fn removing_a_profile_clears_only_its_keys() {
+    let mut storage = Memory::default();
+    let mut list = Profiles::default();
+    let removed = list.add("Guest".to_owned());
+    let kept = list.add("Other".to_owned());
+    for profile in [0, removed, kept] {
+        fill(&mut storage, profile);
+    }
+    list.remove(&mut storage, removed);
+    assert_eq!(list.name(removed), "");
+    for key in KEYS {
+        assert!(!storage.is_set(&profiles::key_of(removed, key)));
+        assert!(storage.is_set(&profiles::key_of(kept, key)));
+        assert!(storage.is_set(key));
+    }
+    for slot in 0..SLOT_COUNT {
+        assert!(saves::load(&storage, removed, slot).is_none());
+        assert!(saves::summary(&storage, removed, slot).is_none());
+        assert!(saves::load(&storage, kept, slot).is_some_and(|save| save.is_ok()));
+    }
+    let leftover = storage.0.iter().filter(|(key, value)| {
+        key.starts_with(&format!("profile_{removed}_")) && !value.is_empty()
+    });
+    assert_eq!(leftover.count(), 0);
+}
+
+#[test]
+fn the_current_profile_cant_be_removed() {
+    let mut storage = Memory::default();
+    let mut list = Profiles::default();
+    fill(&mut storage, 0);
+    list.remove(&mut storage, 0);
+    assert_eq!(list.name(0), "Default");
+    assert!(KEYS.into_iter().all(|key| storage.is_set(key)));
+}
+
+#[test]
+fn everything_the_app_stores_is_either_shared_or_a_profile_key() {
+    let mut storage = Memory::default();
+    eframe::App::save(&mut GameState::with_seed(1), &mut storage);
+    for key in storage.0.keys() {
+        assert!(
+            KEYS.contains(&key.as_str()) || SHARED_KEYS.contains(&key.as_str()),
+            "{key} would outlive its profile"
+        );
+    }
+}
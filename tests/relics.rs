@@ -0,0 +1,83 @@
+use your_excellency::combat::AttackKind;
+use your_excellency::enemy::Enemy;
+use your_excellency::events::GameEvent;
+use your_excellency::relics::{Reaction, Relic, RelicEffect, Relics, SLOTS};
+use your_excellency::rng::GameRng;
+use your_excellency::world::Entity;
+
+fn enemy(maximum_hp: f32, distance: f32) -> Enemy {
+    Enemy {
+        id: Entity(1),
+        speed: 2.,
+        ..Enemy::new(maximum_hp, distance)
+    }
+}
+
+fn relic(effect: RelicEffect, power: f32) -> Relic {
+    Relic { effect, power }
+}
+
+#[test]
+fn rolled_powers_stay_in_range() {
+    let mut rng = GameRng::new(11);
+    for _ in 0..200 {
+        let relic = Relic::roll(&mut rng);
+        let (low, high) = match relic.effect {
+            RelicEffect::Echo(AttackKind::Basic) => (0.3, 0.6),
+            RelicEffect::Echo(AttackKind::Big) => (0.5, 1.),
+            RelicEffect::Volatile => (0.05, 0.15),
+            RelicEffect::Vampiric => (1., 3.),
+            RelicEffect::Greedy => (1., 5.),
+        };
+        assert!((low..=high).contains(&relic.power), "{relic:?}");
+    }
+}
+
+#[test]
+fn relics_answer_only_their_events() {
+    let echo = relic(RelicEffect::Echo(AttackKind::Big), 0.8);
+    let big_fired = GameEvent::AttackFired {
+        kind: AttackKind::Big,
+        hits: vec![],
+    };
+    let basic_fired = GameEvent::AttackFired {
+        kind: AttackKind::Basic,
+        hits: vec![],
+    };
+    assert_eq!(
+        echo.react(&big_fired),
+        Some(Reaction::Echo {
+            kind: AttackKind::Big,
+            damage: 0.8
+        })
+    );
+    assert_eq!(echo.react(&basic_fired), None);
+    let killed = GameEvent::EnemyKilled(enemy(40., 12.));
+    assert_eq!(echo.react(&killed), None);
+    assert_eq!(
+        relic(RelicEffect::Volatile, 0.1).react(&killed),
+        Some(Reaction::Explode {
+            distance: 12.,
+            damage: 4.
+        })
+    );
+    assert_eq!(
+        relic(RelicEffect::Greedy, 3.).react(&killed),
+        Some(Reaction::Gold(3))
+    );
+}
+
+#[test]
+fn only_so_many_relics_are_equipped() {
+    let mut relics = Relics::default();
+    for _ in 0..SLOTS + 1 {
+        relics.add(relic(RelicEffect::Vampiric, 2.));
+    }
+    assert_eq!(relics.equipped().count(), SLOTS);
+    assert!(!relics.is_equipped(SLOTS));
+    assert!(!relics.toggle(SLOTS));
+    assert!(relics.toggle(0));
+    assert!(relics.toggle(SLOTS));
+    assert!(relics.is_equipped(SLOTS));
+    assert!(!relics.is_equipped(0));
+}
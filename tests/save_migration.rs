@@ -0,0 +1,84 @@
+use your_excellency::modes::GameMode;
+use your_excellency::{GameState, SaveError, SAVE_VERSION};
+
+const SAVE_V0: &str = include_str!("fixtures/save_v0.ron");
+const SAVE_V1: &str = include_str!("fixtures/save_v1.ron");
+
+fn resave(state: &GameState) -> String {
+    ron::to_string(state).expect("GameState serializes")
+}
+
+#[test]
+fn v1_save_loads() {
+    let state = GameState::from_save(SAVE_V1).expect("v1 save loads");
+    let resaved = resave(&state);
+
+    assert!(resaved.starts_with(&format!("(save_version:{SAVE_VERSION},")));
+    assert!(resaved.contains("gold:4,experience:4"));
+
+    let reloaded = GameState::from_save(&resaved).expect("resaved state loads");
+    assert_eq!(resave(&reloaded), resaved);
+}
+
+#[test]
+fn v0_save_is_upgraded() {
+    let state = GameState::from_save(SAVE_V0).expect("v0 save loads");
+    let upgraded = resave(&state);
+
+    assert!(upgraded.starts_with(&format!("(save_version:{SAVE_VERSION},")));
+    // Carried over from the old layout rather than reset to defaults
+    assert!(upgraded.contains("hp:(maximum:100.0,current:73.0)"));
+    assert!(upgraded.contains("distance:(42.5)"));
+    assert!(upgraded.contains("distance:(97.0)"));
+    assert!(upgraded.contains("interval:1.5"));
+    assert!(upgraded.contains("remaining:7.5,one_shot:false,paused:true"));
+
+    let reloaded = GameState::from_save(&upgraded).expect("upgraded save loads");
+    assert_eq!(resave(&reloaded), upgraded);
+}
+
+#[test]
+fn saves_from_before_modes_are_sandbox() {
+    let state = GameState::from_save(SAVE_V1).expect("v1 save loads");
+    assert_eq!(state.mode(), GameMode::Sandbox);
+    let reloaded = GameState::from_save(&resave(&state)).expect("resaved state loads");
+    assert_eq!(reloaded.mode(), GameMode::Sandbox);
+}
+
+#[test]
+fn newer_save_is_rejected() {
+    let save = SAVE_V1.replacen("save_version:1", "save_version:99", 1);
+    assert!(matches!(
+        GameState::from_save(&save),
+        Err(SaveError::UnsupportedVersion(99))
+    ));
+}
+
+#[test]
+fn garbage_save_is_an_error() {
+    assert!(matches!(
+        GameState::from_save("(excellency: 42"),
+        Err(SaveError::Parse(_))
+    ));
+}
+
+#[test]
+fn json_export_round_trips() {
+    let state = GameState::from_save(SAVE_V1).expect("v1 save loads");
+    let json = state.to_json().expect("GameState serializes to JSON");
+    let imported = GameState::from_json(&json).expect("exported JSON imports");
+    assert_eq!(resave(&imported), resave(&state));
+}
+
+#[test]
+fn invalid_import_is_rejected() {
+    let state = GameState::from_save(SAVE_V1).expect("v1 save loads");
+    let json = state
+        .to_json()
+        .expect("GameState serializes to JSON")
+        .replacen("\"interval\": 1.0", "\"interval\": 0.0", 1);
+    assert!(matches!(
+        GameState::from_json(&json),
+        Err(SaveError::Invalid(_))
+    ));
+}
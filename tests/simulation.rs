@@ -0,0 +1,80 @@
+use your_excellency::combat::AttackKind;
+use your_excellency::enemy::Enemy;
+use your_excellency::events::DamageSource;
+use your_excellency::GameState;
+
+#[test]
+fn same_seed_plays_out_the_same() {
+    let mut first = GameState::with_seed(7);
+    let mut second = GameState::with_seed(7);
+    for _ in 0..3000 {
+        first.step(0.02);
+        second.step(0.02);
+    }
+    assert_eq!(first.kills(), second.kills());
+    assert_eq!(first.enemies().len(), second.enemies().len());
+    assert_eq!(
+        first.excellency().hp.current,
+        second.excellency().hp.current
+    );
+}
+
+#[test]
+fn enemies_spawn_and_die() {
+    let mut state = GameState::with_seed(1);
+    for _ in 0..1500 {
+        state.step(0.02);
+    }
+    assert!((state.elapsed() - 30.).abs() < 0.01);
+    assert!(state.kills() > 0);
+    assert!(!state.enemies().is_empty());
+    assert!(state
+        .enemies()
+        .windows(2)
+        .all(|pair| pair[0].distance <= pair[1].distance));
+}
+
+#[test]
+fn coarse_steps_keep_up_with_fine_ones() {
+    let mut fine = GameState::with_seed(4);
+    let mut coarse = GameState::with_seed(4);
+    for _ in 0..3000 {
+        fine.step(0.02);
+    }
+    for _ in 0..240 {
+        coarse.step(0.25);
+    }
+    assert!((fine.elapsed() - coarse.elapsed()).abs() < 0.01);
+    assert_eq!(fine.waves().number, coarse.waves().number);
+    let (fine_kills, coarse_kills) = (fine.kills() as f32, coarse.kills() as f32);
+    assert!((fine_kills - coarse_kills).abs() <= fine_kills * 0.25);
+}
+
+#[test]
+fn damage_by_source_adds_up() {
+    let mut state = GameState::with_seed(5);
+    for _ in 0..600 {
+        state.step(0.1);
+    }
+    let damage = state.damage_by_source();
+    assert!(damage.get(DamageSource::Attack(AttackKind::Basic)) > 0.);
+    let summed: f32 = DamageSource::ALL
+        .into_iter()
+        .map(|source| damage.get(source))
+        .sum();
+    assert!((summed - damage.total()).abs() < 1e-3);
+}
+
+#[test]
+fn the_field_stays_behind_the_defeat() {
+    let mut state = GameState::with_seed(2);
+    let killer = Enemy {
+        damage: 1_000_000.,
+        ..Enemy::new(10., 0.01)
+    };
+    state.add_enemies([killer, Enemy::new(10., 50.), Enemy::new(10., 60.)]);
+    state.step(0.1);
+    assert!(state.is_defeated());
+    assert!(state.enemies().len() >= 2);
+    assert_eq!(state.snapshot().enemies, state.enemies().len());
+}
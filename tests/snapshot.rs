@@ -0,0 +1,35 @@
+use your_excellency::GameState;
+
+#[test]
+fn invariants_hold_over_minutes() {
+    let mut state = GameState::with_seed(11);
+    let mut previous = state.snapshot();
+    for _ in 0..3000 {
+        state.step(0.1);
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.enemies, state.enemies().len());
+        assert!(snapshot.hp <= snapshot.max_hp);
+        assert!(snapshot.enemy_hp >= 0.);
+        assert!(snapshot.elapsed >= previous.elapsed);
+        assert!(snapshot.kills >= previous.kills);
+        assert!(snapshot.experience >= previous.experience);
+        assert!(snapshot.wave >= previous.wave);
+        if previous.defeated {
+            assert_eq!(snapshot, previous);
+        }
+        previous = snapshot;
+    }
+}
+
+#[test]
+fn same_seed_gives_equal_snapshots() {
+    let mut first = GameState::with_seed(4);
+    let mut second = GameState::with_seed(4);
+    for _ in 0..120 {
+        for _ in 0..10 {
+            first.step(0.1);
+            second.step(0.1);
+        }
+        assert_eq!(first.snapshot(), second.snapshot());
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::VecDeque;
+
+use your_excellency::content::Content;
+use your_excellency::enemy::{remove_escaped, Distance, EliteModifier, Enemy, EnemyKind};
+use your_excellency::rng::GameRng;
+use your_excellency::spawner::{
+    EnemySpawner, Overflow, ScalingCurve, SpawnPattern, SpawnerPreset, SpawnerScaling,
+};
+use your_excellency::timer::Timer;
+use your_excellency::treasure;
+
+fn spawner(max_alive: usize, overflow: Overflow) -> EnemySpawner {
+    let flat = ScalingCurve::Linear { per_minute: 0. };
+    EnemySpawner {
+        timer: Timer::new(1.),
+        interval: 1.,
+        scaling: SpawnerScaling {
+            hp: flat,
+            speed: flat,
+            damage: flat,
+            spawn_rate: flat,
+        },
+        maximum_hp: 10.,
+        speed: 1.,
+        damage: 1.,
+        elite_chance: 0.,
+        healer_chance: 0.,
+        max_alive,
+        overflow,
+        queue: VecDeque::new(),
+        pattern: SpawnPattern::Steady,
+        streak: 0,
+        archetype: None,
+        variance: 0.,
+    }
+}
+
+#[test]
+fn spawns_queue_while_the_field_is_full() {
+    let mut spawner = spawner(2, Overflow::Queue);
+    let mut enemies = vec![Enemy::new(10., 1.)];
+    let joining = spawner.admit(
+        vec![Enemy::new(1., 9.), Enemy::new(2., 9.), Enemy::new(3., 9.)],
+        &mut enemies,
+        0,
+    );
+    assert_eq!(joining.len(), 1);
+    assert_eq!(joining[0].hp.maximum, 1.);
+    assert_eq!(spawner.queue.len(), 2);
+    enemies.extend(joining);
+
+    // The queue caps at another `max_alive`, the oldest spawns join first
+    let joining = spawner.admit(vec![Enemy::new(4., 9.)], &mut enemies, 0);
+    assert!(joining.is_empty());
+    assert_eq!(spawner.queue.len(), 2);
+    assert_eq!(enemies[1].hp.maximum, 5.);
+    enemies.clear();
+    let joining = spawner.admit(vec![], &mut enemies, 0);
+    let hps: Vec<f32> = joining.iter().map(|enemy| enemy.hp.maximum).collect();
+    assert_eq!(hps, [2., 3.]);
+}
+
+#[test]
+fn merged_spawns_strengthen_the_farthest_enemy() {
+    let mut spawner = spawner(2, Overflow::Merge);
+    let mut enemies = vec![Enemy::new(10., 1.), Enemy::new(10., 5.)];
+    let joining = spawner.admit(
+        vec![Enemy::new(3., 9.), Enemy::new(4., 9.)],
+        &mut enemies,
+        0,
+    );
+    assert!(joining.is_empty());
+    assert!(spawner.queue.is_empty());
+    assert_eq!(enemies[0].hp.maximum, 10.);
+    assert_eq!(enemies[1].hp.maximum, 17.);
+    assert_eq!(enemies[1].hp.current, 17.);
+}
+
+#[test]
+fn bosses_and_chests_are_never_merged() {
+    let mut spawner = spawner(2, Overflow::Merge);
+    let mut chest = Enemy::new(10., 9.);
+    chest.kind = EnemyKind::Chest;
+    let mut enemies = vec![Enemy::new(10., 1.), chest];
+    let mut boss = Enemy::new(50., 9.);
+    boss.is_boss = true;
+    spawner.admit(vec![boss, Enemy::new(3., 9.)], &mut enemies, 0);
+    assert_eq!(enemies[0].hp.maximum, 13.);
+    assert_eq!(enemies[1].hp.maximum, 10.);
+    assert_eq!(spawner.queue.len(), 1);
+    assert!(spawner.queue[0].is_boss);
+}
+
+#[test]
+fn bursts_spawn_together() {
+    let mut spawner = spawner(100, Overflow::Queue);
+    spawner.pattern = SpawnPattern::Burst {
+        size: 4,
+        period: 3.,
+    };
+    let content = Content::default();
+    let mut rng = GameRng::new(1);
+    let spawned = spawner.tick(1., 0., 1., 1, &content, &mut rng);
+    assert_eq!(spawned.len(), 4);
+    assert!(spawner.tick(2.5, 0., 1., 1, &content, &mut rng).is_empty());
+    assert_eq!(spawner.tick(0.5, 0., 1., 1, &content, &mut rng).len(), 4);
+    assert_eq!(spawner.spawns_per_minute(0.), 80.);
+}
+
+#[test]
+fn accelerating_stream_starts_over_at_the_floor() {
+    let mut spawner = spawner(100, Overflow::Queue);
+    spawner.pattern = SpawnPattern::Accelerating {
+        acceleration: 0.5,
+        floor: 0.2,
+    };
+    let mut rng = GameRng::new(1);
+    let gaps: Vec<f32> = (0..4)
+        .map(|_| {
+            spawner
+                .pattern
+                .next_gap(spawner.interval, &mut spawner.streak, &mut rng)
+        })
+        .collect();
+    assert_eq!(gaps, [0.5, 0.25, 1., 0.5]);
+}
+
+#[test]
+fn fixed_archetype_ignores_the_composition() {
+    let mut spawner = spawner(100, Overflow::Queue);
+    let content = Content::default();
+    let name = content
+        .archetypes
+        .keys()
+        .last()
+        .expect("Built-in archetypes exist");
+    spawner.archetype = Some(name.clone());
+    let mut rng = GameRng::new(1);
+    let spawned = spawner.tick(10., 0., 1., 1, &content, &mut rng);
+    assert_eq!(spawned.len(), 10);
+    assert!(spawned.iter().all(|enemy| &enemy.archetype == name));
+}
+
+#[test]
+fn presets_keep_the_configuration_but_not_the_progress() {
+    let mut main = spawner(100, Overflow::Merge);
+    main.queue.push_back(Enemy::new(1., 9.));
+    main.timer.remaining = 0.3;
+    let preset = SpawnerPreset::capture(&main, &[main.clone()]);
+    for spawner in std::iter::once(&preset.main).chain(&preset.extras) {
+        assert!(spawner.queue.is_empty());
+        assert_eq!(spawner.timer.remaining, 1.);
+        assert!(spawner.overflow == Overflow::Merge);
+    }
+}
+
+#[test]
+fn variance_spreads_the_stats_within_bounds() {
+    let content = Content::default();
+    let (name, archetype) = content
+        .archetypes
+        .iter()
+        .find(|(_, archetype)| archetype.modifiers.is_empty())
+        .expect("A plain archetype exists");
+    let mut spawner = spawner(100, Overflow::Queue);
+    let alike = spawner.spawn(0., name, archetype, &mut GameRng::new(1));
+    spawner.variance = 0.2;
+    let roll = |seed| {
+        let mut rng = GameRng::new(seed);
+        (0..50)
+            .map(|_| spawner.spawn(0., name, archetype, &mut rng))
+            .collect::<Vec<_>>()
+    };
+    let spawned = roll(7);
+    let within = |value: f32, base: f32| (value - base).abs() <= base * 0.2 + 1e-4;
+    assert!(spawned
+        .iter()
+        .all(|enemy| within(enemy.hp.maximum, alike.hp.maximum)
+            && within(enemy.speed, alike.speed)
+            && within(enemy.damage, alike.damage)
+            && enemy.hp.current == enemy.hp.maximum));
+    assert!(spawned
+        .iter()
+        .any(|enemy| enemy.hp.maximum != spawned[0].hp.maximum));
+    // Seeded, so a replay rolls the same
+    let replayed = roll(7);
+    assert!(spawned
+        .iter()
+        .zip(&replayed)
+        .all(|(a, b)| a.hp.maximum == b.hp.maximum && a.speed == b.speed));
+}
+
+#[test]
+fn modifiers_scale_with_the_rolled_hp() {
+    let content = Content::default();
+    let warlord = &content.archetypes["warlord"];
+    assert!(warlord.modifiers.contains(&EliteModifier::Shielded));
+    let mut spawner = spawner(100, Overflow::Queue);
+    spawner.variance = 0.3;
+    let mut rng = GameRng::new(5);
+    for _ in 0..20 {
+        let enemy = spawner.spawn(0., "warlord", warlord, &mut rng);
+        assert!((enemy.shield - 0.5 * enemy.hp.maximum).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn chests_flee_and_escape_unless_broken() {
+    let spawner = spawner(10, Overflow::Queue);
+    let mut chest = spawner.spawn_chest(0.);
+    assert!(chest.kind == EnemyKind::Chest);
+    assert_eq!(chest.damage, 0.);
+    assert_eq!(chest.hp.maximum, 10. * treasure::HP_FACTOR);
+    assert_eq!(chest.gold_bounty(), treasure::BOUNTY_FACTOR);
+    chest.tick(2., None, false);
+    assert_eq!(chest.distance.0, treasure::START + 2.);
+    assert!(!chest.has_escaped());
+    let mut enemies = vec![Enemy::new(10., 50.), chest.clone()];
+    enemies[1].tick(treasure::LIFETIME, None, false);
+    let escaped = remove_escaped(&mut enemies);
+    assert_eq!(escaped.len(), 1);
+    assert_eq!(enemies.len(), 1);
+    // Reaching the spawn is as good as running out of time
+    chest.distance = Distance::start();
+    assert!(chest.has_escaped());
+}
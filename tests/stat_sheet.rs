@@ -0,0 +1,39 @@
+use your_excellency::enemy::Enemy;
+use your_excellency::GameState;
+
+const STEP: f32 = 0.02;
+
+/// A fresh run where only the basic attack deals damage.
+fn basic_attack_only() -> GameState {
+    let mut save: serde_json::Value =
+        serde_json::from_str(&GameState::with_seed(1).to_json().unwrap()).unwrap();
+    save["excellency"]["big_attack"]["damage"] = 0.into();
+    GameState::from_json(&save.to_string()).unwrap()
+}
+
+#[test]
+fn time_to_kill_matches_how_long_killing_takes() {
+    let mut state = basic_attack_only();
+    let sheet = state.stat_sheet();
+    let estimate = sheet.attacks[0].time_to_kill;
+    let cycle = state.excellency().basic_attack.cycle();
+    // Standing right next to the Excellency, so every shot reaches it
+    state.add_enemies([Enemy {
+        speed: 0.,
+        ..Enemy::new(sheet.enemy_hp, 1.)
+    }]);
+    let target = state.enemies()[0].id;
+    let mut elapsed = 0.;
+    while state.enemies().iter().any(|enemy| enemy.id == target) {
+        assert!(elapsed < estimate + cycle, "Still alive after {elapsed}s");
+        state.step(STEP);
+        elapsed += STEP;
+    }
+    assert!(elapsed > estimate - cycle, "Dead after only {elapsed}s");
+}
+
+#[test]
+fn no_intake_when_nothing_gets_through() {
+    let sheet = GameState::with_seed(1).stat_sheet();
+    assert_eq!(sheet.intake_per_minute > 0., sheet.enemy_gets_through);
+}
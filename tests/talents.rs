@@ -0,0 +1,25 @@
+use your_excellency::excellency::level_for;
+use your_excellency::rng::GameRng;
+use your_excellency::talents::{Talent, CHOICES};
+
+#[test]
+fn each_level_takes_more_experience() {
+    assert_eq!(level_for(0), 1);
+    assert_eq!(level_for(9), 1);
+    assert_eq!(level_for(10), 2);
+    assert_eq!(level_for(29), 2);
+    assert_eq!(level_for(30), 3);
+    assert_eq!(level_for(100), 5);
+}
+
+#[test]
+fn choices_are_distinct() {
+    let mut rng = GameRng::new(3);
+    for _ in 0..50 {
+        let choice = Talent::roll_choice(&mut rng);
+        assert_eq!(choice.len(), CHOICES);
+        for (index, talent) in choice.iter().enumerate() {
+            assert!(!choice[index + 1..].contains(talent));
+        }
+    }
+}
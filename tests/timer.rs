@@ -0,0 +1,74 @@
+use your_excellency::timer::Timer;
+
+#[test]
+fn counts_every_completion_within_a_tick() {
+    let mut timer = Timer::new(1.);
+    assert_eq!(timer.tick(0.5), 0);
+    assert_eq!(timer.tick(2.75), 3);
+    assert!((timer.remaining - 0.75).abs() < 1e-5);
+}
+
+#[test]
+fn one_shot_pauses_after_finishing() {
+    let mut timer = Timer::new(1.);
+    timer.one_shot = true;
+    assert_eq!(timer.tick(5.), 1);
+    assert!(timer.is_paused());
+    assert_eq!(timer.tick(5.), 0);
+}
+
+#[test]
+fn paused_timer_ignores_ticks() {
+    let mut timer = Timer::new(2.);
+    timer.pause();
+    assert_eq!(timer.tick(10.), 0);
+    assert_eq!(timer.remaining, 2.);
+    timer.unpause();
+    assert_eq!(timer.tick(2.), 1);
+}
+
+#[test]
+fn finish_now_fires_on_the_next_tick() {
+    let mut timer = Timer::new(10.);
+    timer.finish_now();
+    assert_eq!(timer.tick(0.), 1);
+    assert_eq!(timer.remaining, 10.);
+}
+
+#[test]
+fn zero_length_timer_finishes_once_per_tick() {
+    let mut timer = Timer::new(0.);
+    assert_eq!(timer.tick(1.), 1);
+    assert_eq!(timer.remaining, 0.);
+}
+
+#[test]
+fn reset_starts_the_countdown_over() {
+    let mut timer = Timer::new(4.);
+    assert_eq!(timer.tick(3.), 0);
+    timer.reset();
+    assert_eq!(timer.remaining, 4.);
+    assert_eq!(timer.tick(3.), 0);
+    assert_eq!(timer.tick(1.), 1);
+}
+
+#[test]
+fn reset_keeps_a_paused_timer_paused() {
+    let mut timer = Timer::new(4.);
+    timer.tick(3.);
+    timer.pause();
+    timer.reset();
+    assert!(timer.is_paused());
+    assert_eq!(timer.tick(10.), 0);
+    assert_eq!(timer.remaining, 4.);
+}
+
+#[test]
+fn finish_now_waits_for_a_paused_timer_to_unpause() {
+    let mut timer = Timer::new(10.);
+    timer.pause();
+    timer.finish_now();
+    assert_eq!(timer.tick(1.), 0);
+    timer.unpause();
+    assert_eq!(timer.tick(0.), 1);
+}
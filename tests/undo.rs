@@ -0,0 +1,58 @@
+use your_excellency::mutators::Mutator;
+use your_excellency::undo::{Configuration, EditHistory};
+use your_excellency::GameState;
+
+/// Three configurations differing from each other.
+fn configurations() -> [Configuration; 3] {
+    [
+        vec![],
+        vec![Mutator::ToughEnemies],
+        vec![Mutator::HalfRange],
+    ]
+    .map(|mutators| Configuration::capture(&GameState::with_mutators(0, mutators)))
+}
+
+#[test]
+fn undo_and_redo_walk_the_edits() {
+    let [a, b, _] = configurations();
+    let mut history = EditHistory::default();
+    assert!(!history.can_undo());
+    history.record(a.clone(), &b, false);
+    assert!(history.can_undo() && !history.can_redo());
+    assert!(history.undo() == Some(a));
+    assert!(!history.can_undo() && history.can_redo());
+    assert!(history.redo() == Some(b));
+    assert!(history.can_undo() && !history.can_redo());
+    assert!(history.redo().is_none());
+}
+
+#[test]
+fn a_new_edit_drops_the_undone_ones() {
+    let [a, b, c] = configurations();
+    let mut history = EditHistory::default();
+    history.record(a.clone(), &b, false);
+    history.undo();
+    history.record(a.clone(), &c, false);
+    assert!(!history.can_redo());
+    assert!(history.undo() == Some(a));
+    assert!(history.redo() == Some(c));
+}
+
+#[test]
+fn a_drag_is_a_single_edit() {
+    let [a, b, c] = configurations();
+    let mut history = EditHistory::default();
+    history.record(a.clone(), &b, true);
+    assert!(!history.can_undo());
+    history.record(b, &c, false);
+    assert!(history.undo() == Some(a));
+    assert!(!history.can_undo());
+}
+
+#[test]
+fn unchanged_configuration_records_nothing() {
+    let [a, _, _] = configurations();
+    let mut history = EditHistory::default();
+    history.record(a.clone(), &a, false);
+    assert!(!history.can_undo());
+}
@@ -0,0 +1,53 @@
+use your_excellency::combat::{AttackPhase, BasicAttack, CastMode, DamageType, TargetingStrategy};
+use your_excellency::modes::GameMode;
+use your_excellency::timer::Timer;
+use your_excellency::upgrades::{Upgrade, COST_GROWTH};
+use your_excellency::GameState;
+
+fn attack() -> BasicAttack {
+    BasicAttack {
+        cooldown_timer: Timer::new(2.),
+        damage: 10.,
+        range: 48.,
+        max_targets: 9,
+        targeting: TargetingStrategy::Closest,
+        overkill_carry: false,
+        wind_up: 0.,
+        phase: AttackPhase::Cooldown,
+        cast_mode: CastMode::Auto,
+        damage_type: DamageType::Normal,
+    }
+}
+
+#[test]
+fn every_purchase_costs_more() {
+    for upgrade in Upgrade::ALL {
+        let first = upgrade.cost(0);
+        assert!(first > 0);
+        assert_eq!(
+            upgrade.cost(2),
+            (first as f32 * COST_GROWTH.powi(2)).round() as u32
+        );
+    }
+}
+
+#[test]
+fn upgrades_stop_at_the_slider_caps() {
+    let mut attack = attack();
+    assert!(!Upgrade::Range.is_maxed(&attack));
+    Upgrade::Range.apply(&mut attack);
+    assert_eq!(attack.range, 50.);
+    assert!(Upgrade::Range.is_maxed(&attack));
+    Upgrade::MaxTargets.apply(&mut attack);
+    assert!(Upgrade::MaxTargets.is_maxed(&attack));
+    Upgrade::Cooldown.apply(&mut attack);
+    assert!((attack.cooldown_timer.total - 1.8).abs() < 1e-6);
+}
+
+#[test]
+fn mode_carries_into_the_next_cycle() {
+    let run = GameState::with_mode(4, vec![], GameMode::Sandbox);
+    assert_eq!(run.mode(), GameMode::Sandbox);
+    assert_eq!(run.next_cycle().mode(), GameMode::Sandbox);
+    assert_eq!(GameState::with_seed(4).mode(), GameMode::Survival);
+}
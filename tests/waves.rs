@@ -0,0 +1,37 @@
+use your_excellency::waves::{WavePhase, Waves, EARLY_GOLD_PER_SECOND};
+use your_excellency::GameState;
+
+#[test]
+fn calling_early_skips_the_rest_of_the_break() {
+    let mut waves = Waves::default();
+    assert_eq!(waves.call_early(), None);
+    waves.tick(waves.active_duration);
+    waves.tick(4.);
+    assert_eq!(waves.countdown(), waves.break_duration - 4.);
+    assert_eq!(waves.call_early(), Some(waves.break_duration - 4.));
+    assert!(waves.tick(0.));
+    assert_eq!(waves.number, 2);
+    assert!(waves.phase == WavePhase::Active);
+}
+
+#[test]
+fn calling_a_wave_early_pays_for_the_time_skipped() {
+    let mut state = GameState::with_seed(3);
+    assert!(!state.call_wave_early());
+    while state.waves().phase == WavePhase::Active {
+        state.step(0.5);
+    }
+    let skipped = state.waves().timer.remaining;
+    let gold = state.excellency().gold;
+    assert_eq!(
+        state.early_call_gold(),
+        (skipped * EARLY_GOLD_PER_SECOND).round() as u32
+    );
+    assert!(state.call_wave_early());
+    assert_eq!(
+        state.excellency().gold,
+        gold + (skipped * EARLY_GOLD_PER_SECOND).round() as u32
+    );
+    state.step(0.01);
+    assert_eq!(state.waves().number, 2);
+}
@@ -0,0 +1,38 @@
+use your_excellency::world::{Components, Entities, Entity};
+
+#[test]
+fn components_stay_sorted_by_entity() {
+    let mut components = Components::default();
+    components.insert(Entity(5), "five");
+    components.insert(Entity(1), "one");
+    assert_eq!(components.insert(Entity(5), "FIVE"), Some("five"));
+
+    let entities: Vec<Entity> = components.iter().map(|(entity, _)| entity).collect();
+    assert_eq!(entities, [Entity(1), Entity(5)]);
+    assert_eq!(components.get(Entity(5)), Some(&"FIVE"));
+    assert_eq!(components.get(Entity(3)), None);
+}
+
+#[test]
+fn retain_alive_drops_despawned_entities() {
+    let mut components = Components::default();
+    for id in 0..5 {
+        *components.entry(Entity(id)) += id;
+    }
+    components.retain_alive(&[Entity(1), Entity(3)]);
+    assert_eq!(components.len(), 2);
+    assert_eq!(components.get(Entity(3)), Some(&3));
+}
+
+#[test]
+fn adopt_replaces_duplicate_handles() {
+    let mut entities = Entities::default();
+    let mut handles = [Entity(0); 3];
+    entities.adopt(handles.iter_mut());
+    assert_eq!(handles, [Entity(0), Entity(1), Entity(2)]);
+    assert_eq!(entities.spawn(), Entity(3));
+
+    // Already distinct and handed out, left alone
+    entities.adopt(handles.iter_mut());
+    assert_eq!(handles, [Entity(0), Entity(1), Entity(2)]);
+}